@@ -1,11 +1,18 @@
 pub(crate) mod admin {
-    use cosmwasm_std::{ensure, Addr, BlockInfo, MessageInfo, Response};
+    use std::mem::discriminant;
+
+    use cosmwasm_std::{
+        ensure, Addr, BlockInfo, Empty, MessageInfo, Order, Response, StdResult, Uint64,
+    };
     use cw_ownable::{assert_owner, update_ownership, Action};
     use neutron_sdk::{bindings::msg::NeutronMsg, NeutronResult};
 
     use crate::{
-        contract::ExecuteDeps, error::ContractError, orbital_domain::UncheckedOrbitalDomainConfig,
-        state::ORBITAL_DOMAINS,
+        contract::ExecuteDeps,
+        error::ContractError,
+        orbital_domain::UncheckedOrbitalDomainConfig,
+        state::{IntentStatus, CONFIG, INTENTS, ORBITAL_DOMAINS, PAUSED_DOMAINS, USER_CONFIGS},
+        utils::{validate_config, validate_domain},
     };
 
     pub fn try_update_ownership(
@@ -28,7 +35,8 @@ pub(crate) mod admin {
         // only the owner can register new domains
         assert_owner(deps.storage, &info.sender).map_err(ContractError::Ownership)?;
 
-        // validate the domain configuration
+        // validate the domain identifier and configuration
+        validate_domain(&domain)?;
         let orbital_domain = account_type.try_into_checked(deps.api)?;
 
         // ensure the domain does not already exist
@@ -37,9 +45,6 @@ pub(crate) mod admin {
             ContractError::OrbitalDomainAlreadyExists(domain.to_string())
         );
 
-        // TODO: ensure that the domain identifier is fine to use as part of
-        // ica identifier?
-
         // store the validated domain config in state
         ORBITAL_DOMAINS.save(deps.storage, domain.to_string(), &orbital_domain)?;
 
@@ -47,4 +52,138 @@ pub(crate) mod admin {
             .add_attribute("method", "register_new_domain")
             .add_attribute("domain", domain))
     }
+
+    pub fn try_update_domain(
+        deps: ExecuteDeps,
+        info: MessageInfo,
+        domain: String,
+        account_type: UncheckedOrbitalDomainConfig,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        assert_owner(deps.storage, &info.sender).map_err(ContractError::Ownership)?;
+
+        let current_domain = ORBITAL_DOMAINS
+            .may_load(deps.storage, domain.to_string())?
+            .ok_or_else(|| ContractError::UnknownDomain(domain.to_string()))?;
+        let orbital_domain = account_type.try_into_checked(deps.api)?;
+
+        // existing clearing accounts were created through the current account
+        // type, so only its parameters (e.g. connection or timeout) can change
+        ensure!(
+            discriminant(&current_domain) == discriminant(&orbital_domain),
+            ContractError::DomainAccountTypeMismatch(domain)
+        );
+
+        ORBITAL_DOMAINS.save(deps.storage, domain.to_string(), &orbital_domain)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_domain")
+            .add_attribute("domain", domain))
+    }
+
+    pub fn try_pause_domain(
+        deps: ExecuteDeps,
+        info: MessageInfo,
+        domain: String,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        assert_owner(deps.storage, &info.sender).map_err(ContractError::Ownership)?;
+
+        ensure!(
+            ORBITAL_DOMAINS.has(deps.storage, domain.to_string()),
+            ContractError::UnknownDomain(domain)
+        );
+        ensure!(
+            !PAUSED_DOMAINS.has(deps.storage, domain.to_string()),
+            ContractError::DomainPaused(domain)
+        );
+
+        PAUSED_DOMAINS.save(deps.storage, domain.to_string(), &Empty {})?;
+
+        Ok(Response::default()
+            .add_attribute("method", "pause_domain")
+            .add_attribute("domain", domain))
+    }
+
+    pub fn try_unpause_domain(
+        deps: ExecuteDeps,
+        info: MessageInfo,
+        domain: String,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        assert_owner(deps.storage, &info.sender).map_err(ContractError::Ownership)?;
+
+        ensure!(
+            PAUSED_DOMAINS.has(deps.storage, domain.to_string()),
+            ContractError::DomainNotPaused(domain)
+        );
+
+        PAUSED_DOMAINS.remove(deps.storage, domain.to_string());
+
+        Ok(Response::default()
+            .add_attribute("method", "unpause_domain")
+            .add_attribute("domain", domain))
+    }
+
+    pub fn try_deregister_domain(
+        deps: ExecuteDeps,
+        info: MessageInfo,
+        domain: String,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        assert_owner(deps.storage, &info.sender).map_err(ContractError::Ownership)?;
+
+        ensure!(
+            ORBITAL_DOMAINS.has(deps.storage, domain.to_string()),
+            ContractError::UnknownDomain(domain)
+        );
+
+        // domains can only be removed once no clearing accounts are held on
+        // them and no active intents move funds into or out of them
+        let has_clearing_accounts = USER_CONFIGS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, user_config)| user_config.registered_domains))
+            .collect::<StdResult<Vec<_>>>()?
+            .into_iter()
+            .any(|registered_domains| registered_domains.contains(&domain));
+        let has_active_intents = INTENTS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, intent)| intent))
+            .collect::<StdResult<Vec<_>>>()?
+            .into_iter()
+            .any(|intent| {
+                matches!(
+                    intent.status,
+                    IntentStatus::Open | IntentStatus::Auctioning | IntentStatus::Won
+                ) && (intent.input_domain == domain || intent.destination_domain == domain)
+            });
+        ensure!(
+            !has_clearing_accounts && !has_active_intents,
+            ContractError::DomainInUse(domain)
+        );
+
+        ORBITAL_DOMAINS.remove(deps.storage, domain.to_string());
+        PAUSED_DOMAINS.remove(deps.storage, domain.to_string());
+
+        Ok(Response::default()
+            .add_attribute("method", "deregister_domain")
+            .add_attribute("domain", domain))
+    }
+
+    pub fn try_update_tx_timeouts(
+        deps: ExecuteDeps,
+        info: MessageInfo,
+        min_tx_timeout: Uint64,
+        max_tx_timeout: Uint64,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        assert_owner(deps.storage, &info.sender).map_err(ContractError::Ownership)?;
+
+        let mut config = CONFIG.load(deps.storage)?;
+        config.min_tx_timeout = min_tx_timeout;
+        config.max_tx_timeout = max_tx_timeout;
+        validate_config(&config)?;
+
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_tx_timeouts")
+            .add_attribute("min_tx_timeout", min_tx_timeout)
+            .add_attribute("max_tx_timeout", max_tx_timeout))
+    }
 }
@@ -0,0 +1,208 @@
+pub(crate) mod auction {
+    use cosmwasm_std::{
+        ensure, BlockInfo, Empty, Env, MessageInfo, Response, StdResult, Storage, Uint128,
+    };
+    use cw_utils::Expiration;
+    use neutron_sdk::{bindings::msg::NeutronMsg, NeutronResult};
+
+    use crate::{
+        contract::ExecuteDeps,
+        error::ContractError,
+        intent_logic::intent,
+        state::{
+            Auction, Bid, IntentStatus, SolverConfig, AUCTIONS, AWAITING_FILLS, CONFIG, INTENTS,
+            SOLVERS, SOLVER_BIDS, SOLVER_DOMAIN_ADDRESSES,
+        },
+        utils::{get_clearing_account, get_remote_height},
+    };
+
+    pub fn try_bid(
+        deps: ExecuteDeps,
+        env: Env,
+        info: MessageInfo,
+        intent_id: u64,
+        amount: Uint128,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let config = CONFIG.load(deps.storage)?;
+
+        // only solvers holding at least the minimum bond which is not reserved
+        // by other won auctions can bid
+        let solver_config = SOLVERS
+            .may_load(deps.storage, info.sender.to_string())?
+            .ok_or(ContractError::SolverNotRegistered {})?;
+        ensure!(
+            free_bond(&solver_config) >= config.solver_bond.amount,
+            ContractError::SolverNotBonded {}
+        );
+
+        let mut intent = intent::load_intent(deps.storage, &env.block, intent_id)?;
+        let mut auction = AUCTIONS.load(deps.storage, intent_id)?;
+
+        ensure!(
+            matches!(intent.status, IntentStatus::Open | IntentStatus::Auctioning),
+            ContractError::UnexpectedIntentStatus(intent.status)
+        );
+        ensure!(
+            !auction.end.is_expired(&env.block),
+            ContractError::AuctionEnded {}
+        );
+        // solver must be able to receive the payout on the input domain
+        // and to send the fill from the destination domain
+        for domain in [&intent.input_domain, &intent.destination_domain] {
+            ensure!(
+                SOLVER_DOMAIN_ADDRESSES
+                    .has(deps.storage, (info.sender.to_string(), domain.to_string())),
+                ContractError::SolverDomainAddressNotSet(domain.to_string())
+            );
+        }
+
+        // first bid must cover the minimum amount requested by the intent.
+        // every following bid must outbid the highest one by the minimum increment.
+        let min_bid = match &auction.highest_bid {
+            Some(highest_bid) => highest_bid.amount.checked_add(config.min_bid_increment)?,
+            None => intent.destination_coin.amount,
+        };
+        ensure!(amount >= min_bid, ContractError::BidTooLow(min_bid));
+
+        auction.highest_bid = Some(Bid {
+            solver: info.sender.clone(),
+            amount,
+        });
+        intent.status = IntentStatus::Auctioning;
+
+        AUCTIONS.save(deps.storage, intent_id, &auction)?;
+        INTENTS.save(deps.storage, intent_id, &intent)?;
+        SOLVER_BIDS.save(
+            deps.storage,
+            (info.sender.to_string(), intent_id),
+            &Empty {},
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("method", "bid")
+            .add_attribute("intent_id", intent_id.to_string())
+            .add_attribute("solver", info.sender)
+            .add_attribute("amount", amount.to_string()))
+    }
+
+    pub fn try_close_auction(
+        deps: ExecuteDeps,
+        env: Env,
+        intent_id: u64,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let mut intent = intent::load_intent(deps.storage, &env.block, intent_id)?;
+        let mut auction = AUCTIONS.load(deps.storage, intent_id)?;
+
+        ensure!(
+            intent.status == IntentStatus::Auctioning,
+            ContractError::UnexpectedIntentStatus(intent.status)
+        );
+        ensure!(
+            auction.end.is_expired(&env.block),
+            ContractError::AuctionInProgress {}
+        );
+
+        // highest bidder is granted the exclusive execution rights
+        let winning_bid = auction
+            .highest_bid
+            .clone()
+            .ok_or(ContractError::AuctionHasNoBids {})?;
+
+        let config = CONFIG.load(deps.storage)?;
+
+        // winners which were slashed or whose bond got reserved by other
+        // auctions since bidding forfeit the auction, which is opened again
+        let winner_config = SOLVERS.may_load(deps.storage, winning_bid.solver.to_string())?;
+        let Some(mut winner_config) = winner_config
+            .filter(|solver_config| free_bond(solver_config) >= config.solver_bond.amount)
+        else {
+            let end = open_auction(deps.storage, &env.block, intent_id)?;
+            intent.status = IntentStatus::Open;
+            INTENTS.save(deps.storage, intent_id, &intent)?;
+
+            return Ok(Response::new()
+                .add_attribute("method", "close_auction")
+                .add_attribute("intent_id", intent_id.to_string())
+                .add_attribute("forfeited_by", winning_bid.solver)
+                .add_attribute("auction_end", end.to_string()));
+        };
+
+        // winner's bond backs the intent until it is filled
+        winner_config.reserved = winner_config
+            .reserved
+            .checked_add(config.solver_bond.amount)?;
+        SOLVERS.save(deps.storage, winning_bid.solver.to_string(), &winner_config)?;
+        auction.reserved_bond = config.solver_bond.amount;
+
+        let fill_deadline = config.fill_timeout.after(&env.block);
+
+        // fills are only accepted from the address the winner holds on the
+        // destination domain as of now, later changes do not affect the auction
+        let fill_sender = SOLVER_DOMAIN_ADDRESSES
+            .may_load(
+                deps.storage,
+                (
+                    winning_bid.solver.to_string(),
+                    intent.destination_domain.to_string(),
+                ),
+            )?
+            .ok_or(ContractError::SolverDomainAddressNotSet(
+                intent.destination_domain.to_string(),
+            ))?;
+
+        auction.winner = Some(winning_bid.solver.clone());
+        auction.fill_sender = Some(fill_sender);
+        auction.fill_deadline = Some(fill_deadline);
+        // deposits made before the auction closed can not count as its fill
+        auction.min_fill_height = get_remote_height(deps.storage, &intent.destination_domain)?;
+        intent.status = IntentStatus::Won;
+
+        // the fill is expected to arrive in the owner's clearing account
+        // on the destination domain
+        let recipient =
+            get_clearing_account(deps.storage, &intent.owner, &intent.destination_domain)?;
+
+        AUCTIONS.save(deps.storage, intent_id, &auction)?;
+        INTENTS.save(deps.storage, intent_id, &intent)?;
+        AWAITING_FILLS.save(deps.storage, (recipient.addr, intent_id), &Empty {})?;
+
+        Ok(Response::new()
+            .add_attribute("method", "close_auction")
+            .add_attribute("intent_id", intent_id.to_string())
+            .add_attribute("winner", winning_bid.solver)
+            .add_attribute("amount", winning_bid.amount.to_string())
+            .add_attribute("fill_deadline", fill_deadline.to_string()))
+    }
+
+    /// returns the bond of the solver which is not reserved by won auctions
+    fn free_bond(solver_config: &SolverConfig) -> Uint128 {
+        solver_config.bond.saturating_sub(solver_config.reserved)
+    }
+
+    /// opens a fresh auction for the execution rights of the given intent,
+    /// replacing any previous auction. returns the auction end.
+    pub fn open_auction(
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        intent_id: u64,
+    ) -> StdResult<Expiration> {
+        let config = CONFIG.load(storage)?;
+        let end = config.auction_duration.after(block);
+
+        AUCTIONS.save(
+            storage,
+            intent_id,
+            &Auction {
+                end,
+                highest_bid: None,
+                winner: None,
+                fill_deadline: None,
+                min_fill_height: 0,
+                fill_sender: None,
+                reserved_bond: Uint128::zero(),
+            },
+        )?;
+
+        Ok(end)
+    }
+}
@@ -1,26 +1,40 @@
 use crate::{
     admin_logic::admin,
+    auction_logic::auction,
+    ica,
     icq::{self},
+    intent_logic::intent,
+    migrations,
     msg::{GetTransfersAmountResponse, RecipientTxsResponse},
+    polytone,
+    solver_logic::solver,
     state::{
-        ClearingAccountConfig, OrbitalDomainConfig, UserConfig, RECIPIENT_TXS, TRANSFERS,
-        USER_NONCE,
+        Auction, ClearingAccountConfig, ClearingAccountQueries, ClearingAccountStatus,
+        ClearingBalance, Config, IcaTx, IcqDeposit, Intent, OrbitalDomainConfig, SolverConfig,
+        UserConfig, AUCTIONS, CLEARING_ACCOUNT_QUERIES, CLEARING_BALANCES, CONFIG,
+        CREDITED_DEPOSITS, ICA_TXS, ICQ_DEPOSITS, INTENTS, INTENT_NONCE, LOCKED_BALANCES,
+        PAUSED_DOMAINS, PAYOUTS, PENDING_ICQ_REGISTRATIONS, RECIPIENT_TXS, SOLVERS,
+        SOLVER_DOMAIN_ADDRESSES, TRANSFERS, USER_ICA_TXS, USER_ICQS, USER_INTENTS, USER_NONCE,
     },
     user_logic::user,
-    utils::{extract_ica_identifier_from_port, get_ica_identifier, OpenAckVersion},
+    utils::{
+        extract_ica_identifier_from_port, get_ica_identifier, resolve_ica_identifier,
+        validate_config, OpenAckVersion,
+    },
 };
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    state::{CLEARING_ACCOUNTS, ORBITAL_DOMAINS, USER_CONFIGS},
+    state::{CLEARING_ACCOUNTS, CLEARING_ACCOUNT_ADDRESSES, ORBITAL_DOMAINS, USER_CONFIGS},
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
-    StdResult, Uint64,
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
+    StdError, StdResult, Uint128, Uint64,
 };
-use cw2::set_contract_version;
+use cw2::{ensure_from_older_version, set_contract_version};
 use cw_ownable::{get_ownership, initialize_owner};
+use cw_storage_plus::Bound;
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     interchain_queries::v047::queries::{query_balance, BalanceResponse},
@@ -31,6 +45,10 @@ use neutron_sdk::{
 pub const CONTRACT_NAME: &str = "orbital-core";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// page size limits of paginated queries
+const DEFAULT_QUERY_LIMIT: u32 = 10;
+const MAX_QUERY_LIMIT: u32 = 30;
+
 pub type QueryDeps<'a> = Deps<'a, NeutronQuery>;
 pub type ExecuteDeps<'a> = DepsMut<'a, NeutronQuery>;
 
@@ -44,7 +62,21 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
 
+    let config = Config {
+        solver_bond: msg.solver_bond,
+        unbonding_period: msg.unbonding_period,
+        auction_duration: msg.auction_duration,
+        min_bid_increment: msg.min_bid_increment,
+        fill_timeout: msg.fill_timeout,
+        slashed_bond_user_share: msg.slashed_bond_user_share,
+        min_tx_timeout: msg.min_tx_timeout,
+        max_tx_timeout: msg.max_tx_timeout,
+    };
+    validate_config(&config)?;
+
+    CONFIG.save(deps.storage, &config)?;
     USER_NONCE.save(deps.storage, &Uint64::zero())?;
+    INTENT_NONCE.save(deps.storage, &Uint64::zero())?;
     Ok(Response::new())
 }
 
@@ -63,41 +95,189 @@ pub fn execute(
             domain,
             account_type,
         } => admin::try_register_new_domain(deps, info, domain, account_type),
+        // admin actions to manage the lifecycle of registered domains
+        ExecuteMsg::UpdateDomain {
+            domain,
+            account_type,
+        } => admin::try_update_domain(deps, info, domain, account_type),
+        ExecuteMsg::PauseDomain { domain } => admin::try_pause_domain(deps, info, domain),
+        ExecuteMsg::UnpauseDomain { domain } => admin::try_unpause_domain(deps, info, domain),
+        ExecuteMsg::DeregisterDomain { domain } => admin::try_deregister_domain(deps, info, domain),
+        ExecuteMsg::UpdateTxTimeouts {
+            min_tx_timeout,
+            max_tx_timeout,
+        } => admin::try_update_tx_timeouts(deps, info, min_tx_timeout, max_tx_timeout),
         // user action to create a new user account which enables registration to domains
         ExecuteMsg::RegisterUser {} => user::try_register(deps, env, info),
         // user action to register a new domain which creates their clearing account
         ExecuteMsg::RegisterUserDomain { domain } => {
             user::try_register_new_domain(deps, env, info, domain)
         }
+        // user action to regain control of a clearing account whose channel was closed
+        ExecuteMsg::ReopenClearingAccount { domain } => {
+            user::try_reopen_clearing_account(deps, env, info, domain)
+        }
         // user action to withdraw funds from a selected domain account they own
-        ExecuteMsg::UserWithdrawFunds { domain, coin, dest } => {
-            user::try_withdraw_from_remote_domain(deps, info, domain, coin, dest)
+        ExecuteMsg::UserWithdrawFunds {
+            domain,
+            withdrawals,
+            timeout,
+            memo,
+        } => user::try_withdraw_from_remote_domain(
+            deps,
+            env,
+            info,
+            domain,
+            withdrawals,
+            timeout,
+            memo,
+        ),
+        // user action to transfer funds from a domain account they own to another domain
+        ExecuteMsg::UserTransferFunds {
+            domain,
+            coin,
+            dest_domain,
+            dest,
+            timeout,
+            memo,
+        } => user::try_transfer_from_remote_domain(
+            deps,
+            env,
+            info,
+            domain,
+            coin,
+            dest_domain,
+            dest,
+            timeout,
+            memo,
+        ),
+        // user action to submit an intent to be fulfilled by solvers
+        ExecuteMsg::SubmitIntent {
+            input_domain,
+            input_coin,
+            destination_domain,
+            destination_coin,
+            deadline,
+        } => intent::try_submit_intent(
+            deps,
+            env,
+            info,
+            input_domain,
+            input_coin,
+            destination_domain,
+            destination_coin,
+            deadline,
+        ),
+        ExecuteMsg::CancelIntent { id, release_to } => {
+            intent::try_cancel_intent(deps, env, info, id, release_to)
+        }
+        // solver actions to manage their bond in the solver registry
+        ExecuteMsg::RegisterSolver {} => solver::try_register_solver(deps, info),
+        ExecuteMsg::SetSolverDomainAddress { domain, addr } => {
+            solver::try_set_domain_address(deps, info, domain, addr)
         }
+        ExecuteMsg::IncreaseBond {} => solver::try_increase_bond(deps, info),
+        ExecuteMsg::UnbondSolver {} => solver::try_unbond(deps, env, info),
+        ExecuteMsg::ClaimUnbonded {} => solver::try_claim_unbonded(deps, env, info),
+        // solver action to bid on intent execution rights
+        ExecuteMsg::Bid { intent_id, amount } => {
+            auction::try_bid(deps, env, info, intent_id, amount)
+        }
+        // permissionless action to settle an ended auction
+        ExecuteMsg::CloseAuction { intent_id } => auction::try_close_auction(deps, env, intent_id),
+        // permissionless action to slash a winner who missed the fill deadline
+        ExecuteMsg::SlashExpired { intent_id } => solver::try_slash_expired(deps, env, intent_id),
+        // permissionless action to resubmit a failed payout to the solver
+        ExecuteMsg::RetryPayout { intent_id } => intent::try_retry_payout(deps, env, intent_id),
         ExecuteMsg::RegisterBalancesQuery {
             connection_id,
             update_period,
             addr,
             denoms,
-        } => icq::register_balances_query(connection_id, addr, denoms, update_period),
+        } => icq::register_balances_query(deps, info, connection_id, addr, denoms, update_period),
         ExecuteMsg::RegisterTransfersQuery {
             connection_id,
             update_period,
             recipient,
             min_height,
-        } => icq::register_transfers_query(connection_id, recipient, update_period, min_height),
+        } => icq::register_transfers_query(
+            deps,
+            info,
+            connection_id,
+            recipient,
+            update_period,
+            min_height,
+        ),
+        ExecuteMsg::UpdateInterchainQuery {
+            query_id,
+            new_keys,
+            new_update_period,
+            new_transactions_filter,
+        } => icq::update_interchain_query(
+            deps,
+            info,
+            query_id,
+            new_keys,
+            new_update_period,
+            new_transactions_filter,
+        ),
+        ExecuteMsg::RemoveInterchainQuery { query_id } => {
+            icq::remove_interchain_query(deps, info, query_id)
+        }
+        ExecuteMsg::Callback(callback) => polytone::try_handle_callback(deps, env, info, callback),
     }
 }
 
 #[entry_point]
 pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::OrbitalDomain { domain } => to_json_binary(&query_orbital_domain(deps, domain)?),
+        QueryMsg::DomainPaused { domain } => {
+            to_json_binary(&PAUSED_DOMAINS.has(deps.storage, domain))
+        }
         QueryMsg::UserConfig { addr } => to_json_binary(&query_user_config(deps, addr)?),
         QueryMsg::Ownership {} => to_json_binary(&query_ownership(deps)?),
         QueryMsg::ClearingAccountAddress { addr, domain } => {
             to_json_binary(&query_clearing_account(deps, domain, addr)?)
         }
+        QueryMsg::Solver { addr } => to_json_binary(&query_solver(deps, addr)?),
+        QueryMsg::SolverDomainAddress { addr, domain } => {
+            to_json_binary(&SOLVER_DOMAIN_ADDRESSES.may_load(deps.storage, (addr, domain))?)
+        }
+        QueryMsg::Intent { id } => to_json_binary(&query_intent(deps, env, id)?),
+        QueryMsg::UserIntents { addr } => to_json_binary(&query_user_intents(deps, env, addr)?),
+        QueryMsg::Auction { intent_id } => to_json_binary(&query_auction(deps, intent_id)?),
+        QueryMsg::Payout { intent_id } => to_json_binary(&PAYOUTS.load(deps.storage, intent_id)?),
+        QueryMsg::IcaTx { id } => to_json_binary(&ICA_TXS.load(deps.storage, id)?),
+        QueryMsg::UserIcaTxs {
+            addr,
+            start_after,
+            limit,
+        } => to_json_binary(&query_user_ica_txs(deps, addr, start_after, limit)?),
+        QueryMsg::UserBalances { addr, domain } => {
+            to_json_binary(&query_user_balances(deps, addr, domain)?)
+        }
+        QueryMsg::UserDeposits { addr, domain } => {
+            to_json_binary(&query_user_deposits(deps, addr, domain)?)
+        }
+        QueryMsg::LockedBalance {
+            addr,
+            domain,
+            denom,
+        } => to_json_binary(&query_locked_balance(deps, addr, domain, denom)?),
         QueryMsg::Balance { query_id } => to_json_binary(&query_icq_balance(deps, env, query_id)?),
+        QueryMsg::IcqDeposit { query_id } => {
+            to_json_binary(&ICQ_DEPOSITS.load(deps.storage, query_id)?)
+        }
+        QueryMsg::ClearingAccountQueries { addr, domain } => {
+            to_json_binary(&query_clearing_account_queries(deps, addr, domain)?)
+        }
+        QueryMsg::UserIcqs {
+            addr,
+            start_after,
+            limit,
+        } => to_json_binary(&query_user_icqs(deps, addr, start_after, limit)?),
         QueryMsg::IcqTransfersAmount {} => to_json_binary(&query_transfers_number(deps)?),
         QueryMsg::IcqRecipientTxs { recipient } => {
             to_json_binary(&query_recipient_txs(deps, recipient)?)
@@ -105,6 +285,114 @@ pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     }
 }
 
+fn query_clearing_account_queries(
+    deps: QueryDeps,
+    addr: String,
+    domain: String,
+) -> StdResult<ClearingAccountQueries> {
+    Ok(CLEARING_ACCOUNT_QUERIES
+        .may_load(deps.storage, (addr, domain))?
+        .unwrap_or_default())
+}
+
+fn query_user_icqs(
+    deps: QueryDeps,
+    addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, IcqDeposit)>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    USER_ICQS
+        .prefix(addr)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|id| id.and_then(|id| Ok((id, ICQ_DEPOSITS.load(deps.storage, id)?))))
+        .collect()
+}
+
+fn query_solver(deps: QueryDeps, addr: String) -> StdResult<SolverConfig> {
+    SOLVERS.load(deps.storage, addr)
+}
+
+fn query_intent(deps: QueryDeps, env: Env, id: u64) -> StdResult<Intent> {
+    let mut intent = INTENTS.load(deps.storage, id)?;
+    intent.apply_expiry(&env.block);
+    Ok(intent)
+}
+
+fn query_user_intents(deps: QueryDeps, env: Env, addr: String) -> StdResult<Vec<Intent>> {
+    USER_INTENTS
+        .prefix(addr)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|id| id.and_then(|id| query_intent(deps, env.clone(), id)))
+        .collect()
+}
+
+fn query_user_ica_txs(
+    deps: QueryDeps,
+    addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, IcaTx)>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    USER_ICA_TXS
+        .prefix(addr)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|id| id.and_then(|id| Ok((id, ICA_TXS.load(deps.storage, id)?))))
+        .collect()
+}
+
+fn query_auction(deps: QueryDeps, intent_id: u64) -> StdResult<Auction> {
+    AUCTIONS.load(deps.storage, intent_id)
+}
+
+fn query_user_balances(
+    deps: QueryDeps,
+    addr: String,
+    domain: String,
+) -> StdResult<Vec<(String, ClearingBalance)>> {
+    CLEARING_BALANCES
+        .prefix((addr, domain))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+fn query_user_deposits(
+    deps: QueryDeps,
+    addr: String,
+    domain: String,
+) -> StdResult<Vec<(String, Uint128)>> {
+    CREDITED_DEPOSITS
+        .prefix((addr, domain))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+fn query_locked_balance(
+    deps: QueryDeps,
+    addr: String,
+    domain: String,
+    denom: String,
+) -> StdResult<Uint128> {
+    let locked = LOCKED_BALANCES
+        .may_load(deps.storage, (addr, domain, denom))?
+        .unwrap_or_default();
+    Ok(locked)
+}
+
 fn query_recipient_txs(deps: QueryDeps, recipient: String) -> StdResult<RecipientTxsResponse> {
     let txs = RECIPIENT_TXS
         .may_load(deps.storage, recipient)?
@@ -145,13 +433,37 @@ fn query_user_config(deps: QueryDeps, user: String) -> StdResult<UserConfig> {
 }
 
 #[entry_point]
-pub fn reply(_deps: ExecuteDeps, _env: Env, _msg: Reply) -> StdResult<Response<NeutronMsg>> {
-    unimplemented!()
+pub fn reply(deps: ExecuteDeps, env: Env, msg: Reply) -> StdResult<Response<NeutronMsg>> {
+    match msg.id {
+        icq::REGISTER_BALANCES_QUERY_REPLY_ID => icq::reply_register_balances_query(deps, msg),
+        icq::REGISTER_TRANSFERS_QUERY_REPLY_ID => icq::reply_register_transfers_query(deps, msg),
+        ica::SUBMIT_TX_REPLY_ID => ica::reply_submit_tx(deps, env, msg),
+        ica::REGISTER_ICA_REPLY_ID => ica::reply_register_ica(deps, msg),
+        _ => Err(StdError::generic_err(format!(
+            "unknown reply id: {}",
+            msg.id
+        ))),
+    }
 }
 
 #[entry_point]
-pub fn migrate(_deps: ExecuteDeps, _env: Env, _msg: MigrateMsg) -> StdResult<Response<NeutronMsg>> {
-    unimplemented!()
+pub fn migrate(deps: ExecuteDeps, _env: Env, msg: MigrateMsg) -> StdResult<Response<NeutronMsg>> {
+    // only migrations of orbital core to the same or a newer version are
+    // allowed. the stored version is bumped to the current one.
+    let previous_version =
+        ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let response = match msg {
+        MigrateMsg::UpdateVersion {} => Response::new().add_attribute("method", "update_version"),
+        MigrateMsg::MigrateIcaIdentifiers {} => migrations::migrate_ica_identifiers(deps.storage)?,
+        MigrateMsg::UpdateConfig { config } => migrations::update_config(deps.storage, config)?,
+        MigrateMsg::IndexDomainAddresses {} => migrations::index_domain_addresses(deps.storage)?,
+        MigrateMsg::MigrateAll { config } => migrations::migrate_all(deps.storage, config)?,
+    };
+
+    Ok(response
+        .add_attribute("previous_version", previous_version.to_string())
+        .add_attribute("version", CONTRACT_VERSION))
 }
 
 // neutron uses the `sudo` entry point in their ICA/ICQ related logic
@@ -181,6 +493,10 @@ pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response<Neu
 
         // For handling kv query result
         SudoMsg::KVQueryResult { query_id } => icq::sudo_kv_query_result(deps, env, query_id),
+        // For handling the outcome of txs submitted through clearing accounts
+        SudoMsg::Response { request, data } => ica::sudo_response(deps, env, request, data),
+        SudoMsg::Error { request, details } => ica::sudo_error(deps, env, request, details),
+        SudoMsg::Timeout { request } => ica::sudo_timeout(deps, env, request),
         _ => Ok(Response::default()),
     }
 }
@@ -188,7 +504,7 @@ pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response<Neu
 // handler
 fn sudo_open_ack(
     deps: ExecuteDeps,
-    _env: Env,
+    env: Env,
     port_id: String,
     _channel_id: String,
     _counterparty_channel_id: String,
@@ -199,16 +515,48 @@ fn sudo_open_ack(
         serde_json_wasm::from_str(counterparty_version.as_str())
             .map_err(|_| StdError::generic_err("Can't parse counterparty_version"))?;
 
-    // extract the ICA identifier from the port
-    let ica_identifier = extract_ica_identifier_from_port(port_id)?;
+    // extract the ICA identifier from the port and resolve its clearing account
+    let ica_identifier =
+        resolve_ica_identifier(deps.storage, &extract_ica_identifier_from_port(port_id)?)?;
 
     let clearing_account_config = ClearingAccountConfig {
         addr: parsed_version.address,
         controller_connection_id: parsed_version.controller_connection_id,
+        status: ClearingAccountStatus::Open,
     };
 
+    // new clearing accounts are monitored by interchain queries from birth,
+    // while reopened accounts are still monitored by their original queries
+    let query_submsgs =
+        match PENDING_ICQ_REGISTRATIONS.may_load(deps.storage, ica_identifier.to_string())? {
+            Some(pending_registration) => {
+                PENDING_ICQ_REGISTRATIONS.remove(deps.storage, ica_identifier.to_string());
+                icq::get_clearing_account_query_submsgs(
+                    pending_registration,
+                    clearing_account_config.controller_connection_id.to_string(),
+                    clearing_account_config.addr.to_string(),
+                )?
+            }
+            None => vec![],
+        };
+
     // Update the storage record associated with the interchain account.
-    CLEARING_ACCOUNTS.save(deps.storage, ica_identifier, &Some(clearing_account_config))?;
+    CLEARING_ACCOUNT_ADDRESSES.save(
+        deps.storage,
+        clearing_account_config.addr.to_string(),
+        &ica_identifier,
+    )?;
+    CLEARING_ACCOUNTS.save(
+        deps.storage,
+        ica_identifier.to_string(),
+        &Some(clearing_account_config),
+    )?;
+
+    // payouts which did not complete over the closed channel are resubmitted
+    let payout_submsgs = ica::get_reopened_account_payout_txs(deps, &env, &ica_identifier)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
 
-    Ok(Response::default())
+    Ok(Response::default()
+        .add_submessages(query_submsgs)
+        .add_submessages(payout_submsgs))
 }
@@ -1,9 +1,11 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError, Uint128, Uint64};
 use cw_ownable::OwnershipError;
 use cw_utils::PaymentError;
 use neutron_sdk::NeutronError;
 use thiserror::Error;
 
+use crate::state::{IntentStatus, PayoutStatus};
+
 #[derive(Error, Debug)]
 pub enum ContractError {
     #[error("{0}")]
@@ -24,14 +26,131 @@ pub enum ContractError {
     #[error("User not registered")]
     UserNotRegistered {},
 
+    #[error("User already registered to domain: {0}")]
+    UserAlreadyRegisteredToDomain(String),
+
+    #[error("ICA identifier already in use: {0}")]
+    IcaIdentifierInUse(String),
+
     #[error("User not registered to domain: {0}")]
     UserNotRegisteredToDomain(String),
 
     #[error("Unknown domain: {0}")]
     UnknownDomain(String),
 
+    #[error("No transfer channel from {0} to {1}")]
+    TransferChannelNotFound(String, String),
+
+    #[error("Invalid domain identifier: {0}")]
+    InvalidDomainIdentifier(String),
+
+    #[error("Domain paused: {0}")]
+    DomainPaused(String),
+
+    #[error("Domain in use: {0}")]
+    DomainInUse(String),
+
+    #[error("Domain not paused: {0}")]
+    DomainNotPaused(String),
+
+    #[error("Domain account type can not be changed: {0}")]
+    DomainAccountTypeMismatch(String),
+
+    #[error("Clearing account closed on domain: {0}")]
+    ClearingAccountClosed(String),
+
+    #[error("Clearing account not closed on domain: {0}")]
+    ClearingAccountNotClosed(String),
+
+    #[error("Unauthorized polytone callback")]
+    UnauthorizedCallback {},
+
     #[error("Domain registration error: {0}")]
     DomainRegistrationError(String),
+
+    #[error("Insufficient available balance, available: {0}")]
+    InsufficientAvailableBalance(Coin),
+
+    #[error("Insufficient interchain query deposit, required: {0}")]
+    InsufficientIcqDeposit(Coin),
+
+    #[error("Not authorized to register an interchain query for: {0}")]
+    UnauthorizedQuery(String),
+
+    #[error("Transfers query already registered for: {0}")]
+    TransfersQueryExists(String),
+
+    #[error("Only the owner of interchain query {0} can modify it")]
+    NotQueryOwner(u64),
+
+    #[error("Only the update period of interchain query {0} can be changed")]
+    UnauthorizedQueryUpdate(u64),
+
+    #[error("Tx timeout of {0} seconds outside of bounds [{1}, {2}]")]
+    InvalidTxTimeout(Uint64, Uint64, Uint64),
+
+    #[error("Withdrawal batch is empty")]
+    EmptyWithdrawalBatch {},
+
+    #[error("Withdrawal batch too large: {0} withdrawals, max {1}")]
+    WithdrawalBatchTooLarge(usize, u64),
+
+    #[error("Invalid intent: {0}")]
+    InvalidIntent(String),
+
+    #[error("Unknown intent: {0}")]
+    UnknownIntent(u64),
+
+    #[error("Only the intent owner can perform this action")]
+    NotIntentOwner {},
+
+    #[error("Unexpected intent status: {0:?}")]
+    UnexpectedIntentStatus(IntentStatus),
+
+    #[error("Unexpected payout status: {0:?}")]
+    UnexpectedPayoutStatus(PayoutStatus),
+
+    #[error("Auction has ended")]
+    AuctionEnded {},
+
+    #[error("Auction still in progress")]
+    AuctionInProgress {},
+
+    #[error("Auction has no bids")]
+    AuctionHasNoBids {},
+
+    #[error("Bid too low, minimum bid is {0}")]
+    BidTooLow(Uint128),
+
+    #[error("Fill deadline not reached")]
+    FillDeadlineNotReached {},
+
+    #[error("Solver already registered")]
+    SolverAlreadyRegistered {},
+
+    #[error("Solver not registered")]
+    SolverNotRegistered {},
+
+    #[error("Insufficient solver bond")]
+    InsufficientBond {},
+
+    #[error("Solver not bonded")]
+    SolverNotBonded {},
+
+    #[error("Solver address not set for domain: {0}")]
+    SolverDomainAddressNotSet(String),
+
+    #[error("Domain address already in use: {0}")]
+    DomainAddressInUse(String),
+
+    #[error("Nothing to unbond")]
+    NothingToUnbond {},
+
+    #[error("No unbonded funds ready to be claimed")]
+    NothingToClaim {},
+
+    #[error("Solver bond backs the leading bid or execution rights of intent {0}")]
+    SolverBondInUse(u64),
 }
 
 impl From<ContractError> for NeutronError {
@@ -0,0 +1,433 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, BlockInfo, Coin, DepsMut, Empty, Env, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
+};
+use neutron_sdk::{
+    bindings::{
+        msg::{MsgRegisterInterchainAccountResponse, MsgSubmitTxResponse, NeutronMsg},
+        query::NeutronQuery,
+    },
+    sudo::msg::RequestPacket,
+    NeutronResult,
+};
+
+use crate::{
+    intent_logic::intent,
+    state::{
+        ClearingAccountStatus, IcaTx, IcaTxKind, IcaTxStatus, PayoutStatus, CLEARING_ACCOUNTS,
+        ICA_TXS, ICA_TX_NONCE, ICA_TX_SEQUENCES, INTENTS, PAYOUTS, PENDING_ICQ_REGISTRATIONS,
+        REOPENING_CLEARING_ACCOUNTS, USER_CONFIGS, USER_ICA_TXS,
+    },
+    utils::{
+        escrow,
+        fees::{get_deposit_refund, get_fee_refund},
+        get_ica_identifier,
+    },
+};
+
+/// reply id of ICA tx submissions. neutron replies with the channel and
+/// sequence of the submitted packet which correlate the later ICA callbacks.
+pub const SUBMIT_TX_REPLY_ID: u64 = 2;
+
+/// reply id of clearing account ICA registrations. neutron replies with
+/// the channel and port on which the registration is being opened.
+pub const REGISTER_ICA_REPLY_ID: u64 = 3;
+
+/// clearing account registration passed along as the reply payload
+#[cw_serde]
+struct IcaRegistration {
+    owner: Addr,
+    domain: String,
+    ica_identifier: String,
+    // whether the registration reopens a closed clearing account
+    #[serde(default)]
+    reopening: bool,
+}
+
+/// wraps the ICA registration of a clearing account into a submessage
+/// whose reply handles both the successful and the failed registration
+pub fn register_clearing_account(
+    register_msg: NeutronMsg,
+    owner: &Addr,
+    domain: &str,
+    ica_identifier: String,
+    reopening: bool,
+) -> StdResult<SubMsg<NeutronMsg>> {
+    let registration = IcaRegistration {
+        owner: owner.clone(),
+        domain: domain.to_string(),
+        ica_identifier,
+        reopening,
+    };
+
+    Ok(SubMsg::reply_always(register_msg, REGISTER_ICA_REPLY_ID)
+        .with_payload(to_json_binary(&registration)?))
+}
+
+/// handles the outcome of a clearing account ICA registration. the clearing
+/// account itself is filled in once the open ack callback is received.
+pub fn reply_register_ica(
+    deps: DepsMut<NeutronQuery>,
+    msg: Reply,
+) -> StdResult<Response<NeutronMsg>> {
+    let registration: IcaRegistration = from_json(&msg.payload)?;
+    let response = Response::new()
+        .add_attribute("method", "reply_register_ica")
+        .add_attribute("ica_identifier", registration.ica_identifier.to_string());
+
+    match msg.result {
+        SubMsgResult::Ok(result) => {
+            // neutron returns the json encoded registration response as the message data
+            #[allow(deprecated)]
+            let data = result
+                .data
+                .ok_or_else(|| StdError::generic_err("register ica reply has no data"))?;
+            let channel: MsgRegisterInterchainAccountResponse = from_json(data)?;
+
+            Ok(response
+                .add_attribute("channel", channel.channel_id)
+                .add_attribute("port", channel.port_id))
+        }
+        SubMsgResult::Err(error) => {
+            // failed re-registrations leave the account closed
+            let deposit_refund = if registration.reopening {
+                REOPENING_CLEARING_ACCOUNTS.remove(deps.storage, registration.ica_identifier);
+                None
+            } else {
+                roll_back_clearing_account(
+                    deps.storage,
+                    &registration.owner,
+                    &registration.domain,
+                    &registration.ica_identifier,
+                )?
+            };
+
+            Ok(response
+                .add_messages(deposit_refund)
+                .add_attribute("error", error))
+        }
+    }
+}
+
+/// rolls back the clearing account which awaits its first registration, so that
+/// the user can register to the domain again. the deposit held for the queries
+/// of the account is refunded.
+pub fn roll_back_clearing_account(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    domain: &str,
+    ica_identifier: &str,
+) -> StdResult<Option<BankMsg>> {
+    // accounts which were opened before are left in place
+    let Some(None) = CLEARING_ACCOUNTS.may_load(storage, ica_identifier.to_string())? else {
+        return Ok(None);
+    };
+    CLEARING_ACCOUNTS.remove(storage, ica_identifier.to_string());
+
+    let mut user_config = USER_CONFIGS.load(storage, owner.to_string())?;
+    user_config
+        .registered_domains
+        .retain(|registered_domain| registered_domain != domain);
+    USER_CONFIGS.save(storage, owner.to_string(), &user_config)?;
+
+    // queries of the account will never be registered, so their deposit is refunded
+    let deposit_refund = PENDING_ICQ_REGISTRATIONS
+        .may_load(storage, ica_identifier.to_string())?
+        .and_then(|pending_registration| {
+            get_deposit_refund(
+                &pending_registration.owner,
+                pending_registration.total_deposit(),
+            )
+        });
+    PENDING_ICQ_REGISTRATIONS.remove(storage, ica_identifier.to_string());
+
+    Ok(deposit_refund)
+}
+
+/// completes the reopening of the clearing account, if it was being reopened,
+/// and builds the messages which resubmit its payouts that did not complete
+pub fn get_reopened_account_payout_txs(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    ica_identifier: &str,
+) -> NeutronResult<Vec<SubMsg<NeutronMsg>>> {
+    let Some((owner, domain)) =
+        REOPENING_CLEARING_ACCOUNTS.may_load(deps.storage, ica_identifier.to_string())?
+    else {
+        return Ok(vec![]);
+    };
+    REOPENING_CLEARING_ACCOUNTS.remove(deps.storage, ica_identifier.to_string());
+
+    intent::get_unsettled_payout_txs(deps, env, &owner, &domain)
+}
+
+/// records the outbound ICA tx in the ledger and wraps it into a submessage
+/// which captures the channel and sequence assigned by neutron
+pub fn submit_tx(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    submit_tx_msg: NeutronMsg,
+    owner: &Addr,
+    domain: &str,
+    kind: IcaTxKind,
+    outflows: Vec<Coin>,
+) -> StdResult<SubMsg<NeutronMsg>> {
+    let (msgs, fee, memo) = match &submit_tx_msg {
+        NeutronMsg::SubmitTx {
+            msgs, fee, memo, ..
+        } => (
+            msgs.iter().map(|msg| msg.type_url.to_string()).collect(),
+            fee.clone(),
+            Some(memo.to_string()).filter(|memo| !memo.is_empty()),
+        ),
+        _ => return Err(StdError::generic_err("expected a submit tx message")),
+    };
+
+    // fees of user operations are deposited by the user, while
+    // payouts are covered by orbital-core
+    let fee_payer = match kind {
+        IcaTxKind::Withdrawal | IcaTxKind::Transfer { .. } => Some(owner.clone()),
+        IcaTxKind::Payout { .. } => None,
+    };
+
+    let tx_id = ICA_TX_NONCE.may_load(storage)?.unwrap_or_default();
+    escrow::add_pending_outflows(storage, owner, domain, &outflows)?;
+
+    ICA_TXS.save(
+        storage,
+        tx_id,
+        &IcaTx {
+            owner: owner.clone(),
+            domain: domain.to_string(),
+            kind,
+            msgs,
+            fee,
+            fee_payer,
+            submitted_height: block.height,
+            status: IcaTxStatus::Submitted,
+            channel: None,
+            sequence: None,
+            ack: None,
+            memo,
+            outflows,
+        },
+    )?;
+    USER_ICA_TXS.save(storage, (owner.to_string(), tx_id), &Empty {})?;
+    ICA_TX_NONCE.save(storage, &(tx_id + 1))?;
+
+    // outbound tx id is passed along as the payload to be available in the reply
+    Ok(SubMsg::reply_always(submit_tx_msg, SUBMIT_TX_REPLY_ID)
+        .with_payload(to_json_binary(&tx_id)?))
+}
+
+/// stores the channel and sequence of a submitted ICA tx. txs which could not
+/// be submitted never leave orbital, so they are settled as failed right away.
+pub fn reply_submit_tx(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    msg: Reply,
+) -> StdResult<Response<NeutronMsg>> {
+    let tx_id: u64 = from_json(&msg.payload)?;
+
+    let response = match msg.result {
+        SubMsgResult::Ok(response) => response,
+        SubMsgResult::Err(error) => {
+            let fee_refund = settle_ica_tx(
+                deps.storage,
+                &env.block,
+                tx_id,
+                IcaTxStatus::Failed {
+                    error: error.to_string(),
+                },
+                None,
+            )?;
+
+            return Ok(Response::new()
+                .add_messages(fee_refund)
+                .add_attribute("method", "reply_submit_tx")
+                .add_attribute("ica_tx_id", tx_id.to_string())
+                .add_attribute("error", error));
+        }
+    };
+    // neutron returns the json encoded submission response as the message data
+    #[allow(deprecated)]
+    let data = response
+        .data
+        .ok_or_else(|| StdError::generic_err("submit tx reply has no data"))?;
+    let submission: MsgSubmitTxResponse = from_json(data)?;
+
+    let mut ica_tx = ICA_TXS.load(deps.storage, tx_id)?;
+    ica_tx.status = IcaTxStatus::Pending;
+    ica_tx.channel = Some(submission.channel.to_string());
+    ica_tx.sequence = Some(submission.sequence_id);
+
+    ICA_TXS.save(deps.storage, tx_id, &ica_tx)?;
+    ICA_TX_SEQUENCES.save(
+        deps.storage,
+        (submission.channel.to_string(), submission.sequence_id),
+        &tx_id,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "reply_submit_tx")
+        .add_attribute("ica_tx_id", tx_id.to_string())
+        .add_attribute("channel", submission.channel)
+        .add_attribute("sequence", submission.sequence_id.to_string()))
+}
+
+/// handles the acknowledgement of a successfully executed ICA tx
+pub fn sudo_response(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    request: RequestPacket,
+    data: Binary,
+) -> StdResult<Response<NeutronMsg>> {
+    settle_ica_packet(
+        deps.storage,
+        &env.block,
+        request,
+        IcaTxStatus::Succeeded,
+        Some(data),
+        "sudo_response",
+    )
+}
+
+/// handles the acknowledgement of an ICA tx which failed on the remote domain
+pub fn sudo_error(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    request: RequestPacket,
+    details: String,
+) -> StdResult<Response<NeutronMsg>> {
+    settle_ica_packet(
+        deps.storage,
+        &env.block,
+        request,
+        IcaTxStatus::Failed { error: details },
+        None,
+        "sudo_error",
+    )
+}
+
+/// handles an ICA tx whose packet timed out before being relayed
+pub fn sudo_timeout(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    request: RequestPacket,
+) -> StdResult<Response<NeutronMsg>> {
+    settle_ica_packet(
+        deps.storage,
+        &env.block,
+        request,
+        IcaTxStatus::TimedOut,
+        None,
+        "sudo_timeout",
+    )
+}
+
+/// settles the ICA tx matching the request packet
+fn settle_ica_packet(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    request: RequestPacket,
+    status: IcaTxStatus,
+    ack: Option<Binary>,
+    method: &str,
+) -> StdResult<Response<NeutronMsg>> {
+    let (channel, sequence) = match (request.source_channel, request.sequence) {
+        (Some(channel), Some(sequence)) => (channel, sequence),
+        _ => {
+            return Err(StdError::generic_err(
+                "request packet is missing channel or sequence",
+            ))
+        }
+    };
+
+    let tx_id = ICA_TX_SEQUENCES
+        .may_load(storage, (channel.to_string(), sequence))?
+        .ok_or_else(|| StdError::generic_err(format!("unknown ICA tx: {channel}/{sequence}")))?;
+
+    let fee_refund = settle_ica_tx(storage, block, tx_id, status, ack)?;
+
+    Ok(Response::new()
+        .add_messages(fee_refund)
+        .add_attribute("method", method)
+        .add_attribute("ica_tx_id", tx_id.to_string())
+        .add_attribute("channel", channel)
+        .add_attribute("sequence", sequence.to_string()))
+}
+
+/// records the final status of the ICA tx and settles the user operation it
+/// originated from. returns the refund of the IBC fees to their payer, if any.
+pub fn settle_ica_tx(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    tx_id: u64,
+    status: IcaTxStatus,
+    ack: Option<Binary>,
+) -> StdResult<Option<BankMsg>> {
+    let mut ica_tx = ICA_TXS.load(storage, tx_id)?;
+    ica_tx.status = status;
+    ica_tx.ack = ack;
+    ICA_TXS.save(storage, tx_id, &ica_tx)?;
+
+    // outflows of failed txs stay available, while executed outflows are held
+    // back until the balances query stops reporting them
+    escrow::settle_pending_outflows(
+        storage,
+        &ica_tx.owner,
+        &ica_tx.domain,
+        &ica_tx.outflows,
+        ica_tx.status == IcaTxStatus::Succeeded,
+        block.height,
+    )?;
+
+    // ICS-27 channels are ordered and get closed once a packet times out
+    if ica_tx.status == IcaTxStatus::TimedOut {
+        close_clearing_account(storage, &ica_tx.owner, &ica_tx.domain)?;
+    }
+
+    if let IcaTxKind::Payout { intent_id } = ica_tx.kind {
+        let mut payout = PAYOUTS.load(storage, intent_id)?;
+        // outcomes of payout txs which were resubmitted do not affect the payout
+        if payout
+            .tx_id
+            .is_some_and(|payout_tx_id| payout_tx_id != tx_id)
+        {
+            return Ok(get_fee_refund(&ica_tx));
+        }
+        if ica_tx.status == IcaTxStatus::Succeeded {
+            // paid out input funds have left the clearing account
+            let intent = INTENTS.load(storage, intent_id)?;
+            escrow::unlock(
+                storage,
+                &intent.owner,
+                &intent.input_domain,
+                &intent.input_coin,
+            )?;
+            payout.status = PayoutStatus::Completed;
+        } else {
+            payout.status = PayoutStatus::Failed;
+        }
+        PAYOUTS.save(storage, intent_id, &payout)?;
+    }
+
+    Ok(get_fee_refund(&ica_tx))
+}
+
+/// marks the clearing account of the user on the given domain as closed.
+/// it can be reopened by the user with the same ica identifier.
+fn close_clearing_account(storage: &mut dyn Storage, owner: &Addr, domain: &str) -> StdResult<()> {
+    let user_config = USER_CONFIGS.load(storage, owner.to_string())?;
+    let ica_identifier = get_ica_identifier(user_config.id, domain.to_string());
+
+    if let Some(mut clearing_account) =
+        CLEARING_ACCOUNTS.load(storage, ica_identifier.to_string())?
+    {
+        clearing_account.status = ClearingAccountStatus::Closed;
+        CLEARING_ACCOUNTS.save(storage, ica_identifier, &Some(clearing_account))?;
+    }
+
+    Ok(())
+}
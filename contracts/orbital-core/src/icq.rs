@@ -5,14 +5,24 @@ use cosmos_sdk_proto::{
     },
     prost::Message,
 };
-use cosmwasm_std::{Binary, DepsMut, Env, Response};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    ensure, from_json, to_json_binary, Addr, Binary, Checksum, Coin, DepsMut, Empty, Env,
+    MessageInfo, Reply, Response, Storage, SubMsg, SubMsgResponse, SubMsgResult, Uint128,
+};
+use cw_ownable::is_owner;
 use neutron_sdk::{
-    bindings::{msg::NeutronMsg, query::NeutronQuery, types::Height},
+    bindings::{
+        msg::{MsgRegisterInterchainQueryResponse, NeutronMsg},
+        query::NeutronQuery,
+        types::{Height, KVKey},
+    },
     interchain_queries::{
         get_registered_query,
         v045::{new_register_balances_query_msg, new_register_transfers_query_msg},
+        v047::queries::query_balance,
     },
-    NeutronResult,
+    NeutronError, NeutronResult,
 };
 
 use cosmwasm_std::{StdError, StdResult};
@@ -25,30 +35,477 @@ use neutron_sdk::interchain_queries::types::{
 };
 use serde_json_wasm;
 
-use crate::state::{Transfer, RECIPIENT_TXS, TRANSFERS};
+use crate::{
+    contract::{ExecuteDeps, QueryDeps},
+    error::ContractError,
+    intent_logic::intent,
+    state::{
+        ClearingAccountQueries, ClearingBalance, IcqDeposit, OrbitalDomainConfig,
+        PendingIcqRegistration, Transfer, CLEARING_ACCOUNT_QUERIES, CLEARING_BALANCES,
+        CREDITED_DEPOSITS, ICQ_DEPOSITS, ORBITAL_DOMAINS, PROCESSED_TXS, RECIPIENT_TXS,
+        REMOTE_HEIGHTS, TRANSFERS, TRANSFERS_QUERIES, USER_CONFIGS, USER_ICQS,
+    },
+    utils::{
+        escrow,
+        fees::{collect_icq_deposit, get_deposit_refund, query_icq_deposit},
+        get_clearing_account_domain,
+    },
+};
 
 const MAX_ALLOWED_MESSAGES: usize = 20;
 
+/// reply id of the balances query registration. the id of the registered
+/// query is returned in the reply and stored against the queried address.
+pub const REGISTER_BALANCES_QUERY_REPLY_ID: u64 = 1;
+
+/// reply id of the transfers query registration. the id of the registered
+/// query is returned in the reply and stored along with its deposit.
+pub const REGISTER_TRANSFERS_QUERY_REPLY_ID: u64 = 4;
+
+/// update period in blocks of the queries monitoring new clearing accounts
+const CLEARING_ACCOUNT_QUERY_UPDATE_PERIOD: u64 = 5;
+
+/// interchain query registration passed along as the reply payload
+#[cw_serde]
+struct QueryRegistration {
+    // address monitored by the query
+    addr: String,
+    // account which paid the query deposit
+    payer: Addr,
+    deposit: Vec<Coin>,
+    // (owner, domain) of the clearing account if the query was
+    // registered automatically when the account opened
+    clearing_account: Option<(Addr, String)>,
+    // denoms reported by the query if it is a balances query
+    #[serde(default)]
+    denoms: Vec<String>,
+}
+
 pub fn register_balances_query(
+    deps: ExecuteDeps,
+    info: MessageInfo,
     connection_id: String,
     addr: String,
     denoms: Vec<String>,
     update_period: u64,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    let msg = new_register_balances_query_msg(connection_id, addr, denoms, update_period)?;
+    assert_query_authorized(deps.as_ref(), &info.sender, &connection_id, &addr)?;
+
+    let msg = new_register_balances_query_msg(
+        connection_id,
+        addr.to_string(),
+        denoms.clone(),
+        update_period,
+    )?;
+
+    register_query(
+        deps.as_ref(),
+        &info,
+        msg,
+        addr,
+        denoms,
+        REGISTER_BALANCES_QUERY_REPLY_ID,
+    )
+}
+
+pub fn register_transfers_query(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    connection_id: String,
+    recipient: String,
+    update_period: u64,
+    min_height: Option<u64>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    assert_query_authorized(deps.as_ref(), &info.sender, &connection_id, &recipient)?;
+    // a second query would observe the same deposits again
+    ensure!(
+        !TRANSFERS_QUERIES.has(deps.storage, recipient.to_string()),
+        ContractError::TransfersQueryExists(recipient)
+    );
+
+    let msg = new_register_transfers_query_msg(
+        connection_id,
+        recipient.to_string(),
+        update_period,
+        min_height,
+    )?;
+
+    register_query(
+        deps.as_ref(),
+        &info,
+        msg,
+        recipient,
+        vec![],
+        REGISTER_TRANSFERS_QUERY_REPLY_ID,
+    )
+}
+
+/// asserts that the caller may register an interchain query for the address.
+/// the owner may query any address, while users may only query their own
+/// clearing accounts over the connection of their domain. polytone domains
+/// without a configured connection reject queries of their clearing accounts.
+fn assert_query_authorized(
+    deps: QueryDeps,
+    sender: &Addr,
+    connection_id: &str,
+    addr: &str,
+) -> Result<(), ContractError> {
+    if is_owner(deps.storage, sender)? {
+        return Ok(());
+    }
+
+    let authorized = match get_clearing_account_domain(deps.storage, sender, addr)? {
+        Some(domain) => match ORBITAL_DOMAINS.may_load(deps.storage, domain)? {
+            Some(OrbitalDomainConfig::InterchainAccount {
+                connection_id: domain_connection_id,
+                ..
+            }) => domain_connection_id == connection_id,
+            Some(OrbitalDomainConfig::Polytone {
+                connection_id: domain_connection_id,
+                ..
+            }) => domain_connection_id.as_deref() == Some(connection_id),
+            None => false,
+        },
+        None => false,
+    };
+    ensure!(
+        authorized,
+        ContractError::UnauthorizedQuery(addr.to_string())
+    );
+
+    Ok(())
+}
+
+/// wraps the query registration into a submessage whose reply records the
+/// deposit paid by the caller. deposits paid in excess are refunded.
+fn register_query(
+    deps: QueryDeps,
+    info: &MessageInfo,
+    register_msg: NeutronMsg,
+    addr: String,
+    denoms: Vec<String>,
+    reply_id: u64,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let deposit = query_icq_deposit(deps)?;
+    let deposit_refund = collect_icq_deposit(info, &deposit)?;
+
+    let registration = QueryRegistration {
+        addr,
+        payer: info.sender.clone(),
+        deposit,
+        clearing_account: None,
+        denoms,
+    };
+
+    Ok(Response::new()
+        .add_submessage(get_query_registration_submsg(
+            register_msg,
+            &registration,
+            reply_id,
+        )?)
+        .add_messages(deposit_refund))
+}
+
+/// builds the registrations of the queries monitoring a newly opened clearing
+/// account: a transfers query of deposits into the account, and a balances
+/// query if the domain monitors any denoms. deposits of the queries were paid
+/// by the owner when registering to the domain.
+pub fn get_clearing_account_query_submsgs(
+    pending_registration: PendingIcqRegistration,
+    connection_id: String,
+    addr: String,
+) -> StdResult<Vec<SubMsg<NeutronMsg>>> {
+    let to_std_err = |e: NeutronError| StdError::generic_err(e.to_string());
+    let registration = QueryRegistration {
+        addr: addr.to_string(),
+        payer: pending_registration.owner.clone(),
+        deposit: pending_registration.deposit,
+        clearing_account: Some((pending_registration.owner, pending_registration.domain)),
+        denoms: vec![],
+    };
+
+    let mut submsgs = vec![get_query_registration_submsg(
+        new_register_transfers_query_msg(
+            connection_id.to_string(),
+            addr.to_string(),
+            CLEARING_ACCOUNT_QUERY_UPDATE_PERIOD,
+            None,
+        )
+        .map_err(to_std_err)?,
+        &registration,
+        REGISTER_TRANSFERS_QUERY_REPLY_ID,
+    )?];
 
-    Ok(Response::new().add_message(msg))
+    if !pending_registration.monitored_denoms.is_empty() {
+        submsgs.push(get_query_registration_submsg(
+            new_register_balances_query_msg(
+                connection_id,
+                addr,
+                pending_registration.monitored_denoms.clone(),
+                CLEARING_ACCOUNT_QUERY_UPDATE_PERIOD,
+            )
+            .map_err(to_std_err)?,
+            &QueryRegistration {
+                denoms: pending_registration.monitored_denoms,
+                ..registration
+            },
+            REGISTER_BALANCES_QUERY_REPLY_ID,
+        )?);
+    }
+
+    Ok(submsgs)
+}
+
+/// wraps the query registration into a submessage whose reply handles
+/// both the successful and the failed registration
+fn get_query_registration_submsg(
+    register_msg: NeutronMsg,
+    registration: &QueryRegistration,
+    reply_id: u64,
+) -> StdResult<SubMsg<NeutronMsg>> {
+    // registration is passed along as the payload to be available in the reply
+    Ok(SubMsg::reply_always(register_msg, reply_id).with_payload(to_json_binary(registration)?))
+}
+
+/// stores the deposit of a newly registered query and returns the id of the query
+fn save_query_registration(
+    storage: &mut dyn Storage,
+    registration: &QueryRegistration,
+    response: SubMsgResponse,
+) -> StdResult<u64> {
+    // neutron returns the json encoded registration response as the message data
+    #[allow(deprecated)]
+    let data = response
+        .data
+        .ok_or_else(|| StdError::generic_err("register query reply has no data"))?;
+    let query: MsgRegisterInterchainQueryResponse = from_json(data)?;
+
+    ICQ_DEPOSITS.save(
+        storage,
+        query.id,
+        &IcqDeposit {
+            payer: registration.payer.clone(),
+            deposit: registration.deposit.clone(),
+            addr: registration.addr.to_string(),
+            denoms: registration.denoms.clone(),
+        },
+    )?;
+    USER_ICQS.save(
+        storage,
+        (registration.payer.to_string(), query.id),
+        &Empty {},
+    )?;
+
+    Ok(query.id)
+}
+
+/// refunds the deposit of a failed query registration, which neutron never
+/// escrowed, to its payer
+fn reject_query_registration(
+    response: Response<NeutronMsg>,
+    registration: QueryRegistration,
+    error: String,
+) -> Response<NeutronMsg> {
+    response
+        .add_messages(get_deposit_refund(
+            &registration.payer,
+            registration.deposit,
+        ))
+        .add_attribute("error", error)
+}
+
+/// stores the id of a newly registered balances query against the queried
+/// address, and against the clearing account it monitors
+pub fn reply_register_balances_query(
+    deps: DepsMut<NeutronQuery>,
+    msg: Reply,
+) -> StdResult<Response<NeutronMsg>> {
+    let registration: QueryRegistration = from_json(&msg.payload)?;
+    let response = Response::new()
+        .add_attribute("method", "reply_register_balances_query")
+        .add_attribute("addr", registration.addr.to_string());
+
+    let query_id = match msg.result {
+        SubMsgResult::Ok(result) => save_query_registration(deps.storage, &registration, result)?,
+        SubMsgResult::Err(error) => {
+            return Ok(reject_query_registration(response, registration, error))
+        }
+    };
+
+    if let Some((owner, domain)) = registration.clearing_account {
+        CLEARING_ACCOUNT_QUERIES.update(
+            deps.storage,
+            (owner.to_string(), domain),
+            |queries| -> StdResult<_> {
+                Ok(ClearingAccountQueries {
+                    balances_query_id: Some(query_id),
+                    ..queries.unwrap_or_default()
+                })
+            },
+        )?;
+    }
+
+    Ok(response.add_attribute("query_id", query_id.to_string()))
+}
+
+/// records the deposit of a newly registered transfers query, and stores its
+/// id against the recipient and the clearing account it monitors
+pub fn reply_register_transfers_query(
+    deps: DepsMut<NeutronQuery>,
+    msg: Reply,
+) -> StdResult<Response<NeutronMsg>> {
+    let registration: QueryRegistration = from_json(&msg.payload)?;
+    let response = Response::new()
+        .add_attribute("method", "reply_register_transfers_query")
+        .add_attribute("recipient", registration.addr.to_string());
+
+    let query_id = match msg.result {
+        SubMsgResult::Ok(result) => save_query_registration(deps.storage, &registration, result)?,
+        SubMsgResult::Err(error) => {
+            return Ok(reject_query_registration(response, registration, error))
+        }
+    };
+
+    if !TRANSFERS_QUERIES.has(deps.storage, registration.addr.to_string()) {
+        TRANSFERS_QUERIES.save(deps.storage, registration.addr.to_string(), &query_id)?;
+    }
+    if let Some((owner, domain)) = registration.clearing_account {
+        CLEARING_ACCOUNT_QUERIES.update(
+            deps.storage,
+            (owner.to_string(), domain),
+            |queries| -> StdResult<_> {
+                Ok(ClearingAccountQueries {
+                    transfers_query_id: Some(query_id),
+                    ..queries.unwrap_or_default()
+                })
+            },
+        )?;
+    }
+
+    Ok(response.add_attribute("query_id", query_id.to_string()))
+}
+
+pub fn update_interchain_query(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    query_id: u64,
+    new_keys: Option<Vec<KVKey>>,
+    new_update_period: Option<u64>,
+    new_transactions_filter: Option<Vec<TransactionFilterItem>>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    assert_query_owner(deps.as_ref(), &info.sender, query_id)?;
+
+    // users may only monitor their own clearing accounts, so they can not
+    // change the keys or filter which determine the monitored address
+    ensure!(
+        (new_keys.is_none() && new_transactions_filter.is_none())
+            || is_owner(deps.storage, &info.sender)?,
+        ContractError::UnauthorizedQueryUpdate(query_id)
+    );
+
+    let update_msg = NeutronMsg::update_interchain_query(
+        query_id,
+        new_keys,
+        new_update_period,
+        new_transactions_filter,
+    )?;
+
+    Ok(Response::new()
+        .add_message(update_msg)
+        .add_attribute("method", "update_interchain_query")
+        .add_attribute("query_id", query_id.to_string()))
+}
+
+pub fn remove_interchain_query(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    query_id: u64,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let registration = assert_query_owner(deps.as_ref(), &info.sender, query_id)?;
+
+    // neutron returns the deposit to orbital-core as the owner of the query
+    // on removal, which is then forwarded to the account which paid it
+    let deposit_refund = match registration {
+        Some(registration) => {
+            remove_query_registration(deps.storage, query_id, &registration)?;
+            get_deposit_refund(&registration.payer, registration.deposit)
+        }
+        None => None,
+    };
+
+    Ok(Response::new()
+        .add_message(NeutronMsg::remove_interchain_query(query_id))
+        .add_messages(deposit_refund)
+        .add_attribute("method", "remove_interchain_query")
+        .add_attribute("query_id", query_id.to_string()))
+}
+
+/// asserts that the sender owns the query and returns its registration.
+/// queries registered before their owners were recorded have no registration
+/// and are owned by the contract owner.
+fn assert_query_owner(
+    deps: QueryDeps,
+    sender: &Addr,
+    query_id: u64,
+) -> Result<Option<IcqDeposit>, ContractError> {
+    let registration = ICQ_DEPOSITS.may_load(deps.storage, query_id)?;
+    let authorized = match &registration {
+        Some(registration) => &registration.payer == sender,
+        None => is_owner(deps.storage, sender)?,
+    };
+    ensure!(authorized, ContractError::NotQueryOwner(query_id));
+
+    Ok(registration)
+}
+
+/// removes the registration of the query along with every reference to it
+fn remove_query_registration(
+    storage: &mut dyn Storage,
+    query_id: u64,
+    registration: &IcqDeposit,
+) -> StdResult<()> {
+    let owner = registration.payer.to_string();
+    ICQ_DEPOSITS.remove(storage, query_id);
+    USER_ICQS.remove(storage, (owner.to_string(), query_id));
+    if TRANSFERS_QUERIES.may_load(storage, registration.addr.to_string())? == Some(query_id) {
+        TRANSFERS_QUERIES.remove(storage, registration.addr.to_string());
+    }
+
+    // queries of clearing accounts are owned by the owner of the account
+    let registered_domains = USER_CONFIGS
+        .may_load(storage, owner.to_string())?
+        .map(|user_config| user_config.registered_domains)
+        .unwrap_or_default();
+    for domain in registered_domains {
+        let key = (owner.to_string(), domain);
+        let Some(mut queries) = CLEARING_ACCOUNT_QUERIES.may_load(storage, key.clone())? else {
+            continue;
+        };
+        if queries.balances_query_id == Some(query_id) {
+            queries.balances_query_id = None;
+        }
+        if queries.transfers_query_id == Some(query_id) {
+            queries.transfers_query_id = None;
+        }
+        CLEARING_ACCOUNT_QUERIES.save(storage, key, &queries)?;
+    }
+
+    Ok(())
 }
 
 /// sudo_check_tx_query_result is an example callback for transaction query results that stores the
 /// deposits received as a result on the registered query in the contract's state.
+/// results of txs already processed for the recipient are skipped, and deposits observed by queries which
+/// users own for their clearing accounts are credited to the (user, domain) of the account.
 pub fn sudo_tx_query_result(
-    deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
     query_id: u64,
-    _height: Height,
+    height: Height,
     data: Binary,
 ) -> StdResult<Response<NeutronMsg>> {
+    let tx_hash = Checksum::generate(data.as_slice()).to_hex();
+
     // Decode the transaction data
     let tx: TxRaw = TxRaw::decode(data.as_slice())
         .map_err(|_| StdError::generic_err("sudo_tx_query_result failed to decode tx_raw"))?;
@@ -60,6 +517,7 @@ pub fn sudo_tx_query_result(
         get_registered_query(deps.as_ref(), query_id).map_err(|_| {
             StdError::generic_err("sudo_tx_query_result failed to get registered query response")
         })?;
+    let connection_id = registered_query.registered_query.connection_id.to_string();
     let transactions_filter = registered_query.registered_query.transactions_filter;
 
     #[allow(clippy::match_single_binding)]
@@ -80,6 +538,23 @@ pub fn sudo_tx_query_result(
                 })
                 .unwrap_or("");
 
+            // the same tx must not be processed twice for a recipient, otherwise a
+            // replayed result or another query of the recipient would credit its
+            // deposits again. queries of other recipients still process the tx.
+            let tx_key = (
+                recipient.to_string(),
+                height.revision_height,
+                tx_hash.to_string(),
+            );
+            if PROCESSED_TXS.has(deps.storage, tx_key.clone()) {
+                return Ok(Response::new()
+                    .add_attribute("method", "sudo_tx_query_result")
+                    .add_attribute("query_id", query_id.to_string())
+                    .add_attribute("tx_hash", tx_hash)
+                    .add_attribute("skipped", "already_processed"));
+            }
+            PROCESSED_TXS.save(deps.storage, tx_key, &Empty {})?;
+
             let deposits = recipient_deposits_from_tx_body(body, recipient).map_err(|_| {
                 StdError::generic_err(
                     "sudo_tx_query_result failed to decode recipient deposits from tx body",
@@ -93,6 +568,44 @@ pub fn sudo_tx_query_result(
                 ));
             }
 
+            // deposits into a clearing account may be fills of intents won by solvers
+            let filled_intent_ids = intent::verify_fills(
+                deps.storage,
+                &env.block,
+                recipient,
+                &deposits,
+                height.revision_height,
+            )?;
+
+            // auctions closing from now on only accept fills above this height
+            REMOTE_HEIGHTS.update(
+                deps.storage,
+                connection_id,
+                |remote_height| -> StdResult<_> {
+                    Ok(remote_height
+                        .unwrap_or_default()
+                        .max(height.revision_height))
+                },
+            )?;
+
+            // filling solvers are paid out of the intent owner's input clearing account.
+            // fills stand even if their payout can not be submitted right now, e.g. while
+            // the clearing account is closed. such payouts are recorded as failed, to be
+            // resubmitted through a retry or once the clearing account is reopened.
+            let mut payout_submsgs = vec![];
+            let mut failed_payout_ids = vec![];
+            for intent_id in &filled_intent_ids {
+                match intent::get_payout_tx(deps.branch(), &env, *intent_id) {
+                    Ok(payout_submsg) => payout_submsgs.push(payout_submsg),
+                    Err(_) => {
+                        intent::fail_payout(deps.storage, *intent_id)?;
+                        failed_payout_ids.push(*intent_id);
+                    }
+                }
+            }
+
+            let credited_account = credit_deposits(deps.storage, query_id, recipient, &deposits)?;
+
             let mut stored_transfers: u64 = TRANSFERS.load(deps.storage).unwrap_or_default();
             stored_transfers += deposits.len() as u64;
             TRANSFERS.save(deps.storage, &stored_transfers)?;
@@ -102,7 +615,29 @@ pub fn sudo_tx_query_result(
                 .unwrap_or_default();
             stored_deposits.extend(deposits);
             RECIPIENT_TXS.save(deps.storage, recipient.to_string(), &stored_deposits)?;
-            Ok(Response::new())
+
+            let mut response = Response::new()
+                .add_attribute("method", "sudo_tx_query_result")
+                .add_attribute("query_id", query_id.to_string())
+                .add_attribute("tx_hash", tx_hash);
+            if let Some((user, domain)) = credited_account {
+                response = response
+                    .add_attribute("user", user)
+                    .add_attribute("domain", domain);
+            }
+
+            Ok(response
+                .add_submessages(payout_submsgs)
+                .add_attributes(
+                    filled_intent_ids
+                        .into_iter()
+                        .map(|intent_id| ("filled_intent_id", intent_id.to_string())),
+                )
+                .add_attributes(
+                    failed_payout_ids
+                        .into_iter()
+                        .map(|intent_id| ("failed_payout_intent_id", intent_id.to_string())),
+                ))
         }
     }
 }
@@ -139,31 +674,110 @@ fn recipient_deposits_from_tx_body(
     Ok(deposits)
 }
 
+/// credits the deposits to the clearing account of the user who registered
+/// the transfers query. returns the (user, domain) of the credited account,
+/// or `None` if the recipient is not a clearing account of the query owner.
+fn credit_deposits(
+    storage: &mut dyn Storage,
+    query_id: u64,
+    recipient: &str,
+    deposits: &[Transfer],
+) -> StdResult<Option<(String, String)>> {
+    // queries registered by the contract owner or for other addresses are not tracked
+    let Some(registration) = ICQ_DEPOSITS.may_load(storage, query_id)? else {
+        return Ok(None);
+    };
+    let Some(domain) = get_clearing_account_domain(storage, &registration.payer, recipient)? else {
+        return Ok(None);
+    };
+
+    let user = registration.payer.to_string();
+    for deposit in deposits {
+        let amount: Uint128 = deposit.amount.parse()?;
+        CREDITED_DEPOSITS.update(
+            storage,
+            (
+                user.to_string(),
+                domain.to_string(),
+                deposit.denom.to_string(),
+            ),
+            |credited| -> StdResult<_> { Ok(credited.unwrap_or_default().checked_add(amount)?) },
+        )?;
+    }
+
+    Ok(Some((user, domain)))
+}
+
 /// sudo_kv_query_result is the contract's callback for KV query results. Note that only the query
 /// id is provided, so you need to read the query result from the state.
+/// balances reported by queries which users own for their clearing accounts
+/// replace the snapshot of the account in the clearing balances ledger.
 pub fn sudo_kv_query_result(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     query_id: u64,
 ) -> StdResult<Response<NeutronMsg>> {
-    deps.api.debug(
-        format!(
-            "WASMDEBUG: sudo_kv_query_result received; query_id: {:?}",
-            query_id,
-        )
-        .as_str(),
-    );
-    Ok(Response::default())
-}
+    let response = Response::new()
+        .add_attribute("method", "sudo_kv_query_result")
+        .add_attribute("query_id", query_id.to_string());
 
-pub fn register_transfers_query(
-    connection_id: String,
-    recipient: String,
-    update_period: u64,
-    min_height: Option<u64>,
-) -> NeutronResult<Response<NeutronMsg>> {
-    let msg =
-        new_register_transfers_query_msg(connection_id, recipient, update_period, min_height)?;
+    // queries registered by the contract owner or for other addresses are not tracked
+    let Some(registration) = ICQ_DEPOSITS.may_load(deps.storage, query_id)? else {
+        return Ok(response);
+    };
+    let Some(domain) =
+        get_clearing_account_domain(deps.storage, &registration.payer, &registration.addr)?
+    else {
+        return Ok(response);
+    };
+
+    let balances = query_balance(deps.as_ref(), env.clone(), query_id)
+        .map_err(|e| StdError::generic_err(e.to_string()))?
+        .balances
+        .coins;
+
+    // denoms monitored by the query which are no longer reported are dropped
+    // from the snapshot, while the balances reported by other queries of the
+    // account are kept. queries registered before their denoms were recorded
+    // only replace the balances they report.
+    let user = registration.payer.to_string();
+    for denom in &registration.denoms {
+        CLEARING_BALANCES.remove(
+            deps.storage,
+            (user.to_string(), domain.to_string(), denom.to_string()),
+        );
+    }
+    let mut reported_denoms = registration.denoms.clone();
+    for balance in balances {
+        CLEARING_BALANCES.save(
+            deps.storage,
+            (
+                user.to_string(),
+                domain.to_string(),
+                balance.denom.to_string(),
+            ),
+            &ClearingBalance {
+                amount: balance.amount,
+                last_updated_height: env.block.height,
+            },
+        )?;
+        reported_denoms.push(balance.denom);
+    }
+
+    // outflows of txs settled before this snapshot are no longer reported
+    reported_denoms.sort();
+    reported_denoms.dedup();
+    for denom in reported_denoms {
+        escrow::release_settled_outflows(
+            deps.storage,
+            &registration.payer,
+            &domain,
+            &denom,
+            env.block.height,
+        )?;
+    }
 
-    Ok(Response::new().add_message(msg))
+    Ok(response
+        .add_attribute("user", user)
+        .add_attribute("domain", domain))
 }
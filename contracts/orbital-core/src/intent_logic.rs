@@ -0,0 +1,415 @@
+pub(crate) mod intent {
+    use std::str::FromStr;
+
+    use cosmwasm_std::{
+        ensure, Addr, BlockInfo, Coin, Empty, Env, MessageInfo, Order, Response, StdResult,
+        Storage, SubMsg, Uint128, Uint64,
+    };
+    use cw_utils::Expiration;
+    use neutron_sdk::{bindings::msg::NeutronMsg, NeutronResult};
+
+    use crate::{
+        auction_logic::auction,
+        contract::ExecuteDeps,
+        error::ContractError,
+        msg::Withdrawal,
+        solver_logic::solver,
+        state::{
+            IcaTxKind, Intent, IntentStatus, Payout, PayoutStatus, Transfer, AUCTIONS,
+            AWAITING_FILLS, CLEARING_ACCOUNT_ADDRESSES, ICA_TX_NONCE, INTENTS, INTENT_NONCE,
+            ORBITAL_DOMAINS, PAYOUTS, SOLVER_DOMAIN_ADDRESSES, USER_CONFIGS, USER_INTENTS,
+        },
+        user_logic::user,
+        utils::{assert_domain_active, escrow, get_clearing_account, get_open_clearing_account},
+    };
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_submit_intent(
+        deps: ExecuteDeps,
+        env: Env,
+        info: MessageInfo,
+        input_domain: String,
+        input_coin: Coin,
+        destination_domain: String,
+        destination_coin: Coin,
+        deadline: Option<Expiration>,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        // user must be registered in order to submit intents
+        let user_config = USER_CONFIGS
+            .may_load(deps.storage, info.sender.to_string())?
+            .ok_or(ContractError::UserNotRegistered {})?;
+
+        // both domains must be enabled on orbital level and the user must
+        // own a clearing account on each of them
+        for domain in [&input_domain, &destination_domain] {
+            assert_domain_active(deps.storage, domain)?;
+            ensure!(
+                user_config.registered_domains.contains(domain),
+                ContractError::UserNotRegisteredToDomain(domain.to_string())
+            );
+            // clearing account must have completed its registration
+            get_clearing_account(deps.storage, &info.sender, domain)?;
+        }
+        // input funds are paid out through the input clearing account
+        get_open_clearing_account(deps.storage, &info.sender, &input_domain)?;
+
+        ensure!(
+            !input_coin.amount.is_zero() && !destination_coin.amount.is_zero(),
+            ContractError::InvalidIntent("amounts must be non-zero".to_string())
+        );
+
+        // an intent that asks for the same token on the same domain is a no-op
+        ensure!(
+            input_domain != destination_domain || input_coin.denom != destination_coin.denom,
+            ContractError::InvalidIntent("input and destination must differ".to_string())
+        );
+
+        ensure!(
+            !deadline.is_some_and(|deadline| deadline.is_expired(&env.block)),
+            ContractError::InvalidIntent("deadline must be in the future".to_string())
+        );
+
+        // input funds must not already be pledged to other intents of the user
+        release_expired(deps.storage, &env.block, &info.sender)?;
+        escrow::assert_available(deps.storage, &info.sender, &input_domain, &input_coin)?;
+        escrow::lock(deps.storage, &info.sender, &input_domain, &input_coin)?;
+
+        let intent_id = INTENT_NONCE.load(deps.storage)?;
+
+        let intent = Intent {
+            id: intent_id,
+            owner: info.sender.clone(),
+            input_domain,
+            input_coin,
+            destination_domain,
+            destination_coin,
+            status: IntentStatus::Open,
+            submitted_at: env.block.height,
+            deadline,
+        };
+
+        INTENTS.save(deps.storage, intent_id.u64(), &intent)?;
+        USER_INTENTS.save(
+            deps.storage,
+            (info.sender.to_string(), intent_id.u64()),
+            &Empty {},
+        )?;
+        // auction for the execution rights opens along with the intent
+        let auction_end = auction::open_auction(deps.storage, &env.block, intent_id.u64())?;
+        // increment the nonce
+        INTENT_NONCE.save(deps.storage, &intent_id.checked_add(Uint64::one())?)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "submit_intent")
+            .add_attribute("intent_id", intent_id.to_string())
+            .add_attribute("auction_end", auction_end.to_string()))
+    }
+
+    pub fn try_cancel_intent(
+        deps: ExecuteDeps,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+        release_to: Option<String>,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let mut intent = load_intent(deps.storage, &env.block, id)?;
+
+        ensure!(
+            intent.owner == info.sender,
+            ContractError::NotIntentOwner {}
+        );
+        // intents can only be cancelled before their auction has a winner
+        ensure!(
+            matches!(intent.status, IntentStatus::Open | IntentStatus::Auctioning),
+            ContractError::UnexpectedIntentStatus(intent.status)
+        );
+
+        intent.status = IntentStatus::Cancelled;
+        INTENTS.save(deps.storage, id, &intent)?;
+        escrow::unlock(
+            deps.storage,
+            &intent.owner,
+            &intent.input_domain,
+            &intent.input_coin,
+        )?;
+
+        let mut response = Response::new()
+            .add_attribute("method", "cancel_intent")
+            .add_attribute("intent_id", id.to_string());
+
+        // input funds remain in the user's clearing account on the input domain
+        // unless the user chose to release them to another destination
+        if let Some(dest) = release_to {
+            let user_config = USER_CONFIGS.load(deps.storage, info.sender.to_string())?;
+            let withdraw_submsgs = user::get_withdraw_msg(
+                deps,
+                &env,
+                &info,
+                &user_config,
+                intent.input_domain.to_string(),
+                vec![Withdrawal {
+                    coin: intent.input_coin,
+                    dest: dest.to_string(),
+                }],
+                None,
+                None,
+            )?;
+            response = response
+                .add_submessages(withdraw_submsgs)
+                .add_attribute("release_to", dest);
+        }
+
+        Ok(response)
+    }
+
+    pub fn try_retry_payout(
+        deps: ExecuteDeps,
+        env: Env,
+        intent_id: u64,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let payout = PAYOUTS
+            .may_load(deps.storage, intent_id)?
+            .ok_or(ContractError::UnknownIntent(intent_id))?;
+        // only failed payouts are resubmitted, pending ones may still succeed
+        ensure!(
+            payout.status == PayoutStatus::Failed,
+            ContractError::UnexpectedPayoutStatus(payout.status)
+        );
+
+        let payout_submsg = get_payout_tx(deps, &env, intent_id)?;
+
+        Ok(Response::new()
+            .add_submessage(payout_submsg)
+            .add_attribute("method", "retry_payout")
+            .add_attribute("intent_id", intent_id.to_string()))
+    }
+
+    /// loads the intent with the given id and applies its expiry
+    pub fn load_intent(
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        id: u64,
+    ) -> Result<Intent, ContractError> {
+        let mut intent = INTENTS
+            .may_load(storage, id)?
+            .ok_or(ContractError::UnknownIntent(id))?;
+        intent.apply_expiry(block);
+
+        Ok(intent)
+    }
+
+    /// persists the expiry of the owner's intents whose deadline has passed
+    /// and releases their locked input funds. returns the ids of expired intents.
+    pub fn release_expired(
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        owner: &Addr,
+    ) -> StdResult<Vec<u64>> {
+        let intent_ids = USER_INTENTS
+            .prefix(owner.to_string())
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+
+        let mut expired_ids = vec![];
+        for intent_id in intent_ids {
+            let mut intent = INTENTS.load(storage, intent_id)?;
+            if !matches!(intent.status, IntentStatus::Open | IntentStatus::Auctioning) {
+                continue;
+            }
+
+            intent.apply_expiry(block);
+            if intent.status == IntentStatus::Expired {
+                INTENTS.save(storage, intent_id, &intent)?;
+                escrow::unlock(storage, owner, &intent.input_domain, &intent.input_coin)?;
+                expired_ids.push(intent_id);
+            }
+        }
+
+        Ok(expired_ids)
+    }
+
+    /// builds the message which pays the intent input funds out of the owner's
+    /// clearing account to the solver who filled the intent, and records the
+    /// payout as pending. IBC fees are covered by the contract.
+    pub fn get_payout_tx(
+        deps: ExecuteDeps,
+        env: &Env,
+        intent_id: u64,
+    ) -> NeutronResult<SubMsg<NeutronMsg>> {
+        let intent = INTENTS.load(deps.storage, intent_id)?;
+        let solver = match &intent.status {
+            IntentStatus::Filled { solver, .. } => solver,
+            _ => return Err(ContractError::UnexpectedIntentStatus(intent.status).into()),
+        };
+
+        let recipient = SOLVER_DOMAIN_ADDRESSES
+            .may_load(
+                deps.storage,
+                (solver.to_string(), intent.input_domain.to_string()),
+            )?
+            .ok_or_else(|| {
+                ContractError::SolverDomainAddressNotSet(intent.input_domain.to_string())
+            })?;
+
+        // the payout tx is recorded under the next id of the ica tx ledger
+        let tx_id = ICA_TX_NONCE.may_load(deps.storage)?.unwrap_or_default();
+        PAYOUTS.save(
+            deps.storage,
+            intent_id,
+            &Payout {
+                recipient: recipient.to_string(),
+                amount: intent.input_coin.clone(),
+                status: PayoutStatus::Pending,
+                tx_id: Some(tx_id),
+            },
+        )?;
+
+        let domain_config = ORBITAL_DOMAINS.load(deps.storage, intent.input_domain.to_string())?;
+        let payout_submsg = domain_config.get_send_message(
+            deps,
+            env,
+            &intent.owner,
+            &intent.input_domain,
+            vec![(intent.input_coin.clone(), recipient)],
+            IcaTxKind::Payout { intent_id },
+            None,
+            None,
+        )?;
+
+        Ok(payout_submsg)
+    }
+
+    /// records the payout of the filled intent as failed without a tx in flight,
+    /// so that it is resubmitted through a retry or once the owner's clearing
+    /// account is reopened
+    pub fn fail_payout(storage: &mut dyn Storage, intent_id: u64) -> StdResult<()> {
+        let intent = INTENTS.load(storage, intent_id)?;
+        let payout = match PAYOUTS.may_load(storage, intent_id)? {
+            Some(payout) => Payout {
+                status: PayoutStatus::Failed,
+                tx_id: None,
+                ..payout
+            },
+            // the recipient is resolved again once the payout is resubmitted
+            None => Payout {
+                recipient: String::new(),
+                amount: intent.input_coin,
+                status: PayoutStatus::Failed,
+                tx_id: None,
+            },
+        };
+
+        PAYOUTS.save(storage, intent_id, &payout)
+    }
+
+    /// builds the messages which resubmit the payouts out of the owner's
+    /// clearing account on the domain which did not complete. used once the
+    /// ICA channel of a closed clearing account is reopened, as txs sent over
+    /// the closed channel can no longer be delivered.
+    pub fn get_unsettled_payout_txs(
+        mut deps: ExecuteDeps,
+        env: &Env,
+        owner: &Addr,
+        domain: &str,
+    ) -> NeutronResult<Vec<SubMsg<NeutronMsg>>> {
+        let intent_ids = USER_INTENTS
+            .prefix(owner.to_string())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+
+        let mut payout_submsgs = vec![];
+        for intent_id in intent_ids {
+            let intent = INTENTS.load(deps.storage, intent_id)?;
+            if intent.input_domain != domain {
+                continue;
+            }
+            let Some(payout) = PAYOUTS.may_load(deps.storage, intent_id)? else {
+                continue;
+            };
+            if payout.status != PayoutStatus::Completed {
+                payout_submsgs.push(get_payout_tx(deps.branch(), env, intent_id)?);
+            }
+        }
+
+        Ok(payout_submsgs)
+    }
+
+    /// matches deposits observed on a clearing account against the intents which
+    /// await a fill into it. a deposit fills an intent if it is denominated in the
+    /// intent destination denom, covers the winning bid, was sent by the winner from
+    /// their address on the destination domain, which is not a clearing account, and
+    /// was executed after the auction closed, before the fill deadline. each deposit can fill at most one intent.
+    /// returns the ids of the filled intents.
+    pub fn verify_fills(
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        recipient: &str,
+        deposits: &[Transfer],
+        height: u64,
+    ) -> StdResult<Vec<u64>> {
+        let mut awaiting_intent_ids = AWAITING_FILLS
+            .prefix(recipient.to_string())
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+        let mut filled_intent_ids = vec![];
+
+        for deposit in deposits {
+            let deposit_amount = Uint128::from_str(&deposit.amount)?;
+
+            // find the oldest awaiting intent satisfied by this deposit
+            let mut matched = None;
+            for (index, intent_id) in awaiting_intent_ids.iter().enumerate() {
+                let intent = INTENTS.load(storage, *intent_id)?;
+                let auction = AUCTIONS.load(storage, *intent_id)?;
+
+                // winners which missed the fill deadline are left to be slashed
+                if auction
+                    .fill_deadline
+                    .is_some_and(|deadline| deadline.is_expired(block))
+                {
+                    continue;
+                }
+
+                if let Some(winning_bid) = auction.highest_bid {
+                    // auctions closed before fill senders were recorded accept
+                    // the current address of the winner
+                    let fill_sender = match auction.fill_sender {
+                        Some(fill_sender) => Some(fill_sender),
+                        None => SOLVER_DOMAIN_ADDRESSES.may_load(
+                            storage,
+                            (
+                                winning_bid.solver.to_string(),
+                                intent.destination_domain.to_string(),
+                            ),
+                        )?,
+                    };
+
+                    if intent.destination_coin.denom == deposit.denom
+                        && deposit_amount >= winning_bid.amount
+                        && fill_sender.as_deref() == Some(deposit.sender.as_str())
+                        && !CLEARING_ACCOUNT_ADDRESSES.has(storage, deposit.sender.to_string())
+                        && height > auction.min_fill_height
+                    {
+                        matched = Some((index, intent, winning_bid.solver, auction.reserved_bond));
+                        break;
+                    }
+                }
+            }
+
+            if let Some((index, mut intent, solver, reserved_bond)) = matched {
+                let intent_id = awaiting_intent_ids.remove(index);
+
+                // bond of the solver no longer backs the filled intent
+                solver::release_reserved_bond(storage, &solver, reserved_bond)?;
+
+                intent.status = IntentStatus::Filled { solver, height };
+                INTENTS.save(storage, intent_id, &intent)?;
+                AWAITING_FILLS.remove(storage, (recipient.to_string(), intent_id));
+
+                filled_intent_ids.push(intent_id);
+            }
+        }
+
+        Ok(filled_intent_ids)
+    }
+}
@@ -1,11 +1,17 @@
 extern crate core;
 
 pub mod admin_logic;
+pub mod auction_logic;
 pub mod contract;
 pub mod error;
+pub mod ica;
 pub mod icq;
+pub mod intent_logic;
+pub mod migrations;
 pub mod msg;
 pub mod orbital_domain;
+pub mod polytone;
+pub mod solver_logic;
 pub mod state;
 pub mod user_logic;
 pub mod utils;
@@ -0,0 +1,170 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use cosmwasm_std::{Addr, Order, Response, StdError, StdResult, Storage, Uint64};
+use neutron_sdk::bindings::msg::NeutronMsg;
+
+use crate::{
+    state::{
+        ClearingAccountConfig, Config, UserConfig, CLEARING_ACCOUNTS, CLEARING_ACCOUNT_ADDRESSES,
+        CONFIG, INTENT_NONCE, LEGACY_ICA_IDENTIFIERS, ORBITAL_DOMAINS, REGISTERED_ICA_IDENTIFIERS,
+        SOLVER_ADDRESS_OWNERS, SOLVER_DOMAIN_ADDRESSES, USER_CONFIGS,
+    },
+    utils::{get_ica_identifier, validate_config},
+};
+
+/// rewrites the keys of clearing accounts registered under the legacy
+/// `{domain}{user_id}` identifiers to the encoding of `utils::get_ica_identifier`.
+/// the legacy identifiers are retained as the registered identifiers of the
+/// moved accounts, since their ICA ports can not be renamed.
+///
+/// legacy identifiers claimed by more than one (user, domain) combination can
+/// not be attributed to a single user. neither can legacy identifiers which
+/// match the current encoding for a registered domain, as a future user of the
+/// domain could claim them. they are left in place and reported in the
+/// `collisions` attribute for manual resolution.
+pub fn migrate_ica_identifiers(storage: &mut dyn Storage) -> StdResult<Response<NeutronMsg>> {
+    let users = USER_CONFIGS
+        .range(storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(_, user_config)| user_config))
+        .collect::<StdResult<Vec<UserConfig>>>()?;
+
+    // every (user, domain) combination, keyed by its legacy identifier
+    let mut claims: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut ica_identifiers = BTreeSet::new();
+    for user_config in users {
+        for domain in user_config.registered_domains {
+            let ica_identifier = get_ica_identifier(user_config.id, domain.to_string());
+            claims
+                .entry(format!("{domain}{}", user_config.id))
+                .or_default()
+                .insert(ica_identifier.to_string());
+            ica_identifiers.insert(ica_identifier);
+        }
+    }
+
+    let domains = ORBITAL_DOMAINS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<BTreeSet<String>>>()?;
+
+    let mut moves = vec![];
+    let mut collisions = vec![];
+    for (legacy_identifier, claimants) in claims {
+        let Some(clearing_account) =
+            CLEARING_ACCOUNTS.may_load(storage, legacy_identifier.to_string())?
+        else {
+            continue;
+        };
+
+        // a legacy identifier matching the current encoding of some other
+        // combination, present or future, is just as ambiguous as one with
+        // several claimants
+        let ambiguous = ica_identifiers.contains(&legacy_identifier)
+            || matches_ica_identifier(&domains, &legacy_identifier);
+        let mut claimants = claimants.into_iter();
+        match (claimants.next(), claimants.next()) {
+            (Some(ica_identifier), None) if !ambiguous => {
+                moves.push((legacy_identifier, ica_identifier, clearing_account))
+            }
+            _ => collisions.push(legacy_identifier),
+        }
+    }
+
+    // legacy keys are all removed before the moved accounts are saved, so
+    // that no account overwrites another one which is yet to be moved
+    for (legacy_identifier, _, _) in moves.iter() {
+        CLEARING_ACCOUNTS.remove(storage, legacy_identifier.to_string());
+    }
+    for (legacy_identifier, ica_identifier, clearing_account) in moves.iter() {
+        CLEARING_ACCOUNTS.save(storage, ica_identifier.to_string(), clearing_account)?;
+        REGISTERED_ICA_IDENTIFIERS.save(storage, ica_identifier.to_string(), legacy_identifier)?;
+        LEGACY_ICA_IDENTIFIERS.save(storage, legacy_identifier.to_string(), ica_identifier)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate_ica_identifiers")
+        .add_attribute("migrated", moves.len().to_string())
+        .add_attribute("collisions", collisions.join(",")))
+}
+
+/// returns whether the identifier is the `utils::get_ica_identifier` encoding
+/// of some user id on one of the domains
+fn matches_ica_identifier(domains: &BTreeSet<String>, identifier: &str) -> bool {
+    identifier.rsplit_once('_').is_some_and(|(domain, id)| {
+        domains.contains(domain)
+            && id
+                .parse::<u64>()
+                .is_ok_and(|user_id| user_id.to_string() == id)
+    })
+}
+
+/// replaces the global orbital configuration. the stored configuration is not
+/// loaded, so this also serves migrations which introduce new config items.
+pub fn update_config(storage: &mut dyn Storage, config: Config) -> StdResult<Response<NeutronMsg>> {
+    validate_config(&config)?;
+    CONFIG.save(storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "update_config"))
+}
+
+/// indexes the addresses of opened clearing accounts and binds every solver
+/// domain address to its solver. addresses set by more than one solver can
+/// not be attributed to a single solver. they are bound to the first solver
+/// and reported in the `conflicts` attribute for manual resolution.
+pub fn index_domain_addresses(storage: &mut dyn Storage) -> StdResult<Response<NeutronMsg>> {
+    let clearing_accounts = CLEARING_ACCOUNTS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Option<ClearingAccountConfig>)>>>()?;
+    for (ica_identifier, clearing_account) in clearing_accounts {
+        if let Some(clearing_account) = clearing_account {
+            CLEARING_ACCOUNT_ADDRESSES.save(storage, clearing_account.addr, &ica_identifier)?;
+        }
+    }
+
+    let solver_addresses = SOLVER_DOMAIN_ADDRESSES
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<((String, String), String)>>>()?;
+    let mut conflicts = vec![];
+    for ((solver, domain), addr) in solver_addresses {
+        match SOLVER_ADDRESS_OWNERS.may_load(storage, (domain.to_string(), addr.to_string()))? {
+            Some(owner) if owner.as_str() != solver => conflicts.push(format!("{domain}:{addr}")),
+            _ => SOLVER_ADDRESS_OWNERS.save(storage, (domain, addr), &Addr::unchecked(solver))?,
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "index_domain_addresses")
+        .add_attribute("conflicts", conflicts.join(",")))
+}
+
+/// applies every migration in order. the configuration is replaced if one is
+/// provided and the intent nonce is initialized if it was never stored. the
+/// attributes of the individual migrations are merged into the response.
+pub fn migrate_all(
+    storage: &mut dyn Storage,
+    config: Option<Config>,
+) -> StdResult<Response<NeutronMsg>> {
+    let mut responses = vec![];
+    match config {
+        Some(config) => responses.push(update_config(storage, config)?),
+        None if !CONFIG.exists(storage) => {
+            return Err(StdError::generic_err(
+                "config must be provided when none is stored",
+            ))
+        }
+        None => (),
+    }
+    if !INTENT_NONCE.exists(storage) {
+        INTENT_NONCE.save(storage, &Uint64::zero())?;
+    }
+    responses.push(migrate_ica_identifiers(storage)?);
+    responses.push(index_domain_addresses(storage)?);
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate_all")
+        .add_attributes(
+            responses
+                .into_iter()
+                .flat_map(|response| response.attributes)
+                .filter(|attr| attr.key != "method"),
+        ))
+}
@@ -1,17 +1,36 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Coin, Decimal, Uint128, Uint64};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use cw_utils::{Duration, Expiration};
+use neutron_sdk::{bindings::types::KVKey, interchain_queries::types::TransactionFilterItem};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     orbital_domain::UncheckedOrbitalDomainConfig,
-    state::{ClearingAccountConfig, Transfer},
+    polytone::CallbackMessage,
+    state::{ClearingAccountConfig, Config, Transfer},
 };
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub owner: String,
+    // minimum bond a solver needs to hold in order to participate in auctions
+    pub solver_bond: Coin,
+    // period that needs to pass before unbonded solver funds can be claimed
+    pub unbonding_period: Duration,
+    // duration of an auction for intent execution rights
+    pub auction_duration: Duration,
+    // minimum amount by which a bid needs to outbid the current highest bid
+    pub min_bid_increment: Uint128,
+    // time the auction winner has to fill the intent before getting slashed
+    pub fill_timeout: Duration,
+    // share of a slashed solver bond that compensates the intent owner
+    pub slashed_bond_user_share: Decimal,
+    // bounds in seconds of the remote tx timeout users can choose instead
+    // of the domain timeout
+    pub min_tx_timeout: Uint64,
+    pub max_tx_timeout: Uint64,
 }
 
 #[cw_ownable_execute]
@@ -24,21 +43,125 @@ pub enum ExecuteMsg {
         // type of account to be used
         account_type: UncheckedOrbitalDomainConfig,
     },
+    /// admin-gated action to update the configuration of a registered domain.
+    /// the account type of the domain can not be changed.
+    UpdateDomain {
+        domain: String,
+        account_type: UncheckedOrbitalDomainConfig,
+    },
+    /// admin-gated action to stop new clearing accounts, withdrawals and
+    /// intents on a domain. callbacks of in-flight txs are still processed.
+    PauseDomain {
+        domain: String,
+    },
+    /// admin-gated action to resume a paused domain
+    UnpauseDomain {
+        domain: String,
+    },
+    /// admin-gated action to remove a domain from orbital
+    DeregisterDomain {
+        domain: String,
+    },
+    /// admin-gated action to update the bounds within which users may set
+    /// the timeouts of their withdrawals and transfers, in seconds
+    UpdateTxTimeouts {
+        min_tx_timeout: Uint64,
+        max_tx_timeout: Uint64,
+    },
     /// register user to orbital
     RegisterUser {},
     /// register user to a specific domain
-    RegisterUserDomain { domain: String },
-    /// user action to withdraw funds from their clearing account
+    RegisterUserDomain {
+        domain: String,
+    },
+    /// reopen the clearing account on a domain after its ICA channel was closed
+    ReopenClearingAccount {
+        domain: String,
+    },
+    /// user action to withdraw funds from their clearing account. all
+    /// withdrawals are executed by a single tx on the remote domain.
     UserWithdrawFunds {
         // domain from which to withdraw funds
         domain: String,
-        // coin to withdraw denominated in target domain
+        withdrawals: Vec<Withdrawal>,
+        // seconds after which the tx times out, defaults to the domain timeout
+        timeout: Option<Uint64>,
+        // memo to tag the tx with
+        memo: Option<String>,
+    },
+    /// user action to transfer funds from their clearing account to neutron
+    /// or another orbital domain over ICS-20
+    UserTransferFunds {
+        // domain from which to transfer funds
+        domain: String,
+        // coin to transfer denominated in the source domain
         coin: Coin,
-        // target address to send funds to
+        // destination domain of the transfer, `neutron` or an orbital domain
+        dest_domain: String,
+        // target address on the destination domain
         dest: String,
+        // seconds after which the transfer times out, defaults to the domain timeout
+        timeout: Option<Uint64>,
+        // memo to tag the tx and the transfer with
+        memo: Option<String>,
+    },
+    /// user action to submit a new intent
+    SubmitIntent {
+        // domain where the offered funds are held
+        input_domain: String,
+        // coin offered by the user, denominated in the input domain
+        input_coin: Coin,
+        // domain where the user wants to receive funds
+        destination_domain: String,
+        // minimum coin to be received, denominated in the destination domain
+        destination_coin: Coin,
+        // expiration after which the intent can no longer be auctioned
+        deadline: Option<Expiration>,
+    },
+    /// user action to cancel their intent before its auction has a winner
+    CancelIntent {
+        id: u64,
+        // optional address on the input domain to release the input funds to.
+        // funds remain in the user's clearing account if not set.
+        release_to: Option<String>,
+    },
+    /// register as a solver by bonding at least the minimum solver bond
+    RegisterSolver {},
+    /// solver action to set their address on a domain where they receive payouts
+    SetSolverDomainAddress {
+        domain: String,
+        addr: String,
+    },
+    /// solver action to add funds to their bond
+    IncreaseBond {},
+    /// solver action to start unbonding their entire bond
+    UnbondSolver {},
+    /// solver action to claim funds whose unbonding period has passed
+    ClaimUnbonded {},
+    /// solver action to bid on the execution rights of an intent
+    Bid {
+        intent_id: u64,
+        // amount of intent destination coin the solver commits to deliver
+        amount: Uint128,
+    },
+    /// permissionless action to close an ended auction and record its winner
+    CloseAuction {
+        intent_id: u64,
+    },
+    /// permissionless action to slash the winner of an auction who did not
+    /// fill the intent before the fill deadline. reopens the intent unless
+    /// its deadline has passed.
+    SlashExpired {
+        intent_id: u64,
+    },
+    /// permissionless action to resubmit the failed payout of a filled intent
+    /// to the winning solver
+    RetryPayout {
+        intent_id: u64,
     },
 
-    // ICQ related messages
+    // ICQ related messages. users may register queries for their own clearing
+    // accounts, the owner for any address. the ICQ deposit is paid by the caller.
     RegisterBalancesQuery {
         connection_id: String,
         update_period: u64,
@@ -51,24 +174,115 @@ pub enum ExecuteMsg {
         recipient: String,
         min_height: Option<u64>,
     },
+    // only the owner of a query may update or remove it. keys and filters can
+    // only be changed by the contract owner, as they determine the monitored
+    // address. removed queries return their deposit to the owner.
+    UpdateInterchainQuery {
+        query_id: u64,
+        new_keys: Option<Vec<KVKey>>,
+        new_update_period: Option<u64>,
+        new_transactions_filter: Option<Vec<TransactionFilterItem>>,
+    },
+    RemoveInterchainQuery {
+        query_id: u64,
+    },
+
+    /// polytone note callback with the outcome of an execution sent through it
+    Callback(CallbackMessage),
+}
+
+#[cw_serde]
+pub struct Withdrawal {
+    // coin to withdraw denominated in target domain
+    pub coin: Coin,
+    // target address to send funds to
+    pub dest: String,
 }
 
 #[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    #[returns(crate::state::Config)]
+    Config {},
+
     #[returns(crate::state::OrbitalDomainConfig)]
     OrbitalDomain { domain: String },
 
+    #[returns(bool)]
+    DomainPaused { domain: String },
+
     #[returns(crate::state::UserConfig)]
     UserConfig { addr: String },
 
     #[returns(Option<ClearingAccountConfig>)]
     ClearingAccountAddress { addr: String, domain: String },
 
+    #[returns(crate::state::SolverConfig)]
+    Solver { addr: String },
+
+    #[returns(Option<String>)]
+    SolverDomainAddress { addr: String, domain: String },
+
+    #[returns(crate::state::Intent)]
+    Intent { id: u64 },
+
+    #[returns(Vec<crate::state::Intent>)]
+    UserIntents { addr: String },
+
+    #[returns(crate::state::Auction)]
+    Auction { intent_id: u64 },
+
+    #[returns(crate::state::Payout)]
+    Payout { intent_id: u64 },
+
+    #[returns(crate::state::IcaTx)]
+    IcaTx { id: u64 },
+
+    /// txs submitted through the user's clearing accounts, ordered by id
+    #[returns(Vec<(u64, crate::state::IcaTx)>)]
+    UserIcaTxs {
+        addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// balances of the user's clearing account on the domain as reported
+    /// by its balances query, keyed by denom
+    #[returns(Vec<(String, crate::state::ClearingBalance)>)]
+    UserBalances { addr: String, domain: String },
+
+    /// deposits into the user's clearing account on the domain credited
+    /// from the results of its transfers query, keyed by denom
+    #[returns(Vec<(String, Uint128)>)]
+    UserDeposits { addr: String, domain: String },
+
+    #[returns(Uint128)]
+    LockedBalance {
+        addr: String,
+        domain: String,
+        denom: String,
+    },
+
     #[returns(neutron_sdk::interchain_queries::v047::queries::BalanceResponse)]
     Balance { query_id: u64 },
 
+    /// deposit paid for the registration of the interchain query
+    #[returns(crate::state::IcqDeposit)]
+    IcqDeposit { query_id: u64 },
+
+    /// ids of the interchain queries monitoring the user's clearing account on the domain
+    #[returns(crate::state::ClearingAccountQueries)]
+    ClearingAccountQueries { addr: String, domain: String },
+
+    /// interchain queries owned by the user, ordered by id
+    #[returns(Vec<(u64, crate::state::IcqDeposit)>)]
+    UserIcqs {
+        addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
     #[returns(GetTransfersAmountResponse)]
     IcqTransfersAmount {},
 
@@ -88,5 +302,22 @@ pub struct RecipientTxsResponse {
     pub transfers: Vec<Transfer>,
 }
 
+/// every migration bumps the stored contract version and applies
+/// the state transformation of the selected variant
 #[cw_serde]
-pub enum MigrateMsg {}
+pub enum MigrateMsg {
+    /// migration which does not transform any state
+    UpdateVersion {},
+    /// rewrites the keys of clearing accounts registered under the
+    /// legacy `{domain}{user_id}` ICA identifiers
+    MigrateIcaIdentifiers {},
+    /// replaces the global orbital configuration
+    UpdateConfig { config: Config },
+    /// indexes the solver domain addresses and clearing account
+    /// addresses set before they were bound to a single owner
+    IndexDomainAddresses {},
+    /// applies every state migration in a single call, so that state of any
+    /// earlier version is brought up to date. the configuration must be
+    /// provided if none is stored yet.
+    MigrateAll { config: Option<Config> },
+}
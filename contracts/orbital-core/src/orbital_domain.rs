@@ -1,12 +1,25 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{ensure, Api, Coin, MessageInfo, StdError, StdResult, Uint64};
-use neutron_sdk::bindings::msg::NeutronMsg;
+use cosmwasm_std::{
+    ensure, Addr, Api, BankMsg, Coin, Env, IbcMsg, IbcTimeout, MessageInfo, StdError, StdResult,
+    SubMsg, Uint128, Uint64,
+};
+use neutron_sdk::{
+    bindings::msg::NeutronMsg, query::min_ibc_fee::query_min_ibc_fee, NeutronResult,
+};
 
 use crate::{
     contract::ExecuteDeps,
     error::ContractError,
-    state::OrbitalDomainConfig,
-    utils::fees::{assert_fee_payment, query_ica_registration_fee},
+    ica, polytone,
+    state::{IcaTxKind, OrbitalDomainConfig, REOPENING_CLEARING_ACCOUNTS, USER_CONFIGS},
+    utils::{
+        fees::{assert_fee_payment, query_ica_registration_fee},
+        get_clearing_account, get_clearing_account_send_tx, get_clearing_account_transfer_tx,
+        get_ica_identifier, get_open_clearing_account, get_registered_ica_identifier,
+        validate_domain, NEUTRON_DOMAIN,
+    },
 };
 
 #[cw_serde]
@@ -14,28 +27,48 @@ pub enum UncheckedOrbitalDomainConfig {
     Polytone {
         note: String,
         timeout: Uint64,
+        // connection to the domain used by interchain queries of its clearing accounts
+        #[serde(default)]
+        connection_id: Option<String>,
+        // ICS-20 channels on the domain keyed by the destination domain or `neutron`
+        #[serde(default)]
+        transfer_channels: BTreeMap<String, String>,
     },
     InterchainAccount {
         connection_id: String,
         channel_id: String,
         timeout: Uint64,
+        // ICS-20 channels on the domain keyed by the destination domain or `neutron`
+        #[serde(default)]
+        transfer_channels: BTreeMap<String, String>,
+        // denoms monitored by the balances query of every new clearing account
+        #[serde(default)]
+        monitored_denoms: Vec<String>,
     },
 }
 
 impl UncheckedOrbitalDomainConfig {
     pub fn try_into_checked(self, api: &dyn Api) -> StdResult<OrbitalDomainConfig> {
         match self {
-            UncheckedOrbitalDomainConfig::Polytone { note, timeout } => {
+            UncheckedOrbitalDomainConfig::Polytone {
+                note,
+                timeout,
+                connection_id,
+                transfer_channels,
+            } => {
                 // ensure that the timeout is > 0
                 ensure!(
                     timeout.u64() > 0,
                     StdError::generic_err("timeout must be non-zero")
                 );
+                validate_transfer_channels(&transfer_channels)?;
 
                 let validated_config = OrbitalDomainConfig::Polytone {
                     // validate the note address on orbital chain
                     note: api.addr_validate(&note)?,
                     timeout,
+                    connection_id,
+                    transfer_channels,
                 };
 
                 Ok(validated_config)
@@ -44,32 +77,72 @@ impl UncheckedOrbitalDomainConfig {
                 connection_id,
                 channel_id,
                 timeout,
+                transfer_channels,
+                monitored_denoms,
             } => {
                 // ensure that the timeout is > 0
                 ensure!(
                     timeout.u64() > 0,
                     StdError::generic_err("timeout must be non-zero")
                 );
+                validate_transfer_channels(&transfer_channels)?;
+                ensure!(
+                    monitored_denoms.iter().all(|denom| !denom.is_empty()),
+                    StdError::generic_err("monitored denoms must be non-empty")
+                );
 
                 Ok(OrbitalDomainConfig::InterchainAccount {
                     connection_id,
                     channel_id,
                     timeout,
+                    transfer_channels,
+                    monitored_denoms,
                 })
             }
         }
     }
 }
 
+/// ensures that transfer channels lead to neutron or valid domain identifiers
+fn validate_transfer_channels(transfer_channels: &BTreeMap<String, String>) -> StdResult<()> {
+    for (dest_domain, channel) in transfer_channels {
+        ensure!(
+            dest_domain == NEUTRON_DOMAIN || validate_domain(dest_domain).is_ok(),
+            StdError::generic_err(format!(
+                "invalid transfer channel destination: {dest_domain}"
+            ))
+        );
+        ensure!(
+            !channel.is_empty(),
+            StdError::generic_err(format!(
+                "transfer channel to {dest_domain} must be non-empty"
+            ))
+        );
+    }
+
+    Ok(())
+}
+
 impl OrbitalDomainConfig {
+    /// builds the message which registers the clearing account of the sender
+    /// on this domain, or reopens it with the same ica identifier. the sender
+    /// covers the ICA registration fee along with the given ICQ deposit.
     pub fn get_registration_message(
         &self,
         deps: ExecuteDeps,
+        env: &Env,
         info: &MessageInfo,
+        domain: &str,
         ica_identifier: String,
-    ) -> Result<NeutronMsg, ContractError> {
+        icq_deposit: &[Coin],
+    ) -> Result<SubMsg<NeutronMsg>, ContractError> {
         match self {
             OrbitalDomainConfig::InterchainAccount { connection_id, .. } => {
+                // re-registrations open a new channel on the existing ICA port
+                let registered_identifier =
+                    get_registered_ica_identifier(deps.storage, &ica_identifier)?;
+                let reopening =
+                    REOPENING_CLEARING_ACCOUNTS.has(deps.storage, ica_identifier.to_string());
                 let response = query_ica_registration_fee(deps)?;
 
                 // if fee_coins is empty, set value to None; otherwise - set it to Some(fee_coins)
@@ -81,8 +154,6 @@ impl OrbitalDomainConfig {
                             amount: coin.amount,
                             denom: coin.denom.to_string(),
                         };
-                        // assert its covered by the sender
-                        assert_fee_payment(info, &fee_coin)?;
                         // collect fee coins
                         fee_coins.push(fee_coin);
                     }
@@ -91,13 +162,231 @@ impl OrbitalDomainConfig {
                     None
                 };
 
-                Ok(NeutronMsg::register_interchain_account(
+                // assert that fees and deposit are covered by the sender
+                let mut required: BTreeMap<String, Uint128> = BTreeMap::new();
+                for coin in registration_fees.iter().flatten().chain(icq_deposit) {
+                    *required.entry(coin.denom.to_string()).or_default() += coin.amount;
+                }
+                for (denom, amount) in required {
+                    assert_fee_payment(info, &Coin::new(amount, denom))?;
+                }
+
+                let register_msg = NeutronMsg::register_interchain_account(
                     connection_id.to_string(),
-                    ica_identifier,
+                    registered_identifier,
                     registration_fees,
-                ))
+                );
+
+                Ok(ica::register_clearing_account(
+                    register_msg,
+                    &info.sender,
+                    domain,
+                    ica_identifier,
+                    reopening,
+                )?)
+            }
+            // the note creates the remote proxy and reports its address in the callback
+            OrbitalDomainConfig::Polytone { note, timeout, .. } => {
+                Ok(SubMsg::new(polytone::get_registration_msg(
+                    env,
+                    note,
+                    *timeout,
+                    &info.sender,
+                    domain,
+                    ica_identifier,
+                )?))
+            }
+        }
+    }
+
+    /// returns the timeout in seconds of remote txs on this domain
+    pub fn timeout(&self) -> Uint64 {
+        match self {
+            OrbitalDomainConfig::InterchainAccount { timeout, .. }
+            | OrbitalDomainConfig::Polytone { timeout, .. } => *timeout,
+        }
+    }
+
+    /// builds the message which sends the `(coin, dest)` pairs out of the owner's
+    /// clearing account on this domain in a single tx and records it in the
+    /// ica tx ledger. IBC fees of ICA txs are paid from the contract balance.
+    /// the tx times out after the domain timeout unless a timeout is given.
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_send_message(
+        &self,
+        deps: ExecuteDeps,
+        env: &Env,
+        owner: &Addr,
+        domain: &str,
+        sends: Vec<(Coin, String)>,
+        kind: IcaTxKind,
+        timeout: Option<Uint64>,
+        memo: Option<String>,
+    ) -> NeutronResult<SubMsg<NeutronMsg>> {
+        let tx_timeout = timeout.unwrap_or_else(|| self.timeout());
+        // input funds of payouts are already held back by the lock of their intent
+        let outflows = match kind {
+            IcaTxKind::Payout { .. } => vec![],
+            _ => sends.iter().map(|(coin, _)| coin.clone()).collect(),
+        };
+
+        match self {
+            OrbitalDomainConfig::InterchainAccount { .. } => {
+                let user_config = USER_CONFIGS.load(deps.storage, owner.to_string())?;
+                let ica_identifier = get_registered_ica_identifier(
+                    deps.storage,
+                    &get_ica_identifier(user_config.id, domain.to_string()),
+                )?;
+                let clearing_account = get_open_clearing_account(deps.storage, owner, domain)?;
+
+                let min_ibc_fee = query_min_ibc_fee(deps.as_ref())?;
+                let send_tx = get_clearing_account_send_tx(
+                    ica_identifier,
+                    clearing_account,
+                    sends,
+                    tx_timeout,
+                    memo.unwrap_or_default(),
+                    min_ibc_fee.min_fee,
+                )?;
+
+                Ok(ica::submit_tx(
+                    deps.storage,
+                    &env.block,
+                    send_tx,
+                    owner,
+                    domain,
+                    kind,
+                    outflows,
+                )?)
+            }
+            OrbitalDomainConfig::Polytone { note, .. } => {
+                // clearing account proxy must have been created
+                get_clearing_account(deps.storage, owner, domain)?;
+
+                let send_msgs = sends
+                    .into_iter()
+                    .map(|(coin, dest)| {
+                        BankMsg::Send {
+                            to_address: dest,
+                            amount: vec![coin],
+                        }
+                        .into()
+                    })
+                    .collect();
+                let note_msg = polytone::submit_tx(
+                    deps.storage,
+                    env,
+                    note,
+                    tx_timeout,
+                    send_msgs,
+                    owner,
+                    domain,
+                    kind,
+                    memo,
+                    outflows,
+                )?;
+
+                Ok(SubMsg::new(note_msg))
+            }
+        }
+    }
+
+    /// returns the ICS-20 channel on this domain which leads to the destination
+    /// domain, if configured
+    pub fn get_transfer_channel(&self, dest_domain: &str) -> Option<String> {
+        match self {
+            OrbitalDomainConfig::InterchainAccount {
+                transfer_channels, ..
+            }
+            | OrbitalDomainConfig::Polytone {
+                transfer_channels, ..
+            } => transfer_channels.get(dest_domain).cloned(),
+        }
+    }
+
+    /// builds the message which transfers the coin out of the owner's clearing
+    /// account on this domain to the destination domain over ICS-20 and records
+    /// it in the ica tx ledger. the transfer times out after the domain timeout
+    /// unless a timeout is given.
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_transfer_message(
+        &self,
+        deps: ExecuteDeps,
+        env: &Env,
+        owner: &Addr,
+        domain: &str,
+        coin: Coin,
+        dest_domain: String,
+        dest: String,
+        timeout: Option<Uint64>,
+        memo: Option<String>,
+    ) -> NeutronResult<SubMsg<NeutronMsg>> {
+        let channel = self.get_transfer_channel(&dest_domain).ok_or_else(|| {
+            ContractError::TransferChannelNotFound(domain.to_string(), dest_domain.to_string())
+        })?;
+        let transfer_timeout = timeout.unwrap_or_else(|| self.timeout());
+        let timeout_timestamp = env.block.time.plus_seconds(transfer_timeout.u64());
+        let kind = IcaTxKind::Transfer { dest_domain };
+        let outflows = vec![coin.clone()];
+
+        match self {
+            OrbitalDomainConfig::InterchainAccount { .. } => {
+                let user_config = USER_CONFIGS.load(deps.storage, owner.to_string())?;
+                let ica_identifier = get_registered_ica_identifier(
+                    deps.storage,
+                    &get_ica_identifier(user_config.id, domain.to_string()),
+                )?;
+                let clearing_account = get_open_clearing_account(deps.storage, owner, domain)?;
+
+                let min_ibc_fee = query_min_ibc_fee(deps.as_ref())?;
+                let transfer_tx = get_clearing_account_transfer_tx(
+                    ica_identifier,
+                    clearing_account,
+                    coin,
+                    channel,
+                    dest,
+                    timeout_timestamp,
+                    self.timeout(),
+                    memo.unwrap_or_default(),
+                    min_ibc_fee.min_fee,
+                )?;
+
+                Ok(ica::submit_tx(
+                    deps.storage,
+                    &env.block,
+                    transfer_tx,
+                    owner,
+                    domain,
+                    kind,
+                    outflows,
+                )?)
+            }
+            OrbitalDomainConfig::Polytone { note, timeout, .. } => {
+                // clearing account proxy must have been created
+                get_clearing_account(deps.storage, owner, domain)?;
+
+                let transfer_msg = IbcMsg::Transfer {
+                    channel_id: channel,
+                    to_address: dest,
+                    amount: coin,
+                    timeout: IbcTimeout::with_timestamp(timeout_timestamp),
+                    memo: memo.clone(),
+                };
+                let note_msg = polytone::submit_tx(
+                    deps.storage,
+                    env,
+                    note,
+                    *timeout,
+                    vec![transfer_msg.into()],
+                    owner,
+                    domain,
+                    kind,
+                    memo,
+                    outflows,
+                )?;
+
+                Ok(SubMsg::new(note_msg))
             }
-            _ => unimplemented!(),
         }
     }
 }
@@ -0,0 +1,307 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    ensure, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Empty, Env, IbcMsg,
+    MessageInfo, Order, Response, StdResult, Storage, SubMsgResponse, Uint64, WasmMsg,
+};
+use neutron_sdk::{
+    bindings::msg::{IbcFee, NeutronMsg},
+    interchain_queries::v047::types::COSMOS_SDK_TRANSFER_MSG_URL,
+    NeutronResult,
+};
+
+use crate::{
+    contract::ExecuteDeps,
+    error::ContractError,
+    ica,
+    state::{
+        ClearingAccountConfig, ClearingAccountStatus, IcaTx, IcaTxKind, IcaTxStatus,
+        OrbitalDomainConfig, CLEARING_ACCOUNTS, CLEARING_ACCOUNT_ADDRESSES, ICA_TXS, ICA_TX_NONCE,
+        ORBITAL_DOMAINS, USER_ICA_TXS,
+    },
+    utils::{escrow, resolve_ica_identifier, IBC_TRANSFER_MSG_URL},
+};
+
+/// execute message of the polytone note. only the variant used by orbital is mirrored.
+#[cw_serde]
+pub enum NoteExecuteMsg {
+    Execute {
+        // address the execution is made for by the controller of the note.
+        // the voice derives the remote proxy from it.
+        on_behalf_of: Option<String>,
+        msgs: Vec<CosmosMsg<Empty>>,
+        callback: Option<CallbackRequest>,
+        timeout_seconds: Uint64,
+    },
+}
+
+/// requests the note to call back the receiver with the outcome of the execution
+#[cw_serde]
+pub struct CallbackRequest {
+    pub receiver: String,
+    pub msg: Binary,
+}
+
+/// callback delivered by the note once the execution was acknowledged or timed out
+#[cw_serde]
+pub struct CallbackMessage {
+    // address which initiated the execution on the note
+    pub initiator: Addr,
+    // `CallbackRequest::msg` of the execution
+    pub initiator_msg: Binary,
+    pub result: Callback,
+}
+
+#[cw_serde]
+pub enum Callback {
+    /// result of a query, never requested by orbital
+    Query(Result<Vec<Binary>, ErrorResponse>),
+    /// result of executing the msgs through the remote proxy
+    Execute(Result<ExecutionResponse, String>),
+    /// execution could not be completed, e.g. because the packet timed out
+    FatalError(String),
+}
+
+#[cw_serde]
+pub struct ErrorResponse {
+    pub message_index: Uint64,
+    pub error: String,
+}
+
+#[cw_serde]
+pub struct ExecutionResponse {
+    // address of the remote proxy which executed the msgs
+    pub executed_by: String,
+    pub result: Vec<SubMsgResponse>,
+}
+
+/// callback payload orbital attaches to the executions it sends through a note
+#[cw_serde]
+pub enum PolytoneCallback {
+    /// creation of the remote proxy serving as the clearing account
+    RegisterClearingAccount {
+        ica_identifier: String,
+        #[serde(default)]
+        owner: Option<Addr>,
+        #[serde(default)]
+        domain: Option<String>,
+    },
+    /// tx recorded in the ica tx ledger
+    Tx { id: u64 },
+}
+
+/// builds the note execution which creates the remote proxy. executing no
+/// msgs instantiates the proxy and returns its address in the callback.
+/// orbital controls the note and executes on behalf of the owner, so the
+/// voice derives a distinct proxy for every user.
+pub fn get_registration_msg(
+    env: &Env,
+    note: &Addr,
+    timeout: Uint64,
+    owner: &Addr,
+    domain: &str,
+    ica_identifier: String,
+) -> StdResult<CosmosMsg<NeutronMsg>> {
+    get_note_execute_msg(
+        env,
+        note,
+        timeout,
+        owner,
+        vec![],
+        &PolytoneCallback::RegisterClearingAccount {
+            ica_identifier,
+            owner: Some(owner.clone()),
+            domain: Some(domain.to_string()),
+        },
+    )
+}
+
+/// records the tx in the ica tx ledger and builds the note execution
+/// which executes its msgs through the remote proxy
+#[allow(clippy::too_many_arguments)]
+pub fn submit_tx(
+    storage: &mut dyn Storage,
+    env: &Env,
+    note: &Addr,
+    timeout: Uint64,
+    msgs: Vec<CosmosMsg<Empty>>,
+    owner: &Addr,
+    domain: &str,
+    kind: IcaTxKind,
+    memo: Option<String>,
+    outflows: Vec<Coin>,
+) -> StdResult<CosmosMsg<NeutronMsg>> {
+    let tx_id = ICA_TX_NONCE.may_load(storage)?.unwrap_or_default();
+    escrow::add_pending_outflows(storage, owner, domain, &outflows)?;
+
+    ICA_TXS.save(
+        storage,
+        tx_id,
+        &IcaTx {
+            owner: owner.clone(),
+            domain: domain.to_string(),
+            kind,
+            msgs: msgs.iter().map(get_msg_type_url).collect(),
+            // polytone packets are relayed without ibc fees
+            fee: IbcFee {
+                recv_fee: vec![],
+                ack_fee: vec![],
+                timeout_fee: vec![],
+            },
+            fee_payer: None,
+            submitted_height: env.block.height,
+            // executions are correlated by the callback payload
+            // rather than the channel and sequence of the packet
+            status: IcaTxStatus::Pending,
+            channel: None,
+            sequence: None,
+            ack: None,
+            // note executions carry no memo, so it is only kept in the ledger
+            memo,
+            outflows,
+        },
+    )?;
+    USER_ICA_TXS.save(storage, (owner.to_string(), tx_id), &Empty {})?;
+    ICA_TX_NONCE.save(storage, &(tx_id + 1))?;
+
+    get_note_execute_msg(
+        env,
+        note,
+        timeout,
+        owner,
+        msgs,
+        &PolytoneCallback::Tx { id: tx_id },
+    )
+}
+
+/// handles the outcome of an execution sent through a polytone note
+pub fn try_handle_callback(
+    deps: ExecuteDeps,
+    env: Env,
+    info: MessageInfo,
+    callback: CallbackMessage,
+) -> NeutronResult<Response<NeutronMsg>> {
+    // callbacks are only accepted from notes of registered domains
+    // and for executions initiated by orbital
+    let is_registered_note = ORBITAL_DOMAINS
+        .range(deps.storage, None, None, Order::Ascending)
+        .any(|domain| {
+            matches!(
+                domain,
+                Ok((_, OrbitalDomainConfig::Polytone { note, .. })) if note == info.sender
+            )
+        });
+    ensure!(is_registered_note, ContractError::UnauthorizedCallback {});
+    ensure!(
+        callback.initiator == env.contract.address,
+        ContractError::UnauthorizedCallback {}
+    );
+
+    let response = Response::new().add_attribute("method", "polytone_callback");
+
+    match from_json::<PolytoneCallback>(&callback.initiator_msg)? {
+        PolytoneCallback::RegisterClearingAccount {
+            ica_identifier,
+            owner,
+            domain,
+        } => match callback.result {
+            Callback::Execute(Ok(execution)) => {
+                let clearing_account_key = resolve_ica_identifier(deps.storage, &ica_identifier)?;
+                CLEARING_ACCOUNT_ADDRESSES.save(
+                    deps.storage,
+                    execution.executed_by.to_string(),
+                    &clearing_account_key,
+                )?;
+                CLEARING_ACCOUNTS.save(
+                    deps.storage,
+                    clearing_account_key.to_string(),
+                    &Some(ClearingAccountConfig {
+                        addr: execution.executed_by.to_string(),
+                        controller_connection_id: info.sender.to_string(),
+                        status: ClearingAccountStatus::Open,
+                    }),
+                )?;
+
+                // payouts which did not complete before the account closed are resubmitted
+                let payout_submsgs =
+                    ica::get_reopened_account_payout_txs(deps, &env, &clearing_account_key)?;
+
+                Ok(response
+                    .add_submessages(payout_submsgs)
+                    .add_attribute("ica_identifier", ica_identifier)
+                    .add_attribute("clearing_account", execution.executed_by))
+            }
+            // new clearing accounts are rolled back so that the user can register to
+            // the domain again. callbacks of registrations which did not record their
+            // owner leave the account awaiting its registration.
+            Callback::Execute(Err(error)) | Callback::FatalError(error) => {
+                let deposit_refund = match (owner, domain) {
+                    (Some(owner), Some(domain)) => ica::roll_back_clearing_account(
+                        deps.storage,
+                        &owner,
+                        &domain,
+                        &resolve_ica_identifier(deps.storage, &ica_identifier)?,
+                    )?,
+                    _ => None,
+                };
+
+                Ok(response
+                    .add_messages(deposit_refund)
+                    .add_attribute("ica_identifier", ica_identifier)
+                    .add_attribute("error", error))
+            }
+            Callback::Query(_) => Err(ContractError::UnauthorizedCallback {}.into()),
+        },
+        PolytoneCallback::Tx { id } => {
+            let (status, ack) = match callback.result {
+                Callback::Execute(Ok(execution)) => {
+                    (IcaTxStatus::Succeeded, Some(to_json_binary(&execution)?))
+                }
+                Callback::Execute(Err(error)) | Callback::FatalError(error) => {
+                    (IcaTxStatus::Failed { error }, None)
+                }
+                Callback::Query(_) => return Err(ContractError::UnauthorizedCallback {}.into()),
+            };
+            let fee_refund = ica::settle_ica_tx(deps.storage, &env.block, id, status, ack)?;
+
+            Ok(response
+                .add_messages(fee_refund)
+                .add_attribute("ica_tx_id", id.to_string()))
+        }
+    }
+}
+
+fn get_note_execute_msg(
+    env: &Env,
+    note: &Addr,
+    timeout: Uint64,
+    owner: &Addr,
+    msgs: Vec<CosmosMsg<Empty>>,
+    callback: &PolytoneCallback,
+) -> StdResult<CosmosMsg<NeutronMsg>> {
+    Ok(WasmMsg::Execute {
+        contract_addr: note.to_string(),
+        msg: to_json_binary(&NoteExecuteMsg::Execute {
+            on_behalf_of: Some(owner.to_string()),
+            msgs,
+            callback: Some(CallbackRequest {
+                receiver: env.contract.address.to_string(),
+                msg: to_json_binary(callback)?,
+            }),
+            timeout_seconds: timeout,
+        })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// type url of the cosmos sdk message the proxy executes for the given msg
+fn get_msg_type_url(msg: &CosmosMsg<Empty>) -> String {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { .. }) => COSMOS_SDK_TRANSFER_MSG_URL.to_string(),
+        CosmosMsg::Ibc(IbcMsg::Transfer { .. }) => IBC_TRANSFER_MSG_URL.to_string(),
+        CosmosMsg::Wasm(WasmMsg::Execute { .. }) => {
+            "/cosmwasm.wasm.v1.MsgExecuteContract".to_string()
+        }
+        _ => "unknown".to_string(),
+    }
+}
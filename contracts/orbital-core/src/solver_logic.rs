@@ -0,0 +1,389 @@
+pub(crate) mod solver {
+    use cosmwasm_std::{
+        coins, ensure, Addr, BankMsg, BlockInfo, Env, MessageInfo, Order, Response, StdError,
+        StdResult, Storage, Uint128,
+    };
+    use cw_ownable::get_ownership;
+    use cw_utils::must_pay;
+    use neutron_sdk::{bindings::msg::NeutronMsg, NeutronResult};
+
+    use crate::{
+        auction_logic::auction,
+        contract::ExecuteDeps,
+        error::ContractError,
+        intent_logic::intent,
+        state::{
+            IntentStatus, SolverConfig, UnbondingBond, AUCTIONS, AWAITING_FILLS,
+            CLEARING_ACCOUNT_ADDRESSES, CONFIG, INTENTS, ORBITAL_DOMAINS, SOLVERS,
+            SOLVER_ADDRESS_OWNERS, SOLVER_BIDS, SOLVER_DOMAIN_ADDRESSES,
+        },
+        utils::{escrow, get_clearing_account},
+    };
+
+    pub fn try_register_solver(
+        deps: ExecuteDeps,
+        info: MessageInfo,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        // solver can only register once
+        ensure!(
+            !SOLVERS.has(deps.storage, info.sender.to_string()),
+            ContractError::SolverAlreadyRegistered {}
+        );
+
+        let config = CONFIG.load(deps.storage)?;
+
+        // the initial bond must cover the minimum solver bond
+        let bond =
+            must_pay(&info, &config.solver_bond.denom).map_err(ContractError::FeePaymentError)?;
+        ensure!(
+            bond >= config.solver_bond.amount,
+            ContractError::InsufficientBond {}
+        );
+
+        SOLVERS.save(
+            deps.storage,
+            info.sender.to_string(),
+            &SolverConfig {
+                bond,
+                unbonding: None,
+                reserved: Uint128::zero(),
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("method", "register_solver")
+            .add_attribute("bond", bond.to_string()))
+    }
+
+    pub fn try_increase_bond(
+        deps: ExecuteDeps,
+        info: MessageInfo,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let mut solver_config = SOLVERS
+            .may_load(deps.storage, info.sender.to_string())?
+            .ok_or(ContractError::SolverNotRegistered {})?;
+
+        let config = CONFIG.load(deps.storage)?;
+
+        let amount =
+            must_pay(&info, &config.solver_bond.denom).map_err(ContractError::FeePaymentError)?;
+        solver_config.bond = solver_config.bond.checked_add(amount)?;
+
+        SOLVERS.save(deps.storage, info.sender.to_string(), &solver_config)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "increase_bond")
+            .add_attribute("bond", solver_config.bond.to_string()))
+    }
+
+    pub fn try_set_domain_address(
+        deps: ExecuteDeps,
+        info: MessageInfo,
+        domain: String,
+        addr: String,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        ensure!(
+            SOLVERS.has(deps.storage, info.sender.to_string()),
+            ContractError::SolverNotRegistered {}
+        );
+        ensure!(
+            ORBITAL_DOMAINS.has(deps.storage, domain.to_string()),
+            ContractError::UnknownDomain(domain)
+        );
+        ensure!(
+            !addr.is_empty(),
+            StdError::generic_err("solver domain address must be non-empty")
+        );
+
+        // transfers out of the address fill intents on behalf of the solver, so it
+        // must not be bound to another solver or serve as a clearing account
+        let bound_solver =
+            SOLVER_ADDRESS_OWNERS.may_load(deps.storage, (domain.to_string(), addr.to_string()))?;
+        ensure!(
+            !matches!(bound_solver, Some(solver) if solver != info.sender)
+                && !CLEARING_ACCOUNT_ADDRESSES.has(deps.storage, addr.to_string()),
+            ContractError::DomainAddressInUse(addr)
+        );
+
+        // previous address of the solver on the domain is released
+        if let Some(previous_addr) = SOLVER_DOMAIN_ADDRESSES
+            .may_load(deps.storage, (info.sender.to_string(), domain.to_string()))?
+        {
+            SOLVER_ADDRESS_OWNERS.remove(deps.storage, (domain.to_string(), previous_addr));
+        }
+        SOLVER_ADDRESS_OWNERS.save(
+            deps.storage,
+            (domain.to_string(), addr.to_string()),
+            &info.sender,
+        )?;
+
+        // payouts for intents with input funds on this domain are sent to this address
+        SOLVER_DOMAIN_ADDRESSES.save(
+            deps.storage,
+            (info.sender.to_string(), domain.to_string()),
+            &addr,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_solver_domain_address")
+            .add_attribute("domain", domain)
+            .add_attribute("addr", addr))
+    }
+
+    pub fn try_unbond(
+        deps: ExecuteDeps,
+        env: Env,
+        info: MessageInfo,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let mut solver_config = SOLVERS
+            .may_load(deps.storage, info.sender.to_string())?
+            .ok_or(ContractError::SolverNotRegistered {})?;
+
+        ensure!(
+            !solver_config.bond.is_zero(),
+            ContractError::NothingToUnbond {}
+        );
+        assert_bond_released(deps.storage, &env.block, &info.sender)?;
+
+        let config = CONFIG.load(deps.storage)?;
+
+        // the entire bond is moved into unbonding. if there is an ongoing
+        // unbonding, the amounts are merged and the unbonding period restarts.
+        let unbonding_amount = match solver_config.unbonding {
+            Some(unbonding) => unbonding.amount.checked_add(solver_config.bond)?,
+            None => solver_config.bond,
+        };
+        let release_at = config.unbonding_period.after(&env.block);
+
+        solver_config.bond = Uint128::zero();
+        solver_config.unbonding = Some(UnbondingBond {
+            amount: unbonding_amount,
+            release_at,
+        });
+
+        SOLVERS.save(deps.storage, info.sender.to_string(), &solver_config)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "unbond_solver")
+            .add_attribute("unbonding_amount", unbonding_amount.to_string())
+            .add_attribute("release_at", release_at.to_string()))
+    }
+
+    pub fn try_claim_unbonded(
+        deps: ExecuteDeps,
+        env: Env,
+        info: MessageInfo,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let mut solver_config = SOLVERS
+            .may_load(deps.storage, info.sender.to_string())?
+            .ok_or(ContractError::SolverNotRegistered {})?;
+
+        // unbonding must exist and its release expiration must have passed
+        let unbonding = match solver_config.unbonding {
+            Some(unbonding) if unbonding.release_at.is_expired(&env.block) => unbonding,
+            _ => return Err(ContractError::NothingToClaim {}.into()),
+        };
+        // unbonding funds remain slashable until the solver's auctions settle
+        assert_bond_released(deps.storage, &env.block, &info.sender)?;
+
+        let config = CONFIG.load(deps.storage)?;
+
+        // solvers with nothing left bonded are removed from the registry
+        solver_config.unbonding = None;
+        if solver_config.bond.is_zero() {
+            SOLVERS.remove(deps.storage, info.sender.to_string());
+        } else {
+            SOLVERS.save(deps.storage, info.sender.to_string(), &solver_config)?;
+        }
+
+        let claim_msg = BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(unbonding.amount.u128(), config.solver_bond.denom),
+        };
+
+        Ok(Response::new()
+            .add_message(claim_msg)
+            .add_attribute("method", "claim_unbonded")
+            .add_attribute("amount", unbonding.amount.to_string()))
+    }
+
+    /// ensures that the solver neither holds the leading bid of a running auction
+    /// nor the execution rights of an unfilled intent, whose bond could otherwise
+    /// be withdrawn before it is slashed. settled intents are dropped from the index.
+    fn assert_bond_released(
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        solver: &Addr,
+    ) -> Result<(), ContractError> {
+        let intent_ids = SOLVER_BIDS
+            .prefix(solver.to_string())
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+
+        for intent_id in intent_ids {
+            let intent = intent::load_intent(storage, block, intent_id)?;
+            let auction = AUCTIONS.load(storage, intent_id)?;
+
+            let bond_in_use = match intent.status {
+                IntentStatus::Auctioning => auction
+                    .highest_bid
+                    .is_some_and(|highest_bid| &highest_bid.solver == solver),
+                IntentStatus::Won => auction.winner.as_ref() == Some(solver),
+                _ => false,
+            };
+            ensure!(!bond_in_use, ContractError::SolverBondInUse(intent_id));
+
+            SOLVER_BIDS.remove(storage, (solver.to_string(), intent_id));
+        }
+
+        Ok(())
+    }
+
+    /// takes the amount out of the solver's bond, followed by its unbonding
+    /// funds, and releases the reservation of the slashed auction. returns
+    /// the amount actually slashed.
+    fn slash_bond(
+        storage: &mut dyn Storage,
+        solver: &Addr,
+        mut solver_config: SolverConfig,
+        amount: Uint128,
+    ) -> StdResult<Uint128> {
+        let from_bond = amount.min(solver_config.bond);
+        solver_config.bond = solver_config.bond.checked_sub(from_bond)?;
+        solver_config.reserved = solver_config.reserved.saturating_sub(amount);
+
+        let mut from_unbonding = Uint128::zero();
+        if let Some(unbonding) = solver_config.unbonding.as_mut() {
+            from_unbonding = amount.checked_sub(from_bond)?.min(unbonding.amount);
+            unbonding.amount = unbonding.amount.checked_sub(from_unbonding)?;
+        }
+        if solver_config
+            .unbonding
+            .as_ref()
+            .is_some_and(|unbonding| unbonding.amount.is_zero())
+        {
+            solver_config.unbonding = None;
+        }
+
+        if solver_config.bond.is_zero() && solver_config.unbonding.is_none() {
+            SOLVERS.remove(storage, solver.to_string());
+        } else {
+            SOLVERS.save(storage, solver.to_string(), &solver_config)?;
+        }
+
+        Ok(from_bond.checked_add(from_unbonding)?)
+    }
+
+    /// releases the bond the solver reserved for a won auction once its intent is filled
+    pub fn release_reserved_bond(
+        storage: &mut dyn Storage,
+        solver: &Addr,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        let Some(mut solver_config) = SOLVERS.may_load(storage, solver.to_string())? else {
+            return Ok(());
+        };
+        solver_config.reserved = solver_config.reserved.saturating_sub(amount);
+
+        SOLVERS.save(storage, solver.to_string(), &solver_config)
+    }
+
+    pub fn try_slash_expired(
+        deps: ExecuteDeps,
+        env: Env,
+        intent_id: u64,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let mut intent = intent::load_intent(deps.storage, &env.block, intent_id)?;
+        let auction = AUCTIONS.load(deps.storage, intent_id)?;
+
+        ensure!(
+            intent.status == IntentStatus::Won,
+            ContractError::UnexpectedIntentStatus(intent.status)
+        );
+        // winner can only be slashed once the fill deadline has passed
+        ensure!(
+            auction
+                .fill_deadline
+                .is_some_and(|deadline| deadline.is_expired(&env.block)),
+            ContractError::FillDeadlineNotReached {}
+        );
+        let winner = auction
+            .winner
+            .ok_or(ContractError::UnexpectedIntentStatus(intent.status.clone()))?;
+
+        let config = CONFIG.load(deps.storage)?;
+
+        // the bond reserved by the auction is slashed, reaching into funds which
+        // are currently unbonding if needed. auctions closed before bonds were
+        // reserved slash the minimum bond. the solver is removed from the
+        // registry once nothing is left bonded.
+        let reserved_bond = if auction.reserved_bond.is_zero() {
+            config.solver_bond.amount
+        } else {
+            auction.reserved_bond
+        };
+        let slashed_amount = match SOLVERS.may_load(deps.storage, winner.to_string())? {
+            Some(solver_config) => slash_bond(deps.storage, &winner, solver_config, reserved_bond)?,
+            None => Uint128::zero(),
+        };
+
+        // part of the slashed bond compensates the intent owner, the remainder goes
+        // to the contract owner. without an owner, the intent owner receives it all.
+        let owner = get_ownership(deps.storage)?.owner;
+        let user_compensation = match owner {
+            Some(_) => slashed_amount.mul_floor(config.slashed_bond_user_share),
+            None => slashed_amount,
+        };
+        let owner_share = slashed_amount.checked_sub(user_compensation)?;
+
+        let mut response = Response::new();
+        if !user_compensation.is_zero() {
+            response = response.add_message(BankMsg::Send {
+                to_address: intent.owner.to_string(),
+                amount: coins(user_compensation.u128(), &config.solver_bond.denom),
+            });
+        }
+        if let Some(owner) = owner.filter(|_| !owner_share.is_zero()) {
+            response = response.add_message(BankMsg::Send {
+                to_address: owner.to_string(),
+                amount: coins(owner_share.u128(), &config.solver_bond.denom),
+            });
+        }
+
+        // intent no longer awaits the fill of the slashed solver
+        let recipient =
+            get_clearing_account(deps.storage, &intent.owner, &intent.destination_domain)?;
+        AWAITING_FILLS.remove(deps.storage, (recipient.addr, intent_id));
+
+        // intent is reopened and its execution rights auctioned again,
+        // unless its deadline has passed in the meantime
+        let deadline_passed = intent
+            .deadline
+            .is_some_and(|deadline| deadline.is_expired(&env.block));
+        let reopened = !deadline_passed;
+        if reopened {
+            intent.status = IntentStatus::Open;
+            auction::open_auction(deps.storage, &env.block, intent_id)?;
+        } else {
+            intent.status = IntentStatus::Slashed {
+                solver: winner.clone(),
+            };
+            escrow::unlock(
+                deps.storage,
+                &intent.owner,
+                &intent.input_domain,
+                &intent.input_coin,
+            )?;
+        }
+        INTENTS.save(deps.storage, intent_id, &intent)?;
+
+        Ok(response
+            .add_attribute("method", "slash_expired")
+            .add_attribute("intent_id", intent_id.to_string())
+            .add_attribute("solver", winner)
+            .add_attribute("slashed_amount", slashed_amount.to_string())
+            .add_attribute("user_compensation", user_compensation.to_string())
+            .add_attribute("owner_share", owner_share.to_string())
+            .add_attribute("reopened", reopened.to_string()))
+    }
+}
@@ -1,9 +1,16 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint64};
+use cosmwasm_std::{Addr, Binary, BlockInfo, Coin, Decimal, Empty, Uint128, Uint64};
 use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
+use neutron_sdk::bindings::msg::IbcFee;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// global orbital configuration
+pub const CONFIG: Item<Config> = Item::new("config");
+
 /// keeping track of registered user IDs which get incremented
 /// with each new registration. it's needed to generate unique
 /// user clearing account identifiers.
@@ -15,6 +22,11 @@ pub const USER_CONFIGS: Map<String, UserConfig> = Map::new("user_configs");
 /// map of registered remote domains and their configuration
 pub const ORBITAL_DOMAINS: Map<String, OrbitalDomainConfig> = Map::new("domains");
 
+/// set of registered domains paused by the owner. paused domains reject new
+/// clearing accounts, withdrawals and intents, while callbacks of the txs and
+/// queries already in flight keep being processed.
+pub const PAUSED_DOMAINS: Map<String, Empty> = Map::new("paused_domains");
+
 /// map of clearing accounts registered with orbital.
 /// key is a composite of (user_id, domain) generated with
 /// `utils::get_ica_identifier`. value is an optional address where:
@@ -23,11 +35,209 @@ pub const ORBITAL_DOMAINS: Map<String, OrbitalDomainConfig> = Map::new("domains"
 pub const CLEARING_ACCOUNTS: Map<String, Option<ClearingAccountConfig>> =
     Map::new("clearing_accounts");
 
+/// map of clearing account keys to the legacy `{domain}{user_id}` identifiers
+/// their accounts were registered with. ICA ports can not be renamed, so txs
+/// and re-registrations of these accounts keep using the legacy identifier.
+pub const REGISTERED_ICA_IDENTIFIERS: Map<String, String> = Map::new("registered_ica_identifiers");
+
+/// inverse of `REGISTERED_ICA_IDENTIFIERS`, mapping legacy identifiers
+/// to the keys of their clearing accounts
+pub const LEGACY_ICA_IDENTIFIERS: Map<String, String> = Map::new("legacy_ica_identifiers");
+
+/// keeping track of submitted intent IDs which get incremented
+/// with each new intent submission.
+pub const INTENT_NONCE: Item<Uint64> = Item::new("intent_nonce");
+
+/// map of intents submitted to orbital, keyed by their id
+pub const INTENTS: Map<u64, Intent> = Map::new("intents");
+
+/// index of intents submitted by each user.
+/// key is a composite of (user address, intent id).
+pub const USER_INTENTS: Map<(String, u64), Empty> = Map::new("user_intents");
+
+/// map of solvers registered with orbital along with their bonds
+pub const SOLVERS: Map<String, SolverConfig> = Map::new("solvers");
+
+/// map of solver addresses on remote domains where payouts are delivered.
+/// key is a composite of (solver address, domain).
+pub const SOLVER_DOMAIN_ADDRESSES: Map<(String, String), String> =
+    Map::new("solver_domain_addresses");
+
+/// index of the solver each remote address is bound to. an address can be
+/// set by a single solver only. key is a composite of (domain, address).
+pub const SOLVER_ADDRESS_OWNERS: Map<(String, String), Addr> = Map::new("solver_address_owners");
+
+/// index of clearing account addresses, mapping each address to the
+/// ICA identifier of its clearing account
+pub const CLEARING_ACCOUNT_ADDRESSES: Map<String, String> = Map::new("clearing_account_addresses");
+
+/// index of intents on which solvers placed bids, used to keep the bonds backing
+/// leading bids and won auctions from being unbonded.
+/// key is a composite of (solver address, intent id).
+pub const SOLVER_BIDS: Map<(String, u64), Empty> = Map::new("solver_bids");
+
+/// map of auctions for intent execution rights, keyed by intent id
+pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
+
+/// index of intents whose auctions were won and which await a verified fill.
+/// key is a composite of (recipient clearing account address, intent id) where
+/// the recipient is the intent owner's clearing account on the destination domain.
+pub const AWAITING_FILLS: Map<(String, u64), Empty> = Map::new("awaiting_fills");
+
+/// latest remote height observed in tx query results, keyed by the connection
+/// id of the query. bounds the age of the txs which may fill won auctions.
+pub const REMOTE_HEIGHTS: Map<String, u64> = Map::new("remote_heights");
+
+/// map of solver payouts for filled intents, keyed by intent id
+pub const PAYOUTS: Map<u64, Payout> = Map::new("payouts");
+
+/// keeping track of outbound ICA tx IDs which get incremented
+/// with each tx submitted through a clearing account.
+pub const ICA_TX_NONCE: Item<u64> = Item::new("ica_tx_nonce");
+
+/// map of outbound ICA txs submitted through clearing accounts, keyed by their id
+pub const ICA_TXS: Map<u64, IcaTx> = Map::new("ica_txs");
+
+/// index of outbound ICA txs by the (channel, sequence) of their packet,
+/// used to correlate the ICA callbacks with the originating tx
+pub const ICA_TX_SEQUENCES: Map<(String, u64), u64> = Map::new("ica_tx_sequences");
+
+/// map of (user addr, ica tx id) for the txs submitted through the user's clearing accounts
+pub const USER_ICA_TXS: Map<(String, u64), Empty> = Map::new("user_ica_txs");
+
+/// map of query_id => deposit paid for the registration of the interchain
+/// query, to be returned to its payer once the query is removed. the payer
+/// owns the query and is the only one allowed to update or remove it.
+pub const ICQ_DEPOSITS: Map<u64, IcqDeposit> = Map::new("icq_deposits");
+
+/// map of (owner address, query id) for the interchain queries owned by the user
+pub const USER_ICQS: Map<(String, u64), Empty> = Map::new("user_icqs");
+
+/// map of ica identifiers of new clearing accounts to the interchain queries
+/// which are registered once their ICA channel is opened
+pub const PENDING_ICQ_REGISTRATIONS: Map<String, PendingIcqRegistration> =
+    Map::new("pending_icq_registrations");
+
+/// map of ica identifiers of closed clearing accounts which are being reopened
+/// to their (owner, domain). payouts which did not complete are resubmitted
+/// once the ICA channel of the account is reopened.
+pub const REOPENING_CLEARING_ACCOUNTS: Map<String, (Addr, String)> =
+    Map::new("reopening_clearing_accounts");
+
+/// map of (user address, domain) => ids of the interchain queries
+/// registered when the user's clearing account on the domain opened
+pub const CLEARING_ACCOUNT_QUERIES: Map<(String, String), ClearingAccountQueries> =
+    Map::new("clearing_account_queries");
+
+/// snapshot of the balances held in clearing accounts as reported by the
+/// latest result of their balances query. key is a composite of
+/// (user address, domain, denom).
+pub const CLEARING_BALANCES: Map<(String, String, String), ClearingBalance> =
+    Map::new("clearing_balances");
+
+/// amounts of clearing account funds pledged to active intents.
+/// key is a composite of (user address, domain, denom).
+pub const LOCKED_BALANCES: Map<(String, String, String), Uint128> = Map::new("locked_balances");
+
+/// amounts of clearing account funds leaving with submitted withdrawals and
+/// transfers which did not settle yet. key is a composite of
+/// (user address, domain, denom).
+pub const PENDING_OUTFLOWS: Map<(String, String, String), Uint128> = Map::new("pending_outflows");
+
+/// outflows of executed txs which are still held back in the pending outflows,
+/// as the balances query reported the clearing account before they left it.
+/// key is a composite of (user address, domain, denom).
+pub const SETTLED_OUTFLOWS: Map<(String, String, String), Vec<SettledOutflow>> =
+    Map::new("settled_outflows");
+
 /// contains all transfers mapped by a recipient address observed by the contract.
 pub const RECIPIENT_TXS: Map<String, Vec<Transfer>> = Map::new("recipient_txs");
 /// contains number of transfers to addresses observed by the contract.
 pub const TRANSFERS: Item<u64> = Item::new("transfers");
 
+/// tx query results which were already processed for a recipient, used to skip
+/// replays. key is a composite of (recipient, height, hex encoded sha256 hash of
+/// the `TxRaw`).
+pub const PROCESSED_TXS: Map<(String, u64, String), Empty> = Map::new("processed_txs");
+
+/// index of transfers queries by the recipient they monitor. every recipient is
+/// monitored by a single transfers query, so that its deposits are credited once.
+pub const TRANSFERS_QUERIES: Map<String, u64> = Map::new("transfers_queries");
+
+/// deposits into clearing accounts credited from the results of transfers
+/// queries. key is a composite of (user address, domain, denom).
+pub const CREDITED_DEPOSITS: Map<(String, String, String), Uint128> = Map::new("credited_deposits");
+
+/// deposit escrowed by neutron for a registered interchain query
+#[cw_serde]
+pub struct IcqDeposit {
+    // account which paid the deposit
+    pub payer: Addr,
+    pub deposit: Vec<Coin>,
+    // address monitored by the query
+    pub addr: String,
+    // denoms reported by the query if it is a balances query
+    #[serde(default)]
+    pub denoms: Vec<String>,
+}
+
+/// interchain queries awaiting the opening of a new clearing account
+#[cw_serde]
+pub struct PendingIcqRegistration {
+    pub owner: Addr,
+    pub domain: String,
+    // denoms monitored by the balances query, which is only
+    // registered if there are any
+    pub monitored_denoms: Vec<String>,
+    // deposit paid by the owner for each of the queries
+    pub deposit: Vec<Coin>,
+}
+
+impl PendingIcqRegistration {
+    /// number of queries to be registered
+    pub fn query_count(&self) -> u128 {
+        if self.monitored_denoms.is_empty() {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// deposit paid by the owner for all of the queries
+    pub fn total_deposit(&self) -> Vec<Coin> {
+        self.deposit
+            .iter()
+            .map(|coin| {
+                Coin::new(
+                    coin.amount * Uint128::new(self.query_count()),
+                    coin.denom.to_string(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cw_serde]
+pub struct SettledOutflow {
+    pub amount: Uint128,
+    // neutron height at which the tx of the outflow settled
+    pub settled_height: u64,
+}
+
+#[cw_serde]
+pub struct ClearingBalance {
+    pub amount: Uint128,
+    // neutron height at which the balance was reported
+    pub last_updated_height: u64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct ClearingAccountQueries {
+    pub balances_query_id: Option<u64>,
+    pub transfers_query_id: Option<u64>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Transfer {
     pub recipient: String,
@@ -36,10 +246,63 @@ pub struct Transfer {
     pub amount: String,
 }
 
+#[cw_serde]
+pub struct Config {
+    // minimum bond a solver needs to hold in order to participate in auctions
+    pub solver_bond: Coin,
+    // period that needs to pass before unbonded funds can be claimed
+    pub unbonding_period: Duration,
+    // duration of an auction for intent execution rights
+    pub auction_duration: Duration,
+    // minimum amount by which a bid needs to outbid the current highest bid
+    pub min_bid_increment: Uint128,
+    // period after the auction close within which the winner needs to fill the intent
+    pub fill_timeout: Duration,
+    // share of a slashed bond which compensates the intent owner. the
+    // remainder goes to the contract owner.
+    pub slashed_bond_user_share: Decimal,
+    // bounds in seconds of the remote tx timeout users can choose instead
+    // of the domain timeout
+    pub min_tx_timeout: Uint64,
+    pub max_tx_timeout: Uint64,
+}
+
+#[cw_serde]
+pub struct SolverConfig {
+    // amount of bond denom currently bonded by the solver
+    pub bond: Uint128,
+    // funds which are being unbonded, if any
+    pub unbonding: Option<UnbondingBond>,
+    // part of the bond backing won auctions which await their fill
+    #[serde(default)]
+    pub reserved: Uint128,
+}
+
+#[cw_serde]
+pub struct UnbondingBond {
+    pub amount: Uint128,
+    // expiration after which the unbonded funds can be claimed
+    pub release_at: Expiration,
+}
+
 #[cw_serde]
 pub struct ClearingAccountConfig {
     pub addr: String,
     pub controller_connection_id: String,
+    // accounts stored before their status was tracked are open
+    #[serde(default)]
+    pub status: ClearingAccountStatus,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum ClearingAccountStatus {
+    /// ICA channel is open and txs can be submitted
+    #[default]
+    Open,
+    /// ICA channel was closed after a packet timeout. the account keeps its
+    /// remote address and funds, but needs to be reopened to submit txs.
+    Closed,
 }
 
 #[cw_serde]
@@ -50,17 +313,200 @@ pub struct UserConfig {
 
 /// remote domain configuration config which supports different types of account implementations.
 /// currently supported types:
-/// - Polytone: cw-based account implementation that operates via note contract on the origin chain
+/// - Polytone: cw-based account implementation that operates via note contract on the origin chain.
+///   the note must be controlled by orbital, which executes on behalf of each user.
 /// - InterchainAccount: interchain account implementation based on ICS-27
+///
+/// `transfer_channels` map destination domains (or `neutron`) to the ICS-20
+/// channels on this domain which lead to them. the `connection_id` of a polytone
+/// domain is only used to authorize interchain queries of its clearing accounts.
 #[cw_serde]
 pub enum OrbitalDomainConfig {
     Polytone {
         note: Addr,
         timeout: Uint64,
+        #[serde(default)]
+        connection_id: Option<String>,
+        #[serde(default)]
+        transfer_channels: BTreeMap<String, String>,
     },
     InterchainAccount {
         connection_id: String,
         channel_id: String,
         timeout: Uint64,
+        #[serde(default)]
+        transfer_channels: BTreeMap<String, String>,
+        #[serde(default)]
+        monitored_denoms: Vec<String>,
+    },
+}
+
+/// declarative statement of a user who wants to exchange funds held in their
+/// clearing account on the input domain for funds delivered to their clearing
+/// account on the destination domain.
+#[cw_serde]
+pub struct Intent {
+    pub id: Uint64,
+    pub owner: Addr,
+    // domain where the offered funds are held
+    pub input_domain: String,
+    // coin offered by the user, denominated in the input domain
+    pub input_coin: Coin,
+    // domain where the user wants to receive funds
+    pub destination_domain: String,
+    // minimum coin to be received, denominated in the destination domain
+    pub destination_coin: Coin,
+    pub status: IntentStatus,
+    // block height at which the intent was submitted
+    pub submitted_at: u64,
+    // expiration after which the intent can no longer be auctioned, if any
+    pub deadline: Option<Expiration>,
+}
+
+impl Intent {
+    /// intents which did not find a winner before their deadline are expired.
+    /// expiry is applied lazily whenever the intent is loaded.
+    pub fn apply_expiry(&mut self, block: &BlockInfo) {
+        let deadline_passed = self
+            .deadline
+            .is_some_and(|deadline| deadline.is_expired(block));
+
+        if deadline_passed && matches!(self.status, IntentStatus::Open | IntentStatus::Auctioning) {
+            self.status = IntentStatus::Expired;
+        }
+    }
+}
+
+#[cw_serde]
+pub enum IntentStatus {
+    /// intent has been submitted and awaits a solver
+    Open,
+    /// at least one solver has placed a bid on the intent
+    Auctioning,
+    /// auction has been closed and the winner has exclusive execution rights
+    Won,
+    /// winning solver's delivery to the user's clearing account was verified
+    Filled {
+        solver: Addr,
+        // remote domain height at which the fill was observed
+        height: u64,
     },
+    /// winning solver missed the fill deadline after the intent deadline passed,
+    /// so the intent could not be auctioned again
+    Slashed { solver: Addr },
+    /// intent was cancelled by its owner before the auction had a winner
+    Cancelled,
+    /// intent deadline passed before the auction had a winner
+    Expired,
+}
+
+/// english auction in which bonded solvers compete for the exclusive right
+/// to execute an intent. bids are denominated in the destination coin of the
+/// intent and represent the amount the solver commits to deliver.
+#[cw_serde]
+pub struct Auction {
+    // expiration after which no more bids are accepted and the auction can be closed
+    pub end: Expiration,
+    // highest bid placed so far, if any
+    pub highest_bid: Option<Bid>,
+    // solver with exclusive execution rights, set once the auction is closed
+    pub winner: Option<Addr>,
+    // expiration by which the winner needs to fill the intent, set once the auction is closed
+    pub fill_deadline: Option<Expiration>,
+    // remote height of the destination domain known when the auction was closed.
+    // txs at or below it predate the auction and can not fill the intent.
+    #[serde(default)]
+    pub min_fill_height: u64,
+    // address of the winner on the destination domain, set once the auction is
+    // closed. only transfers sent from it can fill the intent.
+    #[serde(default)]
+    pub fill_sender: Option<String>,
+    // bond of the winner reserved until the intent is filled or the winner slashed
+    #[serde(default)]
+    pub reserved_bond: Uint128,
+}
+
+/// transfer of the intent input funds from the user's clearing account
+/// to the winning solver's address on the input domain
+#[cw_serde]
+pub struct Payout {
+    // solver address on the input domain
+    pub recipient: String,
+    pub amount: Coin,
+    pub status: PayoutStatus,
+    // id of the latest ica tx submitted for the payout. outcomes of earlier
+    // txs which were superseded by a resubmission are ignored.
+    #[serde(default)]
+    pub tx_id: Option<u64>,
+}
+
+#[cw_serde]
+pub enum PayoutStatus {
+    /// payout tx was submitted and awaits the ICA acknowledgement
+    Pending,
+    /// payout tx was executed on the input domain
+    Completed,
+    /// payout tx failed or timed out
+    Failed,
+}
+
+/// transaction submitted through a user's clearing account
+#[cw_serde]
+pub struct IcaTx {
+    // user owning the clearing account
+    pub owner: Addr,
+    // domain of the clearing account
+    pub domain: String,
+    pub kind: IcaTxKind,
+    // type urls of the messages executed by the tx
+    pub msgs: Vec<String>,
+    // ibc fee escrowed by orbital-core for relaying the tx
+    pub fee: IbcFee,
+    // account which deposited the ibc fee and receives its refunds. fees
+    // of txs without a payer are covered by orbital-core.
+    pub fee_payer: Option<Addr>,
+    // neutron block height at which the tx was submitted
+    pub submitted_height: u64,
+    pub status: IcaTxStatus,
+    // channel and sequence of the packet, known once neutron accepted the tx
+    pub channel: Option<String>,
+    pub sequence: Option<u64>,
+    // acknowledgement data returned by the remote domain on success
+    pub ack: Option<Binary>,
+    // memo the tx was tagged with by the user, if any
+    pub memo: Option<String>,
+    // funds the user withdraws or transfers out of the clearing account
+    // with the tx, held back from the available balance until it settles
+    #[serde(default)]
+    pub outflows: Vec<Coin>,
+}
+
+#[cw_serde]
+pub enum IcaTxKind {
+    /// user withdrawal of clearing account funds
+    Withdrawal,
+    /// payout of intent input funds to the filling solver
+    Payout { intent_id: u64 },
+    /// user transfer of clearing account funds to another domain over ICS-20
+    Transfer { dest_domain: String },
+}
+
+#[cw_serde]
+pub enum IcaTxStatus {
+    /// tx was submitted and awaits neutron to assign its packet sequence
+    Submitted,
+    /// packet was sent and awaits the acknowledgement
+    Pending,
+    /// tx was executed on the remote domain
+    Succeeded,
+    /// tx failed on the remote domain
+    Failed { error: String },
+    /// packet timed out before being relayed
+    TimedOut,
+}
+
+#[cw_serde]
+pub struct Bid {
+    pub solver: Addr,
+    pub amount: Uint128,
 }
@@ -1,22 +1,37 @@
 pub(crate) mod user {
-    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
-    use cosmwasm_std::{ensure, Coin, Env, MessageInfo, Response, StdError, Uint64};
-    use cw_utils::must_pay;
+    use std::collections::BTreeMap;
+
+    use cosmwasm_std::{
+        coins, ensure, BankMsg, Coin, Env, MessageInfo, Response, StdError, Storage, SubMsg,
+        Uint128, Uint64,
+    };
+    use cw_utils::{must_pay, nonpayable};
     use neutron_sdk::{
-        bindings::msg::NeutronMsg, interchain_queries::v047::types::COSMOS_SDK_TRANSFER_MSG_URL,
-        query::min_ibc_fee::query_min_ibc_fee, NeutronResult,
+        bindings::msg::NeutronMsg, query::min_ibc_fee::query_min_ibc_fee, NeutronResult,
     };
 
     use crate::{
-        contract::ExecuteDeps,
+        contract::{ExecuteDeps, QueryDeps},
         error::ContractError,
-        state::{UserConfig, CLEARING_ACCOUNTS, ORBITAL_DOMAINS, USER_CONFIGS, USER_NONCE},
-        utils::{fees::flatten_ibc_fees_amt, generate_proto_msg, get_ica_identifier},
+        intent_logic::intent,
+        msg::Withdrawal,
+        state::{
+            ClearingAccountStatus, IcaTxKind, OrbitalDomainConfig, PendingIcqRegistration,
+            UserConfig, CLEARING_ACCOUNTS, CONFIG, LEGACY_ICA_IDENTIFIERS, ORBITAL_DOMAINS,
+            PENDING_ICQ_REGISTRATIONS, REOPENING_CLEARING_ACCOUNTS, USER_CONFIGS, USER_NONCE,
+        },
+        utils::{
+            assert_domain_active, escrow,
+            fees::{
+                flatten_ibc_fees_amt, query_ica_registration_fee, query_icq_deposit, IBC_FEE_DENOM,
+            },
+            get_ica_identifier, NEUTRON_DOMAIN,
+        },
     };
 
     pub fn try_register_new_domain(
         deps: ExecuteDeps,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         domain: String,
     ) -> NeutronResult<Response<NeutronMsg>> {
@@ -27,10 +42,7 @@ pub(crate) mod user {
         );
 
         // the domain must be enabled on orbital level to be able to register
-        ensure!(
-            ORBITAL_DOMAINS.has(deps.storage, domain.to_string()),
-            ContractError::UnknownDomain(domain)
-        );
+        assert_domain_active(deps.storage, &domain)?;
 
         let domain_config = ORBITAL_DOMAINS.load(deps.storage, domain.to_string())?;
         let mut user_config = USER_CONFIGS.load(deps.storage, info.sender.to_string())?;
@@ -38,6 +50,21 @@ pub(crate) mod user {
         // get the ica identifier
         let ica_identifier = get_ica_identifier(user_config.id, domain.to_string());
 
+        // each user registers to a domain once. a clearing account awaiting its
+        // registration must not be registered again either, as its rollback
+        // would then remove the account of the earlier registration.
+        ensure!(
+            !user_config.registered_domains.contains(&domain)
+                && !CLEARING_ACCOUNTS.has(deps.storage, ica_identifier.to_string()),
+            ContractError::UserAlreadyRegisteredToDomain(domain)
+        );
+        // legacy `{domain}{user_id}` identifiers keep their ICA ports, so a new
+        // identifier spelled the same would share the port of a legacy account
+        ensure!(
+            !LEGACY_ICA_IDENTIFIERS.has(deps.storage, ica_identifier.to_string()),
+            ContractError::IcaIdentifierInUse(ica_identifier)
+        );
+
         // update the registered domains for the caller
         user_config.registered_domains.push(domain.to_string());
 
@@ -47,11 +74,90 @@ pub(crate) mod user {
         //save the updated user config
         USER_CONFIGS.save(deps.storage, info.sender.to_string(), &user_config)?;
 
+        // ICA clearing accounts are monitored by interchain queries which are
+        // registered once the account is opened. the user pays their deposits
+        // upfront, which are held by orbital-core until then.
+        let icq_deposit = match &domain_config {
+            OrbitalDomainConfig::InterchainAccount {
+                monitored_denoms, ..
+            } => {
+                let pending_registration = PendingIcqRegistration {
+                    owner: info.sender.clone(),
+                    domain: domain.to_string(),
+                    monitored_denoms: monitored_denoms.clone(),
+                    deposit: query_icq_deposit(deps.as_ref())?,
+                };
+                PENDING_ICQ_REGISTRATIONS.save(
+                    deps.storage,
+                    ica_identifier.to_string(),
+                    &pending_registration,
+                )?;
+                pending_registration.total_deposit()
+            }
+            OrbitalDomainConfig::Polytone { .. } => vec![],
+        };
+
         Ok(Response::new()
-            .add_message(domain_config.get_registration_message(deps, &info, ica_identifier)?)
+            .add_submessage(domain_config.get_registration_message(
+                deps,
+                &env,
+                &info,
+                &domain,
+                ica_identifier,
+                &icq_deposit,
+            )?)
             .add_attribute("method", "register_user_domain"))
     }
 
+    pub fn try_reopen_clearing_account(
+        deps: ExecuteDeps,
+        env: Env,
+        info: MessageInfo,
+        domain: String,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let user_config = USER_CONFIGS
+            .may_load(deps.storage, info.sender.to_string())?
+            .ok_or(ContractError::UserNotRegistered {})?;
+        ensure!(
+            user_config.registered_domains.contains(&domain),
+            ContractError::UserNotRegisteredToDomain(domain)
+        );
+
+        // only accounts whose ICA channel was closed can be reopened
+        let ica_identifier = get_ica_identifier(user_config.id, domain.to_string());
+        let clearing_account = CLEARING_ACCOUNTS
+            .load(deps.storage, ica_identifier.to_string())?
+            .ok_or_else(|| ContractError::UserNotRegisteredToDomain(domain.to_string()))?;
+        ensure!(
+            clearing_account.status == ClearingAccountStatus::Closed,
+            ContractError::ClearingAccountNotClosed(domain)
+        );
+
+        // registering the same ica identifier opens a new channel for the
+        // existing remote account. the account stays closed until the
+        // open ack callback is received.
+        assert_domain_active(deps.storage, &domain)?;
+        let domain_config = ORBITAL_DOMAINS.load(deps.storage, domain.to_string())?;
+        REOPENING_CLEARING_ACCOUNTS.save(
+            deps.storage,
+            ica_identifier.to_string(),
+            &(info.sender.clone(), domain.to_string()),
+        )?;
+
+        // queries registered when the account was first opened keep monitoring it
+        Ok(Response::new()
+            .add_submessage(domain_config.get_registration_message(
+                deps,
+                &env,
+                &info,
+                &domain,
+                ica_identifier,
+                &[],
+            )?)
+            .add_attribute("method", "reopen_clearing_account")
+            .add_attribute("domain", domain))
+    }
+
     pub fn try_register(
         deps: ExecuteDeps,
         _env: Env,
@@ -80,61 +186,228 @@ pub(crate) mod user {
         Ok(Response::new().add_attribute("method", "register_user"))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn try_withdraw_from_remote_domain(
         deps: ExecuteDeps,
+        env: Env,
+        info: MessageInfo,
+        domain: String,
+        withdrawals: Vec<Withdrawal>,
+        timeout: Option<Uint64>,
+        memo: Option<String>,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        let user_config = USER_CONFIGS.load(deps.storage, info.sender.to_string())?;
+
+        // withdrawals can not dip into funds locked by active intents. the
+        // batch is checked per denom, as its withdrawals share the balance.
+        intent::release_expired(deps.storage, &env.block, &info.sender)?;
+        let mut withdrawn: BTreeMap<String, Uint128> = BTreeMap::new();
+        for withdrawal in withdrawals.iter() {
+            let amount = withdrawn
+                .entry(withdrawal.coin.denom.to_string())
+                .or_default();
+            *amount = amount.checked_add(withdrawal.coin.amount)?;
+        }
+        for (denom, amount) in withdrawn {
+            escrow::assert_withdrawable(
+                deps.storage,
+                &info.sender,
+                &domain,
+                &Coin::new(amount, denom),
+            )?;
+        }
+
+        let withdraw_submsgs = get_withdraw_msg(
+            deps,
+            &env,
+            &info,
+            &user_config,
+            domain,
+            withdrawals,
+            timeout,
+            memo,
+        )?;
+
+        Ok(Response::default().add_submessages(withdraw_submsgs))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_transfer_from_remote_domain(
+        deps: ExecuteDeps,
+        env: Env,
         info: MessageInfo,
         domain: String,
         coin: Coin,
+        dest_domain: String,
         dest: String,
+        timeout: Option<Uint64>,
+        memo: Option<String>,
     ) -> NeutronResult<Response<NeutronMsg>> {
         let user_config = USER_CONFIGS.load(deps.storage, info.sender.to_string())?;
 
-        // first we validate that user is registered to the domain from which they
-        // want to withdraw funds from
+        assert_valid_timeout(deps.storage, timeout)?;
+        // funds can be transferred back to neutron or to any other orbital domain
         ensure!(
-            user_config.registered_domains.contains(&domain),
-            ContractError::UserNotRegisteredToDomain(domain)
+            dest_domain == NEUTRON_DOMAIN
+                || ORBITAL_DOMAINS.has(deps.storage, dest_domain.to_string()),
+            ContractError::UnknownDomain(dest_domain)
         );
 
-        // validate that IBC fees are covered by the caller
-        let min_ibc_fee = query_min_ibc_fee(deps.as_ref())?;
-        let total_fee_amt = flatten_ibc_fees_amt(&min_ibc_fee.min_fee);
-        let paid_amt = must_pay(&info, "untrn").map_err(ContractError::FeePaymentError)?;
+        // transfers can not dip into funds locked by active intents
+        intent::release_expired(deps.storage, &env.block, &info.sender)?;
+        escrow::assert_withdrawable(deps.storage, &info.sender, &domain, &coin)?;
+
+        let (domain_config, fee_refund) =
+            load_source_domain(deps.as_ref(), &info, &user_config, &domain)?;
+
+        // ICS-20 transfer out of the clearing account to the destination domain
+        let transfer_submsg = domain_config.get_transfer_message(
+            deps,
+            &env,
+            &info.sender,
+            &domain,
+            coin,
+            dest_domain.to_string(),
+            dest,
+            timeout,
+            memo,
+        )?;
+
+        Ok(Response::new()
+            .add_submessage(transfer_submsg)
+            .add_messages(fee_refund)
+            .add_attribute("method", "transfer_from_remote_domain")
+            .add_attribute("domain", domain)
+            .add_attribute("dest_domain", dest_domain))
+    }
+
+    /// ensures that a timeout chosen by the user is within the configured bounds
+    fn assert_valid_timeout(
+        storage: &dyn Storage,
+        timeout: Option<Uint64>,
+    ) -> Result<(), ContractError> {
+        if let Some(timeout) = timeout {
+            let config = CONFIG.load(storage)?;
+            ensure!(
+                config.min_tx_timeout <= timeout && timeout <= config.max_tx_timeout,
+                ContractError::InvalidTxTimeout(
+                    timeout,
+                    config.min_tx_timeout,
+                    config.max_tx_timeout
+                )
+            );
+        }
 
+        Ok(())
+    }
+
+    /// builds the message which transfers funds out of the user's clearing
+    /// account on the given domain. all withdrawals are packed into a single
+    /// tx, whose IBC fees must be covered by the caller on ICA domains. the
+    /// submessages refund any fee paid in excess to the caller.
+    /// the tx times out after the domain timeout unless the caller chose a
+    /// timeout within the configured bounds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_withdraw_msg(
+        mut deps: ExecuteDeps,
+        env: &Env,
+        info: &MessageInfo,
+        user_config: &UserConfig,
+        domain: String,
+        withdrawals: Vec<Withdrawal>,
+        timeout: Option<Uint64>,
+        memo: Option<String>,
+    ) -> NeutronResult<Vec<SubMsg<NeutronMsg>>> {
         ensure!(
-            paid_amt >= total_fee_amt,
-            ContractError::Std(StdError::generic_err("insufficient fee coverage"))
+            !withdrawals.is_empty(),
+            ContractError::EmptyWithdrawalBatch {}
         );
 
-        // derive the port associated with user's clearing account
-        let ica_identifier = get_ica_identifier(user_config.id, domain.to_string());
+        assert_valid_timeout(deps.storage, timeout)?;
 
-        let user_clearing_acc_config = CLEARING_ACCOUNTS
-            .load(deps.storage, ica_identifier.to_string())?
-            .ok_or_else(|| ContractError::UserNotRegisteredToDomain(domain))?;
+        let (domain_config, fee_refund) =
+            load_source_domain(deps.as_ref(), info, user_config, &domain)?;
 
-        // generate the transfer message to be executed on target domain
-        let proto_coin = ProtoCoin {
-            denom: coin.denom,
-            amount: coin.amount.to_string(),
-        };
-        let bank_msg = cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend {
-            from_address: user_clearing_acc_config.addr,
-            to_address: dest,
-            amount: vec![proto_coin],
-        };
+        if let OrbitalDomainConfig::InterchainAccount { .. } = domain_config {
+            // neutron limits the number of messages a single ICA tx can carry
+            if let Some(params) = query_ica_registration_fee(deps.branch())?.params {
+                let max_messages = params.msg_submit_tx_max_messages.u64();
+                ensure!(
+                    withdrawals.len() as u64 <= max_messages,
+                    ContractError::WithdrawalBatchTooLarge(withdrawals.len(), max_messages)
+                );
+            }
+        }
+
+        // generate the transfers to be executed on target domain
+        let send_submsg = domain_config.get_send_message(
+            deps,
+            env,
+            &info.sender,
+            &domain,
+            withdrawals
+                .into_iter()
+                .map(|withdrawal| (withdrawal.coin, withdrawal.dest))
+                .collect(),
+            IcaTxKind::Withdrawal,
+            timeout,
+            memo,
+        )?;
 
-        let proto_msg = generate_proto_msg(bank_msg, COSMOS_SDK_TRANSFER_MSG_URL)?;
+        Ok([send_submsg]
+            .into_iter()
+            .chain(fee_refund.map(SubMsg::new))
+            .collect())
+    }
 
-        let withdraw_tx: NeutronMsg = NeutronMsg::submit_tx(
-            user_clearing_acc_config.controller_connection_id,
-            ica_identifier,
-            vec![proto_msg],
-            "".to_string(),
-            60,
-            min_ibc_fee.min_fee,
+    /// loads the configuration of the domain funds are moved out of. the user
+    /// must be registered to the active domain, and IBC fees of ICA domains
+    /// must be covered by the caller. the fee deposit is capped at the fee
+    /// actually required, any excess is returned in the refund message.
+    fn load_source_domain(
+        deps: QueryDeps,
+        info: &MessageInfo,
+        user_config: &UserConfig,
+        domain: &str,
+    ) -> NeutronResult<(OrbitalDomainConfig, Option<BankMsg>)> {
+        // first we validate that user is registered to the domain from which they
+        // want to move funds out of
+        ensure!(
+            user_config.registered_domains.iter().any(|d| d == domain),
+            ContractError::UserNotRegisteredToDomain(domain.to_string())
         );
+        assert_domain_active(deps.storage, domain)?;
+
+        let domain_config = ORBITAL_DOMAINS.load(deps.storage, domain.to_string())?;
+
+        let fee_refund = match domain_config {
+            OrbitalDomainConfig::InterchainAccount { .. } => {
+                // validate that IBC fees are covered by the caller
+                let min_ibc_fee = query_min_ibc_fee(deps)?;
+                let total_fee_amt = flatten_ibc_fees_amt(&min_ibc_fee.min_fee);
+                let paid_amt =
+                    must_pay(info, IBC_FEE_DENOM).map_err(ContractError::FeePaymentError)?;
+
+                ensure!(
+                    paid_amt >= total_fee_amt,
+                    ContractError::Std(StdError::generic_err("insufficient fee coverage"))
+                );
+
+                // fees paid in excess are returned to the caller right away
+                let excess_amt = paid_amt - total_fee_amt;
+                (!excess_amt.is_zero()).then(|| BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: coins(excess_amt.u128(), IBC_FEE_DENOM),
+                })
+            }
+            OrbitalDomainConfig::Polytone { .. } => {
+                // polytone executions carry no ibc fees, so any funds
+                // attached would be stranded in the contract
+                nonpayable(info).map_err(ContractError::FeePaymentError)?;
+                None
+            }
+        };
 
-        Ok(Response::default().add_message(withdraw_tx))
+        Ok((domain_config, fee_refund))
     }
 }
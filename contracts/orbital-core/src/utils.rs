@@ -1,18 +1,50 @@
-use cosmwasm_std::{Binary, StdError, StdResult, Uint64};
-use neutron_sdk::{bindings::types::ProtobufAny, NeutronResult};
+use cosmos_sdk_proto::{
+    cosmos::{bank::v1beta1::MsgSend, base::v1beta1::Coin as ProtoCoin},
+    ibc::applications::transfer::v1::MsgTransfer,
+};
+use cosmwasm_std::{
+    ensure, Addr, Binary, Coin, Decimal, StdError, StdResult, Storage, Timestamp, Uint64,
+};
+use cw_utils::Duration;
+use neutron_sdk::{
+    bindings::{
+        msg::{IbcFee, NeutronMsg},
+        types::ProtobufAny,
+    },
+    interchain_queries::v047::types::COSMOS_SDK_TRANSFER_MSG_URL,
+    NeutronResult,
+};
 use prost::Message;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    error::ContractError,
+    state::{
+        ClearingAccountConfig, ClearingAccountStatus, Config, OrbitalDomainConfig,
+        CLEARING_ACCOUNTS, LEGACY_ICA_IDENTIFIERS, ORBITAL_DOMAINS, PAUSED_DOMAINS,
+        REGISTERED_ICA_IDENTIFIERS, REMOTE_HEIGHTS, USER_CONFIGS,
+    },
+};
+
 pub mod fees {
     use cosmwasm_schema::cw_serde;
     use cosmwasm_std::{
-        ensure, Binary, Coin, MessageInfo, QueryRequest, StdResult, Uint128, Uint64,
+        coins, ensure, Addr, BankMsg, Binary, Coin, MessageInfo, QueryRequest, StdResult, Uint128,
+        Uint64,
     };
     use cw_utils::must_pay;
     use neutron_sdk::bindings::{msg::IbcFee, query::NeutronQuery};
+    use serde::Deserialize;
+
+    use crate::{
+        contract::{ExecuteDeps, QueryDeps},
+        error::ContractError,
+        state::{IcaTx, IcaTxStatus},
+    };
 
-    use crate::{contract::ExecuteDeps, error::ContractError};
+    /// denom in which IBC fees are paid
+    pub const IBC_FEE_DENOM: &str = "untrn";
 
     #[cw_serde]
     pub struct Params {
@@ -25,6 +57,18 @@ pub mod fees {
         pub params: Option<Params>,
     }
 
+    /// params of the interchain queries module. fields which orbital
+    /// does not rely on are ignored.
+    #[derive(Deserialize)]
+    pub struct IcqParams {
+        pub query_deposit: Vec<Coin>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct QueryIcqParamsResponse {
+        pub params: IcqParams,
+    }
+
     pub fn assert_fee_payment(
         info: &MessageInfo,
         expected_fee: &Coin,
@@ -49,6 +93,107 @@ pub mod fees {
             .sum()
     }
 
+    /// builds the refund of the IBC fees neutron returns once the ICA tx is
+    /// settled. relayers earn the ack fee of acknowledged packets and the
+    /// timeout fee of timed out ones, while txs which never left neutron had
+    /// no fees escrowed at all. txs without a fee payer are not refunded.
+    pub fn get_fee_refund(ica_tx: &IcaTx) -> Option<BankMsg> {
+        let payer = ica_tx.fee_payer.as_ref()?;
+        let fee = &ica_tx.fee;
+
+        let refunded_fees = match (&ica_tx.status, ica_tx.sequence) {
+            (IcaTxStatus::Submitted | IcaTxStatus::Pending, _) => return None,
+            (_, None) => vec![&fee.recv_fee, &fee.ack_fee, &fee.timeout_fee],
+            (IcaTxStatus::TimedOut, _) => vec![&fee.recv_fee, &fee.ack_fee],
+            (IcaTxStatus::Succeeded | IcaTxStatus::Failed { .. }, _) => {
+                vec![&fee.recv_fee, &fee.timeout_fee]
+            }
+        };
+        let refund_amt: Uint128 = refunded_fees
+            .into_iter()
+            .flatten()
+            .map(|fee| fee.amount)
+            .sum();
+
+        if refund_amt.is_zero() {
+            return None;
+        }
+
+        Some(BankMsg::Send {
+            to_address: payer.to_string(),
+            amount: coins(refund_amt.u128(), IBC_FEE_DENOM),
+        })
+    }
+
+    /// builds the refund of an interchain query deposit which neutron
+    /// never escrowed. zero amounts are not refunded.
+    pub fn get_deposit_refund(payer: &Addr, deposit: Vec<Coin>) -> Option<BankMsg> {
+        let amount: Vec<Coin> = deposit
+            .into_iter()
+            .filter(|coin| !coin.amount.is_zero())
+            .collect();
+
+        (!amount.is_empty()).then(|| BankMsg::Send {
+            to_address: payer.to_string(),
+            amount,
+        })
+    }
+
+    /// asserts that the funds paid by the caller cover the deposit of an
+    /// interchain query and returns the message refunding the excess to
+    /// the caller, if any
+    pub fn collect_icq_deposit(
+        info: &MessageInfo,
+        deposit: &[Coin],
+    ) -> Result<Option<BankMsg>, ContractError> {
+        let paid_amt = |denom: &str| -> Uint128 {
+            info.funds
+                .iter()
+                .find(|paid| paid.denom == denom)
+                .map(|paid| paid.amount)
+                .unwrap_or_default()
+        };
+        for required in deposit {
+            ensure!(
+                paid_amt(&required.denom) >= required.amount,
+                ContractError::InsufficientIcqDeposit(required.clone())
+            );
+        }
+
+        let excess: Vec<Coin> = info
+            .funds
+            .iter()
+            .filter_map(|paid| {
+                let required_amt = deposit
+                    .iter()
+                    .find(|required| required.denom == paid.denom)
+                    .map(|required| required.amount)
+                    .unwrap_or_default();
+                let excess_amt = paid.amount - required_amt;
+                (!excess_amt.is_zero()).then(|| Coin::new(excess_amt, paid.denom.to_string()))
+            })
+            .collect();
+
+        Ok((!excess.is_empty()).then(|| BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: excess,
+        }))
+    }
+
+    /// helper method to query the deposit neutron escrows for every
+    /// registered interchain query
+    pub fn query_icq_deposit(deps: QueryDeps) -> StdResult<Vec<Coin>> {
+        #[allow(deprecated)]
+        let stargate_query_msg: QueryRequest<NeutronQuery> = QueryRequest::Stargate {
+            path: "/neutron.interchainqueries.Query/Params".to_string(),
+            data: Binary::default(),
+        };
+
+        let response: QueryIcqParamsResponse = deps.querier.query(&stargate_query_msg)?;
+
+        Ok(response.params.query_deposit)
+    }
+
     /// helper method to query the registration fee for the ICA
     pub fn query_ica_registration_fee(deps: ExecuteDeps) -> StdResult<QueryParamsResponse> {
         // TODO: remove this explicit allow
@@ -64,6 +209,307 @@ pub mod fees {
     }
 }
 
+pub mod escrow {
+    use cosmwasm_std::{coin, ensure, Addr, Coin, Order, StdResult, Storage, Uint128};
+
+    use crate::{
+        error::ContractError,
+        state::{
+            SettledOutflow, CLEARING_BALANCES, ICQ_DEPOSITS, LOCKED_BALANCES, PENDING_OUTFLOWS,
+            SETTLED_OUTFLOWS, USER_ICQS,
+        },
+    };
+
+    use super::get_clearing_account;
+
+    /// returns the amount of the denom held in the user's clearing account on the
+    /// domain according to the latest result of its balances query
+    pub fn clearing_balance(
+        storage: &dyn Storage,
+        user: &Addr,
+        domain: &str,
+        denom: &str,
+    ) -> StdResult<Uint128> {
+        let balance = CLEARING_BALANCES
+            .may_load(
+                storage,
+                (user.to_string(), domain.to_string(), denom.to_string()),
+            )?
+            .map(|balance| balance.amount)
+            .unwrap_or_default();
+
+        Ok(balance)
+    }
+
+    /// returns the amount of the denom locked in the user's clearing account on the domain
+    pub fn locked_balance(
+        storage: &dyn Storage,
+        user: &Addr,
+        domain: &str,
+        denom: &str,
+    ) -> StdResult<Uint128> {
+        let locked = LOCKED_BALANCES
+            .may_load(
+                storage,
+                (user.to_string(), domain.to_string(), denom.to_string()),
+            )?
+            .unwrap_or_default();
+
+        Ok(locked)
+    }
+
+    /// ensures that the coin can be taken out of the user's clearing account on
+    /// the domain without dipping into the funds locked by active intents or
+    /// the funds already leaving with pending withdrawals and transfers
+    pub fn assert_available(
+        storage: &dyn Storage,
+        user: &Addr,
+        domain: &str,
+        amount: &Coin,
+    ) -> Result<(), ContractError> {
+        let balance = clearing_balance(storage, user, domain, &amount.denom)?;
+        let locked = locked_balance(storage, user, domain, &amount.denom)?;
+        let pending = pending_outflow(storage, user, domain, &amount.denom)?;
+        let available = balance.saturating_sub(locked).saturating_sub(pending);
+
+        ensure!(
+            amount.amount <= available,
+            ContractError::InsufficientAvailableBalance(coin(available.u128(), &amount.denom))
+        );
+
+        Ok(())
+    }
+
+    /// ensures that the coin can be withdrawn or transferred out of the user's
+    /// clearing account on the domain. the reported balance is only enforced
+    /// for monitored denoms, as no balance is ever reported for the others.
+    /// funds of those can not be locked, so the remote chain is left to reject
+    /// withdrawals exceeding the funds of the account.
+    pub fn assert_withdrawable(
+        storage: &dyn Storage,
+        user: &Addr,
+        domain: &str,
+        amount: &Coin,
+    ) -> Result<(), ContractError> {
+        if is_monitored(storage, user, domain, &amount.denom)? {
+            assert_available(storage, user, domain, amount)?;
+        }
+
+        Ok(())
+    }
+
+    /// returns whether the balance of the denom in the user's clearing account on
+    /// the domain is tracked, either by a balances query of the user listing the
+    /// denom or because funds of the denom are reported or locked
+    pub fn is_monitored(
+        storage: &dyn Storage,
+        user: &Addr,
+        domain: &str,
+        denom: &str,
+    ) -> StdResult<bool> {
+        let key = (user.to_string(), domain.to_string(), denom.to_string());
+        if CLEARING_BALANCES.has(storage, key.clone()) || LOCKED_BALANCES.has(storage, key) {
+            return Ok(true);
+        }
+        let Ok(clearing_account) = get_clearing_account(storage, user, domain) else {
+            return Ok(false);
+        };
+
+        let query_ids = USER_ICQS
+            .prefix(user.to_string())
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+        for query_id in query_ids {
+            let Some(registration) = ICQ_DEPOSITS.may_load(storage, query_id)? else {
+                continue;
+            };
+            if registration.addr == clearing_account.addr
+                && registration.denoms.iter().any(|d| d == denom)
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// locks the coin in the user's clearing account on the domain
+    pub fn lock(
+        storage: &mut dyn Storage,
+        user: &Addr,
+        domain: &str,
+        amount: &Coin,
+    ) -> StdResult<()> {
+        let locked = locked_balance(storage, user, domain, &amount.denom)?;
+        LOCKED_BALANCES.save(
+            storage,
+            (
+                user.to_string(),
+                domain.to_string(),
+                amount.denom.to_string(),
+            ),
+            &locked.checked_add(amount.amount)?,
+        )
+    }
+
+    /// releases the previously locked coin in the user's clearing account on the domain
+    pub fn unlock(
+        storage: &mut dyn Storage,
+        user: &Addr,
+        domain: &str,
+        amount: &Coin,
+    ) -> StdResult<()> {
+        let key = (
+            user.to_string(),
+            domain.to_string(),
+            amount.denom.to_string(),
+        );
+        let locked =
+            locked_balance(storage, user, domain, &amount.denom)?.checked_sub(amount.amount)?;
+
+        if locked.is_zero() {
+            LOCKED_BALANCES.remove(storage, key);
+            Ok(())
+        } else {
+            LOCKED_BALANCES.save(storage, key, &locked)
+        }
+    }
+
+    /// returns the amount of the denom leaving the user's clearing account on
+    /// the domain with withdrawals and transfers which did not settle yet
+    pub fn pending_outflow(
+        storage: &dyn Storage,
+        user: &Addr,
+        domain: &str,
+        denom: &str,
+    ) -> StdResult<Uint128> {
+        let pending = PENDING_OUTFLOWS
+            .may_load(
+                storage,
+                (user.to_string(), domain.to_string(), denom.to_string()),
+            )?
+            .unwrap_or_default();
+
+        Ok(pending)
+    }
+
+    /// holds back the coins leaving the user's clearing account on the domain
+    /// with a submitted tx. the balances query keeps reporting them until the
+    /// tx executed, so they must not be taken out a second time.
+    pub fn add_pending_outflows(
+        storage: &mut dyn Storage,
+        user: &Addr,
+        domain: &str,
+        outflows: &[Coin],
+    ) -> StdResult<()> {
+        for outflow in outflows {
+            let pending = pending_outflow(storage, user, domain, &outflow.denom)?;
+            PENDING_OUTFLOWS.save(
+                storage,
+                (
+                    user.to_string(),
+                    domain.to_string(),
+                    outflow.denom.to_string(),
+                ),
+                &pending.checked_add(outflow.amount)?,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// settles the coins held back for a tx. coins which left the clearing
+    /// account with an executed tx stay held back until a balance snapshot
+    /// taken after the settlement height no longer reports them, while the
+    /// coins of failed txs and of unmonitored denoms are released right away.
+    pub fn settle_pending_outflows(
+        storage: &mut dyn Storage,
+        user: &Addr,
+        domain: &str,
+        outflows: &[Coin],
+        executed: bool,
+        height: u64,
+    ) -> StdResult<()> {
+        for outflow in outflows {
+            if !executed || !is_monitored(storage, user, domain, &outflow.denom)? {
+                release_pending_outflows(storage, user, domain, &[outflow.clone()])?;
+                continue;
+            }
+
+            let key = (
+                user.to_string(),
+                domain.to_string(),
+                outflow.denom.to_string(),
+            );
+            let mut settled = SETTLED_OUTFLOWS
+                .may_load(storage, key.clone())?
+                .unwrap_or_default();
+            settled.push(SettledOutflow {
+                amount: outflow.amount,
+                settled_height: height,
+            });
+            SETTLED_OUTFLOWS.save(storage, key, &settled)?;
+        }
+
+        Ok(())
+    }
+
+    /// releases the held back coins of the denom whose txs settled before the
+    /// balance snapshot reported at the given height
+    pub fn release_settled_outflows(
+        storage: &mut dyn Storage,
+        user: &Addr,
+        domain: &str,
+        denom: &str,
+        height: u64,
+    ) -> StdResult<()> {
+        let key = (user.to_string(), domain.to_string(), denom.to_string());
+        let Some(settled) = SETTLED_OUTFLOWS.may_load(storage, key.clone())? else {
+            return Ok(());
+        };
+
+        let (released, held): (Vec<_>, Vec<_>) = settled
+            .into_iter()
+            .partition(|outflow| outflow.settled_height < height);
+        for outflow in released {
+            release_pending_outflows(storage, user, domain, &[coin(outflow.amount.u128(), denom)])?;
+        }
+        if held.is_empty() {
+            SETTLED_OUTFLOWS.remove(storage, key);
+        } else {
+            SETTLED_OUTFLOWS.save(storage, key, &held)?;
+        }
+
+        Ok(())
+    }
+
+    /// releases the coins held back for a tx once it settled
+    pub fn release_pending_outflows(
+        storage: &mut dyn Storage,
+        user: &Addr,
+        domain: &str,
+        outflows: &[Coin],
+    ) -> StdResult<()> {
+        for outflow in outflows {
+            let key = (
+                user.to_string(),
+                domain.to_string(),
+                outflow.denom.to_string(),
+            );
+            let pending = pending_outflow(storage, user, domain, &outflow.denom)?
+                .checked_sub(outflow.amount)?;
+
+            if pending.is_zero() {
+                PENDING_OUTFLOWS.remove(storage, key);
+            } else {
+                PENDING_OUTFLOWS.save(storage, key, &pending)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub(crate) struct OpenAckVersion {
@@ -75,11 +521,215 @@ pub(crate) struct OpenAckVersion {
     pub tx_type: String,
 }
 
+/// returns true if the duration would expire in the same block it starts in
+pub fn is_zero_duration(duration: &Duration) -> bool {
+    matches!(duration, Duration::Height(0) | Duration::Time(0))
+}
+
+/// ensures that the global orbital configuration is usable
+pub fn validate_config(config: &Config) -> StdResult<()> {
+    ensure!(
+        !config.solver_bond.amount.is_zero(),
+        StdError::generic_err("solver bond must be non-zero")
+    );
+    ensure!(
+        !is_zero_duration(&config.unbonding_period),
+        StdError::generic_err("unbonding period must be non-zero")
+    );
+    ensure!(
+        !is_zero_duration(&config.auction_duration),
+        StdError::generic_err("auction duration must be non-zero")
+    );
+    ensure!(
+        !config.min_bid_increment.is_zero(),
+        StdError::generic_err("min bid increment must be non-zero")
+    );
+    ensure!(
+        !is_zero_duration(&config.fill_timeout),
+        StdError::generic_err("fill timeout must be non-zero")
+    );
+    // bonds backing won auctions must remain slashable until the fill deadline
+    let unbonding_outlasts_fills = match (
+        &config.unbonding_period,
+        &config.auction_duration,
+        &config.fill_timeout,
+    ) {
+        (
+            Duration::Height(unbonding_period),
+            Duration::Height(auction_duration),
+            Duration::Height(fill_timeout),
+        )
+        | (
+            Duration::Time(unbonding_period),
+            Duration::Time(auction_duration),
+            Duration::Time(fill_timeout),
+        ) => *unbonding_period > auction_duration.saturating_add(*fill_timeout),
+        _ => false,
+    };
+    ensure!(
+        unbonding_outlasts_fills,
+        StdError::generic_err(
+            "unbonding period must exceed auction duration plus fill timeout in the same unit"
+        )
+    );
+    ensure!(
+        config.slashed_bond_user_share <= Decimal::one(),
+        StdError::generic_err("slashed bond user share must not exceed 1")
+    );
+    ensure!(
+        !config.min_tx_timeout.is_zero(),
+        StdError::generic_err("min tx timeout must be non-zero")
+    );
+    ensure!(
+        config.min_tx_timeout <= config.max_tx_timeout,
+        StdError::generic_err("min tx timeout must not exceed max tx timeout")
+    );
+
+    Ok(())
+}
+
+/// ICA port ids are formatted as `icacontroller-{contract}.{ica_identifier}`
+/// and limited to 128 characters by IBC. neutron contract addresses take 66
+/// characters, leaving 47 for the identifier, 21 of which are reserved for
+/// the `_{user_id}` suffix.
+pub const MAX_DOMAIN_LENGTH: usize = 26;
+
+/// destination of transfers back to neutron. it is reserved and can not be
+/// registered as an orbital domain.
+pub const NEUTRON_DOMAIN: &str = "neutron";
+
+/// ensures that the domain identifier can be used as part of ICA identifiers.
+/// domains consist of lowercase ascii alphanumerics, `-` and `_`.
+pub fn validate_domain(domain: &str) -> Result<(), ContractError> {
+    ensure!(
+        !domain.is_empty() && domain.len() <= MAX_DOMAIN_LENGTH,
+        ContractError::InvalidDomainIdentifier(format!(
+            "length must be between 1 and {MAX_DOMAIN_LENGTH} characters"
+        ))
+    );
+    ensure!(
+        domain
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_'),
+        ContractError::InvalidDomainIdentifier(format!("{domain} contains invalid characters"))
+    );
+    ensure!(
+        domain != NEUTRON_DOMAIN,
+        ContractError::InvalidDomainIdentifier(format!("{domain} is reserved"))
+    );
+
+    Ok(())
+}
+
 /// returns the ICA identifier for this specific (user, domain) combination.
-/// it can be any string.
+/// the user id is a decimal suffix following the last `_`, so no two
+/// (user, domain) combinations share an identifier.
 pub fn get_ica_identifier(user_id: Uint64, domain: String) -> String {
     let id = user_id.to_string();
-    format!("{domain}{id}")
+    format!("{domain}_{id}")
+}
+
+/// returns the identifier the clearing account was registered with, which is
+/// the one its ICA port carries. accounts registered before the current
+/// identifier encoding keep their legacy identifier.
+pub fn get_registered_ica_identifier(
+    storage: &dyn Storage,
+    ica_identifier: &str,
+) -> StdResult<String> {
+    Ok(REGISTERED_ICA_IDENTIFIERS
+        .may_load(storage, ica_identifier.to_string())?
+        .unwrap_or_else(|| ica_identifier.to_string()))
+}
+
+/// inverse of `get_registered_ica_identifier`, resolving the identifier
+/// carried by an ICA port or registration callback to the clearing account key
+pub fn resolve_ica_identifier(
+    storage: &dyn Storage,
+    registered_identifier: &str,
+) -> StdResult<String> {
+    Ok(LEGACY_ICA_IDENTIFIERS
+        .may_load(storage, registered_identifier.to_string())?
+        .unwrap_or_else(|| registered_identifier.to_string()))
+}
+
+/// ensures that the domain is registered with orbital and not paused
+pub fn assert_domain_active(storage: &dyn Storage, domain: &str) -> Result<(), ContractError> {
+    ensure!(
+        ORBITAL_DOMAINS.has(storage, domain.to_string()),
+        ContractError::UnknownDomain(domain.to_string())
+    );
+    ensure!(
+        !PAUSED_DOMAINS.has(storage, domain.to_string()),
+        ContractError::DomainPaused(domain.to_string())
+    );
+
+    Ok(())
+}
+
+/// returns the latest height of the domain observed in tx query results on its
+/// connection. domains which are not reached over a connection report 0.
+pub fn get_remote_height(storage: &dyn Storage, domain: &str) -> Result<u64, ContractError> {
+    let domain_config = ORBITAL_DOMAINS
+        .may_load(storage, domain.to_string())?
+        .ok_or_else(|| ContractError::UnknownDomain(domain.to_string()))?;
+
+    match domain_config {
+        OrbitalDomainConfig::InterchainAccount { connection_id, .. } => Ok(REMOTE_HEIGHTS
+            .may_load(storage, connection_id)?
+            .unwrap_or_default()),
+        OrbitalDomainConfig::Polytone { .. } => Ok(0),
+    }
+}
+
+/// loads the clearing account of a user on the given domain. errors out if the
+/// user is not registered to the domain or the account is still being registered.
+pub fn get_clearing_account(
+    storage: &dyn Storage,
+    user: &Addr,
+    domain: &str,
+) -> Result<ClearingAccountConfig, ContractError> {
+    let user_config = USER_CONFIGS
+        .may_load(storage, user.to_string())?
+        .ok_or(ContractError::UserNotRegistered {})?;
+    let ica_identifier = get_ica_identifier(user_config.id, domain.to_string());
+
+    CLEARING_ACCOUNTS
+        .may_load(storage, ica_identifier)?
+        .flatten()
+        .ok_or_else(|| ContractError::UserNotRegisteredToDomain(domain.to_string()))
+}
+
+/// returns the domain on which the address is a clearing account of the user, if any
+pub fn get_clearing_account_domain(
+    storage: &dyn Storage,
+    user: &Addr,
+    addr: &str,
+) -> StdResult<Option<String>> {
+    let registered_domains = USER_CONFIGS
+        .may_load(storage, user.to_string())?
+        .map(|user_config| user_config.registered_domains)
+        .unwrap_or_default();
+
+    Ok(registered_domains.into_iter().find(|domain| {
+        get_clearing_account(storage, user, domain)
+            .is_ok_and(|clearing_account| clearing_account.addr == addr)
+    }))
+}
+
+/// loads the clearing account of the user on the given domain and ensures
+/// that its ICA channel is open, so that txs can be submitted through it
+pub fn get_open_clearing_account(
+    storage: &dyn Storage,
+    user: &Addr,
+    domain: &str,
+) -> Result<ClearingAccountConfig, ContractError> {
+    let clearing_account = get_clearing_account(storage, user, domain)?;
+    match clearing_account.status {
+        ClearingAccountStatus::Open => Ok(clearing_account),
+        ClearingAccountStatus::Closed => {
+            Err(ContractError::ClearingAccountClosed(domain.to_string()))
+        }
+    }
 }
 
 /// inverse of neutron_sdk::interchain_txs::helpers::get_port_id,
@@ -93,6 +743,87 @@ pub fn extract_ica_identifier_from_port(port: String) -> StdResult<String> {
     }
 }
 
+/// builds the ICA tx which sends each coin from the clearing account to its
+/// destination, with one bank send per `(coin, dest)` pair. the tx times out
+/// `tx_timeout` seconds after being sent.
+pub fn get_clearing_account_send_tx(
+    ica_identifier: String,
+    clearing_account: ClearingAccountConfig,
+    sends: Vec<(Coin, String)>,
+    tx_timeout: Uint64,
+    memo: String,
+    fee: IbcFee,
+) -> NeutronResult<NeutronMsg> {
+    let mut proto_msgs = vec![];
+    for (coin, dest) in sends {
+        let proto_coin = ProtoCoin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
+        };
+        let bank_msg = MsgSend {
+            from_address: clearing_account.addr.to_string(),
+            to_address: dest,
+            amount: vec![proto_coin],
+        };
+
+        proto_msgs.push(generate_proto_msg(bank_msg, COSMOS_SDK_TRANSFER_MSG_URL)?);
+    }
+
+    Ok(NeutronMsg::submit_tx(
+        clearing_account.controller_connection_id,
+        ica_identifier,
+        proto_msgs,
+        memo,
+        tx_timeout.u64(),
+        fee,
+    ))
+}
+
+/// type url of the ICS-20 transfer message
+pub const IBC_TRANSFER_MSG_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+
+/// builds the ICA tx which transfers the coin from the clearing account over
+/// the given ICS-20 channel. the transfer times out at the given timestamp,
+/// while the ICA tx itself times out `tx_timeout` seconds after being sent.
+/// both the tx and the transfer carry the memo.
+#[allow(clippy::too_many_arguments)]
+pub fn get_clearing_account_transfer_tx(
+    ica_identifier: String,
+    clearing_account: ClearingAccountConfig,
+    coin: Coin,
+    channel: String,
+    dest: String,
+    timeout: Timestamp,
+    tx_timeout: Uint64,
+    memo: String,
+    fee: IbcFee,
+) -> NeutronResult<NeutronMsg> {
+    let transfer_msg = MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: channel,
+        token: Some(ProtoCoin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
+        }),
+        sender: clearing_account.addr,
+        receiver: dest,
+        timeout_height: None,
+        timeout_timestamp: timeout.nanos(),
+        memo: memo.to_string(),
+    };
+
+    let proto_msg = generate_proto_msg(transfer_msg, IBC_TRANSFER_MSG_URL)?;
+
+    Ok(NeutronMsg::submit_tx(
+        clearing_account.controller_connection_id,
+        ica_identifier,
+        vec![proto_msg],
+        memo,
+        tx_timeout.u64(),
+        fee,
+    ))
+}
+
 pub fn generate_proto_msg(msg: impl Message, type_url: &str) -> NeutronResult<ProtobufAny> {
     let buf = msg.encode_to_vec();
 
@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, coins};
+use cosmwasm_std::{coin, coins, Decimal, Uint128, Uint64};
 use localic_std::modules::{bank::get_balance, cosmwasm::contract_instantiate};
 use localic_utils::{
     ConfigChainBuilder, TestContextBuilder, GAIA_CHAIN_NAME, JUNO_CHAIN_NAME, NEUTRON_CHAIN_NAME,
@@ -8,13 +8,13 @@ use orbital_core::msg::InstantiateMsg;
 use std::{env, error::Error, time::Duration};
 use utils::{
     exec::{
-        admin_register_domain, register_icq_balances_query, register_icq_transfers_query,
-        user_register_orbital_core, user_register_to_new_domain, user_withdraw_funds_from_domain,
+        admin_register_domain, user_register_orbital_core, user_register_to_new_domain,
+        user_withdraw_funds_from_domain,
     },
     misc::{generate_icq_relayer_config, start_icq_relayer},
     query::{
-        query_balance_query_id, query_icq_recipient_txs, query_icq_transfer_amount,
-        query_user_clearing_acc_addr_on_domain,
+        query_balance_query_id, query_clearing_account_queries, query_icq_recipient_txs,
+        query_icq_transfer_amount, query_user_clearing_acc_addr_on_domain,
     },
 };
 
@@ -81,6 +81,14 @@ fn main() -> Result<(), Box<dyn Error>> {
             .get_chain(NEUTRON_CHAIN_NAME)
             .admin_addr
             .to_string(),
+        solver_bond: coin(1_000_000, "untrn"),
+        unbonding_period: cw_utils::Duration::Time(300),
+        auction_duration: cw_utils::Duration::Time(30),
+        min_bid_increment: Uint128::new(100),
+        fill_timeout: cw_utils::Duration::Time(120),
+        slashed_bond_user_share: Decimal::percent(80),
+        min_tx_timeout: Uint64::new(10),
+        max_tx_timeout: Uint64::new(3_600),
     };
 
     // instantiate orbital-core from the ACC0_KEY (=admin in localic-utils)
@@ -98,16 +106,19 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     info!("orbital core: {}", orbital_core.address);
 
+    // clearing accounts get their balances monitored as soon as they open
     admin_register_domain(
         &test_ctx,
         orbital_core.address.to_string(),
         GAIA_CHAIN_NAME.to_string(),
+        vec!["uatom".to_string()],
     )?;
     std::thread::sleep(Duration::from_secs(2));
     admin_register_domain(
         &test_ctx,
         orbital_core.address.to_string(),
         JUNO_CHAIN_NAME.to_string(),
+        vec!["ujuno".to_string()],
     )?;
 
     // first we register users to orbital-core
@@ -186,17 +197,14 @@ fn main() -> Result<(), Box<dyn Error>> {
     .unwrap()
     .addr;
 
-    std::thread::sleep(Duration::from_secs(5));
-
-    let icq_registration_response = register_icq_balances_query(
+    let acc_1_juno_balances_query_id = query_clearing_account_queries(
         &test_ctx,
         orbital_core.address.to_string(),
+        ACC1_ADDR,
         JUNO_CHAIN_NAME.to_string(),
-        acc_1_juno_addr.to_string(),
-        vec!["ujuno".to_string()],
-    )?;
-
-    info!("icq registration response: {:?}", icq_registration_response);
+    )?
+    .balances_query_id
+    .unwrap();
 
     std::thread::sleep(Duration::from_secs(5));
 
@@ -229,8 +237,11 @@ fn main() -> Result<(), Box<dyn Error>> {
     info!("sleeping for 5...");
     std::thread::sleep(Duration::from_secs(5));
 
-    let balance_query_response =
-        query_balance_query_id(&test_ctx, orbital_core.address.to_string(), 1)?;
+    let balance_query_response = query_balance_query_id(
+        &test_ctx,
+        orbital_core.address.to_string(),
+        acc_1_juno_balances_query_id,
+    )?;
     let post_transfer_balance = get_balance(
         test_ctx
             .get_request_builder()
@@ -275,8 +286,11 @@ fn main() -> Result<(), Box<dyn Error>> {
     info!("sleeping for 5...");
     std::thread::sleep(Duration::from_secs(5));
 
-    let balance_query_response =
-        query_balance_query_id(&test_ctx, orbital_core.address.to_string(), 1)?;
+    let balance_query_response = query_balance_query_id(
+        &test_ctx,
+        orbital_core.address.to_string(),
+        acc_1_juno_balances_query_id,
+    )?;
     let post_transfer_balance = get_balance(
         test_ctx
             .get_request_builder()
@@ -300,23 +314,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     info!("user 2 juno acc balance   : {:?}", user_2_juno_bal);
 
-    info!("sleeping for 5...");
-    std::thread::sleep(Duration::from_secs(5));
-
-    info!("registering ICQ transfer queries on juno");
-    register_icq_transfers_query(
-        &test_ctx,
-        orbital_core.address.to_string(),
-        JUNO_CHAIN_NAME.to_string(),
-        acc_2_juno_addr.to_string(),
-    )?;
-    std::thread::sleep(Duration::from_secs(5));
-    register_icq_transfers_query(
-        &test_ctx,
-        orbital_core.address.to_string(),
-        JUNO_CHAIN_NAME.to_string(),
-        acc_1_juno_addr.to_string(),
-    )?;
+    info!("sleeping for 15...");
     std::thread::sleep(Duration::from_secs(15));
 
     info!("user_2 withdrawing juno to user_1");
@@ -1,10 +1,16 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{coin, Uint64};
 use localic_std::{
     errors::LocalError, modules::cosmwasm::contract_execute, types::TransactionResponse,
 };
 use localic_utils::{utils::test_context::TestContext, NEUTRON_CHAIN_NAME};
 use log::info;
-use orbital_core::{msg::ExecuteMsg, orbital_domain::UncheckedOrbitalDomainConfig};
+use orbital_core::{
+    msg::{ExecuteMsg, Withdrawal},
+    orbital_domain::UncheckedOrbitalDomainConfig,
+    utils::NEUTRON_DOMAIN,
+};
 
 use crate::{utils::misc::NEUTRON_IBC_GAS_FLAG, ACC0_KEY};
 
@@ -26,69 +32,6 @@ pub fn user_register_orbital_core(
     )
 }
 
-pub fn register_icq_balances_query(
-    test_ctx: &TestContext,
-    orbital_core: String,
-    domain: String,
-    addr: String,
-    denoms: Vec<String>,
-) -> Result<TransactionResponse, LocalError> {
-    info!("registering ICQ balances query on domain {domain} for {addr}...");
-
-    let register_icq_msg = ExecuteMsg::RegisterBalancesQuery {
-        connection_id: test_ctx
-            .get_connections()
-            .src(NEUTRON_CHAIN_NAME)
-            .dest(&domain)
-            .get(),
-        update_period: 5,
-        addr,
-        denoms,
-    };
-
-    contract_execute(
-        test_ctx
-            .get_request_builder()
-            .get_request_builder(NEUTRON_CHAIN_NAME),
-        &orbital_core,
-        ACC0_KEY,
-        &serde_json::to_string(&register_icq_msg)
-            .map_err(|e| LocalError::Custom { msg: e.to_string() })?,
-            NEUTRON_IBC_GAS_FLAG,
-    )
-}
-
-pub fn register_icq_transfers_query(
-    test_ctx: &TestContext,
-    orbital_core: String,
-    domain: String,
-    addr: String,
-) -> Result<TransactionResponse, LocalError> {
-    info!("registering ICQ transfers query on domain {domain} for {addr}...");
-
-    let register_icq_msg = ExecuteMsg::RegisterTransfersQuery {
-        connection_id: test_ctx
-            .get_connections()
-            .src(NEUTRON_CHAIN_NAME)
-            .dest(&domain)
-            .get(),
-        update_period: 5,
-        recipient: addr,
-        min_height: None,
-    };
-
-    contract_execute(
-        test_ctx
-            .get_request_builder()
-            .get_request_builder(NEUTRON_CHAIN_NAME),
-        &orbital_core,
-        ACC0_KEY,
-        &serde_json::to_string(&register_icq_msg)
-            .map_err(|e| LocalError::Custom { msg: e.to_string() })?,
-            NEUTRON_IBC_GAS_FLAG,
-    )
-}
-
 pub fn user_withdraw_funds_from_domain(
     test_ctx: &TestContext,
     orbital_core: String,
@@ -102,8 +45,12 @@ pub fn user_withdraw_funds_from_domain(
 
     let withdraw_funds_msg = ExecuteMsg::UserWithdrawFunds {
         domain,
-        coin: coin(amount, denom),
-        dest: addr,
+        withdrawals: vec![Withdrawal {
+            coin: coin(amount, denom),
+            dest: addr,
+        }],
+        timeout: None,
+        memo: None,
     };
 
     contract_execute(
@@ -133,7 +80,9 @@ pub fn user_register_to_new_domain(
         user_key,
         &serde_json::to_string(&ExecuteMsg::RegisterUserDomain { domain })
             .map_err(|e| LocalError::Custom { msg: e.to_string() })?,
-        "--amount 1000000untrn --gas 5000000",
+        // covers the ICA registration fee and the deposits of the transfers
+        // and balances queries
+        "--amount 3000000untrn --gas 5000000",
     )
 }
 
@@ -141,6 +90,7 @@ pub fn admin_register_domain(
     test_ctx: &TestContext,
     orbital_core: String,
     domain: String,
+    monitored_denoms: Vec<String>,
 ) -> Result<TransactionResponse, LocalError> {
     let admin_register_domain_msg = ExecuteMsg::RegisterNewDomain {
         domain: domain.to_string(),
@@ -156,6 +106,15 @@ pub fn admin_register_domain(
                 .dest(&domain)
                 .get(),
             timeout: Uint64::new(100),
+            transfer_channels: BTreeMap::from([(
+                NEUTRON_DOMAIN.to_string(),
+                test_ctx
+                    .get_transfer_channels()
+                    .src(&domain)
+                    .dest(NEUTRON_CHAIN_NAME)
+                    .get(),
+            )]),
+            monitored_denoms,
         },
     };
 
@@ -3,7 +3,7 @@ use localic_utils::{utils::test_context::TestContext, NEUTRON_CHAIN_NAME};
 use log::info;
 use orbital_core::{
     msg::{GetTransfersAmountResponse, QueryMsg, RecipientTxsResponse},
-    state::{ClearingAccountConfig, UserConfig},
+    state::{ClearingAccountConfig, ClearingAccountQueries, UserConfig},
 };
 
 pub fn query_user_clearing_acc_addr_on_domain(
@@ -60,6 +60,36 @@ pub fn _query_user_config(
     Ok(user_config)
 }
 
+pub fn query_clearing_account_queries(
+    test_ctx: &TestContext,
+    orbital_core: String,
+    user_addr: &str,
+    domain: String,
+) -> Result<ClearingAccountQueries, LocalError> {
+    let query_response = contract_query(
+        test_ctx
+            .get_request_builder()
+            .get_request_builder(NEUTRON_CHAIN_NAME),
+        &orbital_core,
+        &serde_json::to_string(&QueryMsg::ClearingAccountQueries {
+            addr: user_addr.to_string(),
+            domain: domain.to_string(),
+        })
+        .map_err(|e| LocalError::Custom { msg: e.to_string() })?,
+    )["data"]
+        .clone();
+
+    let clearing_account_queries: ClearingAccountQueries = serde_json::from_value(query_response)
+        .map_err(|e| LocalError::Custom { msg: e.to_string() })?;
+
+    info!(
+        "user {user_addr} clearing account queries on {domain}: {:?}",
+        clearing_account_queries
+    );
+
+    Ok(clearing_account_queries)
+}
+
 pub fn query_balance_query_id(
     test_ctx: &TestContext,
     orbital_core: String,
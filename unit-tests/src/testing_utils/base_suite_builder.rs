@@ -1,12 +1,13 @@
-use cosmwasm_std::{coin, coins, Addr, Coin};
+use cosmwasm_std::{coin, coins, Addr, Coin, Empty};
 use cw_multi_test::{BasicAppBuilder, Executor, MockApiBech32, SimpleAddressGenerator, WasmKeeper};
 
 use super::{
-    consts::{ALL_DENOMS, CHAIN_PREFIX, DENOM_NTRN, FAUCET, NOTE, OWNER, USER_1},
+    consts::{ALL_DENOMS, CHAIN_PREFIX, DENOM_NTRN, FAUCET, OWNER, SOLVER_1, SOLVER_2, USER_1},
     neutron_adapters::{
         neutron_module::NeutronKeeper, neutron_type_contracts::orbital_core_contract,
         stargate_module::StargateModule,
     },
+    polytone_mocks::{mock_note_contract, mock_voice_contract, NoteInstantiateMsg},
     types::CustomApp,
 };
 
@@ -65,16 +66,59 @@ impl Default for SuiteBuilder {
 
         let owner_addr = app.api().addr_make(OWNER);
         let faucet_addr = app.api().addr_make(FAUCET);
-        let note_addr = app.api().addr_make(NOTE);
         let user_addr = app.api().addr_make(USER_1);
 
         app.send_tokens(
             faucet_addr.clone(),
             user_addr.clone(),
-            &coins(1_000_000, DENOM_NTRN),
+            &coins(10_000_000, DENOM_NTRN),
         )
         .unwrap();
 
+        // owner pays the deposits of the interchain queries it registers
+        app.send_tokens(
+            faucet_addr.clone(),
+            owner_addr.clone(),
+            &coins(10_000_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+        for solver in [SOLVER_1, SOLVER_2] {
+            let solver_addr = app.api().addr_make(solver);
+            app.send_tokens(
+                faucet_addr.clone(),
+                solver_addr,
+                &coins(10_000_000, DENOM_NTRN),
+            )
+            .unwrap();
+        }
+
+        // polytone note and voice pair serving polytone domains
+        let voice_code_id = app.store_code(mock_voice_contract());
+        let voice_addr = app
+            .instantiate_contract(
+                voice_code_id,
+                owner_addr.clone(),
+                &Empty {},
+                &[],
+                "voice",
+                None,
+            )
+            .unwrap();
+        let note_code_id = app.store_code(mock_note_contract());
+        let note_addr = app
+            .instantiate_contract(
+                note_code_id,
+                owner_addr.clone(),
+                &NoteInstantiateMsg {
+                    voice: voice_addr.to_string(),
+                },
+                &[],
+                "note",
+                None,
+            )
+            .unwrap();
+
         Self {
             faucet: faucet_addr,
             admin: owner_addr,
@@ -1,20 +1,40 @@
 use cosmwasm_std::Addr;
-use cw_storage_plus::Map;
+use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
+use neutron_sdk::bindings::types::{InterchainQueryResult, RegisteredQuery};
 
 pub const DENOM_FALLBACK: &str = "ufallback";
 pub const DENOM_ATOM: &str = "uatom";
 pub const DENOM_NTRN: &str = "untrn";
 pub const DENOM_OSMO: &str = "uosmo";
+pub const DENOM_STARS: &str = "ustars";
 pub const FAUCET: &str = "faucet_addr";
 pub const ADMIN: &str = "admin_addr";
-pub const ALL_DENOMS: &[&str] = &[DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, DENOM_FALLBACK];
+pub const ALL_DENOMS: &[&str] = &[
+    DENOM_ATOM,
+    DENOM_NTRN,
+    DENOM_OSMO,
+    DENOM_STARS,
+    DENOM_FALLBACK,
+];
 pub const CHAIN_PREFIX: &str = "cosmos";
 pub const OWNER: &str = "owner";
-pub const NOTE: &str = "note";
 pub const USER_1: &str = "user_1";
+pub const SOLVER_1: &str = "solver_1";
+pub const SOLVER_2: &str = "solver_2";
+
+pub const SOLVER_BOND_AMOUNT: u128 = 1_000_000;
+pub const UNBONDING_PERIOD: Duration = Duration::Time(86_400);
+pub const AUCTION_DURATION: Duration = Duration::Time(300);
+pub const MIN_BID_INCREMENT: u128 = 100;
+pub const FILL_TIMEOUT: Duration = Duration::Time(600);
+pub const SLASHED_BOND_USER_SHARE_PERCENT: u64 = 80;
+pub const MIN_TX_TIMEOUT: u64 = 10;
+pub const MAX_TX_TIMEOUT: u64 = 3_600;
 
 pub const GAIA_DOMAIN: &str = "gaia";
 pub const OSMOSIS_DOMAIN: &str = "osmosis";
+pub const STARGAZE_DOMAIN: &str = "stargaze";
 
 /// Namespace for neutron storage
 pub const NAMESPACE_NEUTRON: &[u8] = b"neutron_storage";
@@ -22,6 +42,28 @@ pub const NAMESPACE_NEUTRON: &[u8] = b"neutron_storage";
 /// Map for (sender, conn_id) => account_id
 pub const ACCOUNTS: Map<(&Addr, String, String), Addr> = Map::new("accounts");
 
+/// Map for query_id => registered interchain query
+pub const REGISTERED_QUERIES: Map<u64, RegisteredQuery> = Map::new("registered_queries");
+pub const QUERY_NONCE: Item<u64> = Item::new("query_nonce");
+/// sequence of the last ICA tx packet sent over the mocked channel
+pub const TX_SEQUENCE: Item<u64> = Item::new("tx_sequence");
+/// channel used by the mocked interchain accounts
+pub const ICA_CHANNEL: &str = "channel-1";
+/// max number of messages in an ICA tx reported by the mocked interchaintxs params
+pub const ICA_TX_MAX_MESSAGES: u64 = 5;
+/// untrn fee of ICA registrations reported by the mocked interchaintxs params
+pub const ICA_REGISTER_FEE: u128 = 1_000_000;
+/// untrn deposit of interchain queries reported by the mocked interchainqueries params
+pub const ICQ_DEPOSIT: u128 = 1_000_000;
+/// error returned by the mocked ICA registrations and tx submissions, if set
+pub const ICA_FAILURE: Item<String> = Item::new("ica_failure");
+/// error returned by the mocked interchain query registrations, if set
+pub const ICQ_FAILURE: Item<String> = Item::new("icq_failure");
+/// Map for packet sequence => timeout in seconds of the submitted ICA tx
+pub const SUBMITTED_TX_TIMEOUTS: Map<u64, u64> = Map::new("submitted_tx_timeouts");
+/// Map for query_id => latest KV query result
+pub const QUERY_RESULTS: Map<u64, InterchainQueryResult> = Map::new("query_results");
+
 pub const LOCAL_CHANNELS: Map<String, String> = Map::new("local_channels");
 pub const LOCAL_CHANNELS_VALUES: Map<String, String> = Map::new("local_channels_values");
 
@@ -1,4 +1,5 @@
 pub mod base_suite_builder;
 pub mod consts;
 pub mod neutron_adapters;
+pub mod polytone_mocks;
 pub mod types;
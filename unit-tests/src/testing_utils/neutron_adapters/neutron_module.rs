@@ -1,13 +1,20 @@
 use cosmwasm_std::{
-    coin, to_json_binary, to_json_string, Addr, Api, BlockInfo, CustomMsg, CustomQuery, StdError,
-    StdResult, Storage,
+    coin, coins, to_json_binary, to_json_string, Addr, Api, BlockInfo, CustomMsg, CustomQuery,
+    StdError, StdResult, Storage,
 };
 use cw_multi_test::{
     error::{bail, AnyError, AnyResult},
-    AppResponse, CosmosRouter, MockApiBech32, Module, WasmSudo,
+    AppResponse, BankSudo, CosmosRouter, MockApiBech32, Module, WasmSudo,
 };
 use neutron_sdk::{
-    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    bindings::{
+        msg::{
+            MsgRegisterInterchainAccountResponse, MsgRegisterInterchainQueryResponse,
+            MsgSubmitTxResponse, NeutronMsg,
+        },
+        query::{NeutronQuery, QueryRegisteredQueryResponse, QueryRegisteredQueryResultResponse},
+        types::{KVKey, RegisteredQuery},
+    },
     interchain_txs::helpers::get_port_id,
     query::min_ibc_fee::MinIbcFeeResponse,
     sudo::msg::SudoMsg,
@@ -17,8 +24,9 @@ use serde::de::DeserializeOwned;
 
 use crate::testing_utils::{
     consts::{
-        ACCOUNTS, DENOM_NTRN, LOCAL_CHANNELS, LOCAL_CHANNELS_VALUES, REMOTE_CHANNELS,
-        REMOTE_CHANNELS_VALUES,
+        ACCOUNTS, DENOM_NTRN, ICA_CHANNEL, ICA_FAILURE, ICQ_DEPOSIT, ICQ_FAILURE, LOCAL_CHANNELS,
+        LOCAL_CHANNELS_VALUES, QUERY_NONCE, QUERY_RESULTS, REGISTERED_QUERIES, REMOTE_CHANNELS,
+        REMOTE_CHANNELS_VALUES, SUBMITTED_TX_TIMEOUTS, TX_SEQUENCE,
     },
     types::OpenAckVersion,
 };
@@ -92,8 +100,10 @@ impl NeutronKeeper {
         conn_id: String,
         account_id: String,
     ) -> Result<(), AnyError> {
+        // registering an existing account reopens its channel, which
+        // keeps the same remote address
         if ACCOUNTS.has(storage, (&sender, conn_id.clone(), account_id.clone())) {
-            bail!("Account already registered");
+            return Ok(());
         }
 
         let addr = self
@@ -120,6 +130,45 @@ impl NeutronKeeper {
         ACCOUNTS.remove(storage, (sender, conn_id, account_id))
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn register_query(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        sender: &Addr,
+        query_type: String,
+        keys: Vec<KVKey>,
+        transactions_filter: String,
+        connection_id: String,
+        update_period: u64,
+    ) -> AnyResult<u64> {
+        // neutron query ids start from 1
+        let query_id = QUERY_NONCE.may_load(storage)?.unwrap_or_default() + 1;
+
+        let registered_query: RegisteredQuery = serde_json::from_value(serde_json::json!({
+            "id": query_id,
+            "owner": sender.to_string(),
+            "query_type": query_type,
+            "keys": keys,
+            "transactions_filter": transactions_filter,
+            "connection_id": connection_id,
+            "update_period": update_period,
+            "last_submitted_result_local_height": 0,
+            "last_submitted_result_remote_height": {
+                "revision_number": 0,
+                "revision_height": 0,
+            },
+            "deposit": coins(ICQ_DEPOSIT, DENOM_NTRN),
+            "submit_timeout": 0,
+            "registered_at_height": block.height,
+        }))?;
+
+        REGISTERED_QUERIES.save(storage, query_id, &registered_query)?;
+        QUERY_NONCE.save(storage, &query_id)?;
+
+        Ok(query_id)
+    }
+
     fn get_account(
         &self,
         storage: &dyn Storage,
@@ -161,6 +210,10 @@ impl Module for NeutronKeeper {
                 interchain_account_id,
                 register_fee,
             } => {
+                if let Some(error) = ICA_FAILURE.may_load(storage)? {
+                    bail!(error);
+                }
+
                 // Send fees to fee burner
                 // we do it mainly to make sure fees are deducted in our tests
                 let fee = match register_fee {
@@ -189,8 +242,8 @@ impl Module for NeutronKeeper {
                         contract_addr: sender.clone(),
                         message: to_json_binary(&neutron_sdk::sudo::msg::SudoMsg::OpenAck {
                             port_id: get_port_id(sender.to_string(), interchain_account_id.clone()),
-                            channel_id: "channel-1".to_string(),
-                            counterparty_channel_id: "channel-1".to_string(),
+                            channel_id: ICA_CHANNEL.to_string(),
+                            counterparty_channel_id: ICA_CHANNEL.to_string(),
                             counterparty_version: to_json_string(&OpenAckVersion {
                                 version: "ica".to_string(),
                                 controller_connection_id: connection_id.clone(),
@@ -211,8 +264,114 @@ impl Module for NeutronKeeper {
                     }),
                 )?;
 
+                Ok(AppResponse {
+                    data: Some(to_json_binary(&MsgRegisterInterchainAccountResponse {
+                        channel_id: ICA_CHANNEL.to_string(),
+                        port_id: get_port_id(sender.to_string(), interchain_account_id),
+                    })?),
+                    ..Default::default()
+                })
+            }
+            NeutronMsg::RegisterInterchainQuery {
+                query_type,
+                keys,
+                transactions_filter,
+                connection_id,
+                update_period,
+            } => {
+                if let Some(error) = ICQ_FAILURE.may_load(storage)? {
+                    bail!(error);
+                }
+
+                // escrow the query deposit, which we burn to make sure
+                // it is deducted in our tests
+                let deposit_msg = cosmwasm_std::BankMsg::Burn {
+                    amount: coins(ICQ_DEPOSIT, DENOM_NTRN),
+                };
+                router.execute(api, storage, block, sender.clone(), deposit_msg.into())?;
+
+                let query_id = self.register_query(
+                    storage,
+                    block,
+                    &sender,
+                    query_type,
+                    keys,
+                    transactions_filter,
+                    connection_id,
+                    update_period,
+                )?;
+
+                Ok(AppResponse {
+                    data: Some(to_json_binary(&MsgRegisterInterchainQueryResponse {
+                        id: query_id,
+                    })?),
+                    ..Default::default()
+                })
+            }
+            NeutronMsg::UpdateInterchainQuery {
+                query_id,
+                new_keys,
+                new_update_period,
+                new_transactions_filter,
+            } => {
+                let mut registered_query = REGISTERED_QUERIES.load(storage, query_id)?;
+                if registered_query.owner != sender.to_string() {
+                    bail!("only owner can update the query");
+                }
+
+                if let Some(keys) = new_keys {
+                    registered_query.keys = keys;
+                }
+                if let Some(update_period) = new_update_period {
+                    registered_query.update_period = update_period;
+                }
+                if let Some(transactions_filter) = new_transactions_filter {
+                    registered_query.transactions_filter = transactions_filter;
+                }
+                REGISTERED_QUERIES.save(storage, query_id, &registered_query)?;
+
                 Ok(AppResponse::default())
             }
+            NeutronMsg::RemoveInterchainQuery { query_id } => {
+                let registered_query = REGISTERED_QUERIES.load(storage, query_id)?;
+                if registered_query.owner != sender.to_string() {
+                    bail!("only owner can remove the query");
+                }
+                REGISTERED_QUERIES.remove(storage, query_id);
+                QUERY_RESULTS.remove(storage, query_id);
+
+                // return the burned deposit to the owner of the query
+                router.sudo(
+                    api,
+                    storage,
+                    block,
+                    BankSudo::Mint {
+                        to_address: sender.to_string(),
+                        amount: registered_query.deposit,
+                    }
+                    .into(),
+                )?;
+
+                Ok(AppResponse::default())
+            }
+            // txs are not relayed to remote domains, we only assign them a packet sequence
+            NeutronMsg::SubmitTx { timeout, .. } => {
+                if let Some(error) = ICA_FAILURE.may_load(storage)? {
+                    bail!(error);
+                }
+
+                let sequence_id = TX_SEQUENCE.may_load(storage)?.unwrap_or_default() + 1;
+                TX_SEQUENCE.save(storage, &sequence_id)?;
+                SUBMITTED_TX_TIMEOUTS.save(storage, sequence_id, &timeout)?;
+
+                Ok(AppResponse {
+                    data: Some(to_json_binary(&MsgSubmitTxResponse {
+                        sequence_id,
+                        channel: ICA_CHANNEL.to_string(),
+                    })?),
+                    ..Default::default()
+                })
+            }
             _ => {
                 println!("custom module execute catch-all arm");
                 unimplemented!()
@@ -253,6 +412,16 @@ impl Module for NeutronKeeper {
                 },
             })
             .unwrap()),
+            NeutronQuery::RegisteredInterchainQuery { query_id } => {
+                Ok(to_json_binary(&QueryRegisteredQueryResponse {
+                    registered_query: REGISTERED_QUERIES.load(storage, query_id)?,
+                })?)
+            }
+            NeutronQuery::InterchainQueryResult { query_id } => {
+                Ok(to_json_binary(&QueryRegisteredQueryResultResponse {
+                    result: QUERY_RESULTS.load(storage, query_id)?,
+                })?)
+            }
             _ => {
                 println!("custom module query catch-all arm");
                 unimplemented!()
@@ -12,6 +12,8 @@ use neutron_sdk::proto_types::neutron::interchaintxs::v1::{Params, QueryParamsRe
 
 use serde::de::DeserializeOwned;
 
+use crate::testing_utils::consts::{ICA_REGISTER_FEE, ICA_TX_MAX_MESSAGES, ICQ_DEPOSIT};
+
 pub struct StargateModule;
 
 impl Stargate for StargateModule {
@@ -61,11 +63,33 @@ impl Stargate for StargateModule {
 
             let response = QueryParamsResponseCustom {
                 params: Some(Params {
-                    msg_submit_tx_max_messages: Uint64::new(1_000),
-                    register_fee: coins(1_000_000, "untrn"),
+                    msg_submit_tx_max_messages: Uint64::new(ICA_TX_MAX_MESSAGES),
+                    register_fee: coins(ICA_REGISTER_FEE, "untrn"),
                 }),
             };
 
+            Ok(to_json_binary(&response)?)
+        } else if path == "/neutron.interchainqueries.Query/Params" {
+            #[cw_serde]
+            struct Params {
+                pub query_submit_timeout: Uint64,
+                pub query_deposit: Vec<Coin>,
+                pub tx_query_removal_limit: Uint64,
+            }
+
+            #[cw_serde]
+            struct QueryParamsResponseCustom {
+                pub params: Params,
+            }
+
+            let response = QueryParamsResponseCustom {
+                params: Params {
+                    query_submit_timeout: Uint64::new(1_036_800),
+                    query_deposit: coins(ICQ_DEPOSIT, "untrn"),
+                    tx_query_removal_limit: Uint64::new(10_000),
+                },
+            };
+
             Ok(to_json_binary(&response)?)
         } else {
             Err(anyhow!("Unexpected query request"))
@@ -108,7 +132,7 @@ impl Stargate for StargateModule {
             };
 
             let params = Params {
-                msg_submit_tx_max_messages: 1_000,
+                msg_submit_tx_max_messages: ICA_TX_MAX_MESSAGES,
                 register_fee: vec![proto_coin],
             };
 
@@ -0,0 +1,177 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_json, instantiate2_address, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps,
+    DepsMut, Empty, Env, IbcMsg, MessageInfo, Reply, Response, StdError, StdResult, SubMsg,
+    SubMsgResult, WasmMsg,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
+use orbital_core::{
+    msg::ExecuteMsg as OrbitalExecuteMsg,
+    polytone::{Callback, CallbackMessage, CallbackRequest, ExecutionResponse, NoteExecuteMsg},
+};
+
+/// voice the mock note relays executions to
+const VOICE: Item<Addr> = Item::new("voice");
+
+/// checksum of the proxy code the mock voice derives proxy addresses from
+const PROXY_CHECKSUM: [u8; 32] = [0; 32];
+
+#[cw_serde]
+pub struct NoteInstantiateMsg {
+    pub voice: String,
+}
+
+#[cw_serde]
+pub enum VoiceExecuteMsg {
+    /// executes the msgs through the proxy of the sender the note executes for
+    Rx {
+        sender: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+}
+
+/// mock note which relays executions to the mock voice within the same app.
+/// the outcome of the execution is delivered to the callback receiver right away.
+pub fn mock_note_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let contract =
+        ContractWrapper::new(note_execute, note_instantiate, mock_query).with_reply(note_reply);
+
+    Box::new(contract)
+}
+
+/// mock voice standing in for the remote domain. every sender the note executes
+/// for is served by its own proxy, whose bank balances must cover the funds it is asked to send.
+pub fn mock_voice_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    let contract = ContractWrapper::new(voice_execute, voice_instantiate, mock_query);
+
+    Box::new(contract)
+}
+
+fn note_instantiate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: NoteInstantiateMsg,
+) -> StdResult<Response<NeutronMsg>> {
+    VOICE.save(deps.storage, &deps.api.addr_validate(&msg.voice)?)?;
+    Ok(Response::default())
+}
+
+fn note_execute(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    info: MessageInfo,
+    msg: NoteExecuteMsg,
+) -> StdResult<Response<NeutronMsg>> {
+    let NoteExecuteMsg::Execute {
+        on_behalf_of,
+        msgs,
+        callback,
+        ..
+    } = msg;
+    let voice = VOICE.load(deps.storage)?;
+
+    // the controller of the note executes on behalf of other addresses
+    let sender = on_behalf_of.unwrap_or_else(|| info.sender.to_string());
+    let rx_msg = WasmMsg::Execute {
+        contract_addr: voice.to_string(),
+        msg: to_json_binary(&VoiceExecuteMsg::Rx { sender, msgs })?,
+        funds: vec![],
+    };
+
+    // failed executions are reported through the callback instead of failing the note
+    Ok(Response::new().add_submessage(
+        SubMsg::reply_always(rx_msg, 0).with_payload(to_json_binary(&(info.sender, callback))?),
+    ))
+}
+
+fn note_reply(
+    _deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    reply: Reply,
+) -> StdResult<Response<NeutronMsg>> {
+    let (initiator, callback): (Addr, Option<CallbackRequest>) = from_json(&reply.payload)?;
+    let Some(callback) = callback else {
+        return Ok(Response::default());
+    };
+
+    let result = match reply.result {
+        SubMsgResult::Ok(response) => {
+            let executed_by = response
+                .events
+                .iter()
+                .flat_map(|event| event.attributes.iter())
+                .find(|attr| attr.key == "proxy")
+                .map(|attr| attr.value.to_string())
+                .ok_or_else(|| StdError::generic_err("voice did not report the proxy"))?;
+
+            Callback::Execute(Ok(ExecutionResponse {
+                executed_by,
+                result: vec![],
+            }))
+        }
+        SubMsgResult::Err(error) => Callback::Execute(Err(error)),
+    };
+
+    Ok(Response::new().add_message(WasmMsg::Execute {
+        contract_addr: callback.receiver,
+        msg: to_json_binary(&OrbitalExecuteMsg::Callback(CallbackMessage {
+            initiator,
+            initiator_msg: callback.msg,
+            result,
+        }))?,
+        funds: vec![],
+    }))
+}
+
+fn voice_instantiate(
+    _deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response<NeutronMsg>> {
+    Ok(Response::default())
+}
+
+fn voice_execute(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    _info: MessageInfo,
+    msg: VoiceExecuteMsg,
+) -> StdResult<Response<NeutronMsg>> {
+    let VoiceExecuteMsg::Rx { sender, msgs } = msg;
+
+    // proxies are instantiated by the voice with the sender as the salt
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let salt = deps.api.addr_canonicalize(&sender)?;
+    let proxy = instantiate2_address(&PROXY_CHECKSUM, &creator, salt.as_slice())
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let proxy = deps.api.addr_humanize(&proxy)?;
+
+    for msg in msgs {
+        let amount = match msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount,
+            CosmosMsg::Ibc(IbcMsg::Transfer { amount, .. }) => vec![amount],
+            _ => return Err(StdError::generic_err("unsupported proxy msg")),
+        };
+
+        for coin in amount {
+            let balance = deps.querier.query_balance(&proxy, &coin.denom)?;
+            if balance.amount < coin.amount {
+                return Err(StdError::generic_err(format!(
+                    "insufficient funds: {balance} < {coin}"
+                )));
+            }
+        }
+    }
+
+    Ok(Response::new().add_attribute("proxy", proxy))
+}
+
+fn mock_query(_deps: Deps<NeutronQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err(
+        "mock polytone contracts have no queries",
+    ))
+}
@@ -0,0 +1,187 @@
+use cosmwasm_std::{coin, coins, Uint128};
+
+use orbital_core::state::IntentStatus;
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, MIN_BID_INCREMENT, OSMOSIS_DOMAIN,
+            SOLVER_1, SOLVER_2, SOLVER_BOND_AMOUNT, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{
+        setup_open_auction, setup_user_on_domains, setup_won_auction, Suite,
+    },
+};
+
+/// submits intent 0 of USER_1 asking for 1_000 uosmo on osmosis in
+/// exchange for 100 uatom on gaia
+fn submit_intent(suite: &mut Suite) {
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Solver not registered")]
+fn test_bid_validates_solver_registration() {
+    let mut suite = setup_open_auction();
+
+    suite.bid(USER_1, 0, 1_000).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Solver not bonded")]
+fn test_bid_validates_solver_bond() {
+    let mut suite = setup_open_auction();
+    suite.unbond_solver(SOLVER_1).unwrap();
+
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Solver not bonded")]
+fn test_bid_validates_reserved_bond() {
+    let mut suite = setup_won_auction();
+    submit_intent(&mut suite);
+
+    // bond of SOLVER_1 backs the won auction of intent 0
+    suite.bid(SOLVER_1, 1, 1_000).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown intent: 1")]
+fn test_bid_validates_intent_existance() {
+    let mut suite = setup_open_auction();
+
+    suite.bid(SOLVER_1, 1, 1_000).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Solver address not set for domain: gaia")]
+fn test_bid_validates_solver_input_domain_address() {
+    let mut suite = setup_user_on_domains();
+    submit_intent(&mut suite);
+    suite
+        .register_solver(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+    suite
+        .set_solver_domain_address(SOLVER_1, OSMOSIS_DOMAIN, "osmo_solver")
+        .unwrap();
+
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Solver address not set for domain: osmosis")]
+fn test_bid_validates_solver_destination_domain_address() {
+    let mut suite = setup_user_on_domains();
+    submit_intent(&mut suite);
+    suite
+        .register_solver(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+    suite
+        .set_solver_domain_address(SOLVER_1, GAIA_DOMAIN, "cosmos_solver")
+        .unwrap();
+
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Bid too low, minimum bid is 1000")]
+fn test_bid_validates_intent_minimum() {
+    let mut suite = setup_open_auction();
+
+    suite.bid(SOLVER_1, 0, 999).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Bid too low, minimum bid is 1100")]
+fn test_bid_validates_min_increment() {
+    let mut suite = setup_open_auction();
+
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+    suite
+        .bid(SOLVER_2, 0, 1_000 + MIN_BID_INCREMENT - 1)
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Auction has ended")]
+fn test_bid_after_auction_end() {
+    let mut suite = setup_open_auction();
+
+    suite.advance_time(300);
+
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Auction still in progress")]
+fn test_close_auction_before_end() {
+    let mut suite = setup_open_auction();
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+
+    suite.close_auction(0).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unexpected intent status: Open")]
+fn test_close_auction_without_bids() {
+    let mut suite = setup_open_auction();
+    suite.advance_time(300);
+
+    suite.close_auction(0).unwrap();
+}
+
+#[test]
+fn test_auction_happy() {
+    let mut suite = setup_open_auction();
+
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+    assert_eq!(
+        suite.query_intent(0).unwrap().status,
+        IntentStatus::Auctioning
+    );
+
+    suite.bid(SOLVER_2, 0, 1_000 + MIN_BID_INCREMENT).unwrap();
+    suite.bid(SOLVER_1, 0, 1_500).unwrap();
+
+    suite.advance_time(300);
+    suite.close_auction(0).unwrap();
+
+    let auction = suite.query_auction(0).unwrap();
+    let highest_bid = auction.highest_bid.unwrap();
+    assert_eq!(highest_bid.amount, Uint128::new(1_500));
+    assert_eq!(highest_bid.solver, make_addr(&suite.app, SOLVER_1));
+    assert_eq!(auction.winner, Some(make_addr(&suite.app, SOLVER_1)));
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Won);
+}
+
+#[test]
+fn test_close_auction_forfeited_by_winner_without_free_bond() {
+    let mut suite = setup_open_auction();
+    submit_intent(&mut suite);
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+    suite.bid(SOLVER_1, 1, 1_000).unwrap();
+    suite.advance_time(300);
+
+    suite.close_auction(0).unwrap();
+    suite.close_auction(1).unwrap();
+
+    assert_eq!(
+        suite.query_solver(SOLVER_1).unwrap().reserved,
+        Uint128::new(SOLVER_BOND_AMOUNT)
+    );
+    assert_eq!(suite.query_intent(1).unwrap().status, IntentStatus::Open);
+    let auction = suite.query_auction(1).unwrap();
+    assert!(auction.highest_bid.is_none());
+    assert!(auction.winner.is_none());
+}
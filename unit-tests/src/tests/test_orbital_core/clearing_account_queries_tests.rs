@@ -0,0 +1,189 @@
+use cosmwasm_std::{coin, coins, Uint128};
+
+use orbital_core::state::{ClearingAccountQueries, IcqDeposit};
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::{make_addr, BaseSuite},
+        consts::{DENOM_ATOM, DENOM_NTRN, GAIA_DOMAIN, ICA_REGISTER_FEE, ICQ_DEPOSIT, USER_1},
+    },
+    tests::test_orbital_core::suite::{setup_monitored_domain, setup_user_on_domains, Suite},
+};
+
+fn query_user_ntrn(suite: &Suite) -> Uint128 {
+    suite
+        .query_balance(&make_addr(&suite.app, USER_1), DENOM_NTRN)
+        .amount
+}
+
+#[test]
+fn test_transfers_query_is_registered_on_open() {
+    let mut suite = setup_monitored_domain(vec![]);
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let queries = suite
+        .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+        .unwrap();
+    assert_eq!(
+        queries,
+        ClearingAccountQueries {
+            balances_query_id: None,
+            transfers_query_id: Some(suite.last_query_id()),
+        }
+    );
+    assert_eq!(
+        suite
+            .query_icq_deposit(queries.transfers_query_id.unwrap())
+            .unwrap(),
+        IcqDeposit {
+            payer: make_addr(&suite.app, USER_1),
+            deposit: coins(ICQ_DEPOSIT, DENOM_NTRN),
+            addr: clearing_account,
+            denoms: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_balances_query_is_registered_for_monitored_denoms() {
+    let mut suite = setup_monitored_domain(vec![DENOM_ATOM.to_string()]);
+    let pre_register_balance = query_user_ntrn(&suite);
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + 2 * ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    let queries = suite
+        .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+        .unwrap();
+    let balances_query_id = queries.balances_query_id.unwrap();
+    assert_ne!(Some(balances_query_id), queries.transfers_query_id);
+    assert_eq!(
+        suite.query_icq_deposit(balances_query_id).unwrap().payer,
+        make_addr(&suite.app, USER_1)
+    );
+
+    assert_eq!(
+        query_user_ntrn(&suite),
+        pre_register_balance - Uint128::new(ICA_REGISTER_FEE + 2 * ICQ_DEPOSIT)
+    );
+}
+
+#[test]
+#[should_panic(expected = "insufficient fee")]
+fn test_registration_requires_query_deposits() {
+    let mut suite = setup_monitored_domain(vec![DENOM_ATOM.to_string()]);
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_reopened_account_keeps_its_queries() {
+    let mut suite = setup_user_on_domains();
+    let queries = suite
+        .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+        .unwrap();
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+    suite.ica_timeout(1).unwrap();
+
+    let last_query_id = suite.last_query_id();
+    suite
+        .reopen_clearing_account(USER_1, GAIA_DOMAIN, coins(ICA_REGISTER_FEE, DENOM_NTRN))
+        .unwrap();
+
+    assert_eq!(suite.last_query_id(), last_query_id);
+    assert_eq!(
+        suite
+            .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+            .unwrap(),
+        queries
+    );
+}
+
+#[test]
+fn test_failed_account_registration_refunds_query_deposit() {
+    let mut suite = setup_monitored_domain(vec![DENOM_ATOM.to_string()]);
+    suite.set_ica_failure(Some("connection not found"));
+    let pre_register_balance = query_user_ntrn(&suite);
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + 2 * ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert_eq!(
+        query_user_ntrn(&suite),
+        pre_register_balance - Uint128::new(ICA_REGISTER_FEE)
+    );
+    assert_eq!(
+        suite
+            .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+            .unwrap(),
+        ClearingAccountQueries::default()
+    );
+}
+
+#[test]
+fn test_failed_query_registration_refunds_deposit() {
+    let mut suite = setup_monitored_domain(vec![]);
+    suite.set_icq_failure(Some("invalid connection"));
+    let pre_register_balance = query_user_ntrn(&suite);
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    // the clearing account is opened without being monitored
+    assert!(suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        suite
+            .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+            .unwrap(),
+        ClearingAccountQueries::default()
+    );
+    assert_eq!(
+        query_user_ntrn(&suite),
+        pre_register_balance - Uint128::new(ICA_REGISTER_FEE)
+    );
+}
@@ -0,0 +1,215 @@
+use cosmwasm_std::{coin, coins};
+use cw_multi_test::{error::AnyResult, AppResponse};
+
+use orbital_core::{
+    state::{ClearingAccountStatus, CLEARING_ACCOUNTS},
+    utils::get_ica_identifier,
+};
+
+use crate::{
+    testing_utils::consts::{
+        DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, ICA_CHANNEL, ICA_REGISTER_FEE,
+        ICQ_DEPOSIT, OSMOSIS_DOMAIN, SOLVER_1, USER_1,
+    },
+    tests::test_orbital_core::suite::{get_attribute, setup_user_on_domains, Suite},
+};
+
+fn withdraw(suite: &mut Suite) -> AnyResult<AppResponse> {
+    suite.withdraw_funds(
+        USER_1,
+        GAIA_DOMAIN,
+        coin(100, DENOM_ATOM),
+        "cosmos1dest",
+        &coins(20_000, DENOM_NTRN),
+    )
+}
+
+/// builds a suite in which the gaia clearing account of USER_1 was
+/// closed after its first withdrawal tx timed out
+fn setup_closed_clearing_account() -> Suite {
+    let mut suite = setup_user_on_domains();
+    withdraw(&mut suite).unwrap();
+    suite.ica_timeout(1).unwrap();
+    suite
+}
+
+#[test]
+fn test_timeout_closes_clearing_account() {
+    let mut suite = setup_closed_clearing_account();
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Closed);
+
+    // clearing accounts on other domains are unaffected
+    let clearing_account = suite
+        .query_clearing_account(OSMOSIS_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Open);
+}
+
+#[test]
+#[should_panic(expected = "Clearing account closed on domain: gaia")]
+fn test_withdraw_validates_open_clearing_account() {
+    let mut suite = setup_closed_clearing_account();
+
+    withdraw(&mut suite).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Clearing account closed on domain: gaia")]
+fn test_submit_intent_validates_open_input_clearing_account() {
+    let mut suite = setup_closed_clearing_account();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(100, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Clearing account not closed on domain: gaia")]
+fn test_reopen_validates_closed_clearing_account() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .reopen_clearing_account(USER_1, GAIA_DOMAIN, coins(1_000_000, DENOM_NTRN))
+        .unwrap();
+}
+
+#[test]
+fn test_reopen_restores_same_clearing_account() {
+    let mut suite = setup_closed_clearing_account();
+    let closed_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+
+    suite
+        .reopen_clearing_account(USER_1, GAIA_DOMAIN, coins(1_000_000, DENOM_NTRN))
+        .unwrap();
+
+    let reopened_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(reopened_account.status, ClearingAccountStatus::Open);
+    assert_eq!(reopened_account.addr, closed_account.addr);
+
+    // txs can be submitted through the reopened account again
+    withdraw(&mut suite).unwrap();
+}
+
+#[test]
+fn test_registration_failure_rolls_back_clearing_account() {
+    let mut suite = setup_user_on_domains();
+    suite.register_user(SOLVER_1).unwrap();
+    suite.set_ica_failure(Some("connection not found"));
+
+    let response = suite
+        .register_user_to_new_domain(
+            SOLVER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+    assert!(get_attribute(&response, "error").contains("connection not found"));
+
+    assert!(suite.query_clearing_account(GAIA_DOMAIN, SOLVER_1).is_err());
+    assert!(suite
+        .query_user(SOLVER_1)
+        .unwrap()
+        .registered_domains
+        .is_empty());
+
+    // the user can register to the domain again
+    suite.set_ica_failure(None);
+    let response = suite
+        .register_user_to_new_domain(
+            SOLVER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+    assert_eq!(get_attribute(&response, "channel"), ICA_CHANNEL);
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, SOLVER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Open);
+    assert_eq!(
+        suite.query_user(SOLVER_1).unwrap().registered_domains,
+        vec![GAIA_DOMAIN.to_string()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "User already registered to domain: gaia")]
+fn test_register_to_registered_domain() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_reopen_failure_keeps_clearing_account_closed() {
+    let mut suite = setup_closed_clearing_account();
+    suite.set_ica_failure(Some("connection not found"));
+
+    suite
+        .reopen_clearing_account(USER_1, GAIA_DOMAIN, coins(1_000_000, DENOM_NTRN))
+        .unwrap();
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Closed);
+    assert!(suite
+        .query_user(USER_1)
+        .unwrap()
+        .registered_domains
+        .contains(&GAIA_DOMAIN.to_string()));
+}
+
+#[test]
+fn test_clearing_account_stored_without_status_is_open() {
+    let mut suite = setup_user_on_domains();
+    let ica_identifier = get_ica_identifier(
+        suite.query_user(USER_1).unwrap().id,
+        GAIA_DOMAIN.to_string(),
+    );
+
+    // clearing account as stored before its status was tracked
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    storage.set(
+        &CLEARING_ACCOUNTS.key(ica_identifier),
+        br#"{"addr":"cosmos1legacy","controller_connection_id":"connection-0"}"#,
+    );
+    drop(storage);
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.addr, "cosmos1legacy");
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Open);
+
+    // txs can be submitted through the account
+    withdraw(&mut suite).unwrap();
+}
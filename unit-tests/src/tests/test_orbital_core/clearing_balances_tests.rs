@@ -0,0 +1,188 @@
+use cosmwasm_std::{coin, coins, Uint128};
+
+use orbital_core::state::ClearingBalance;
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, ICA_REGISTER_FEE, ICQ_DEPOSIT, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{setup_monitored_domain, Suite},
+};
+
+/// builds a suite in which the gaia clearing account of USER_1 is monitored
+/// for uatom and uosmo by the balances query registered when it opened.
+/// returns the suite along with the clearing account and the query id.
+fn setup_monitored_clearing_account() -> (Suite, String, u64) {
+    let mut suite = setup_monitored_domain(vec![DENOM_ATOM.to_string(), DENOM_OSMO.to_string()]);
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + 2 * ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let query_id = suite
+        .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .balances_query_id
+        .unwrap();
+
+    (suite, clearing_account, query_id)
+}
+
+/// builds a suite in which USER_1 holds a gaia clearing account which is
+/// not monitored by any balances query. returns the suite along with the
+/// clearing account.
+fn setup_unmonitored_clearing_account() -> (Suite, String) {
+    let mut suite = setup_monitored_domain(vec![]);
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+
+    (suite, clearing_account)
+}
+
+#[test]
+fn test_kv_result_updates_clearing_balances() {
+    let (mut suite, clearing_account, query_id) = setup_monitored_clearing_account();
+    let height = suite.app.block_info().height;
+
+    suite.set_query_balances(
+        query_id,
+        &clearing_account,
+        vec![coin(1_000, DENOM_ATOM), coin(0, DENOM_OSMO)],
+    );
+
+    assert_eq!(
+        suite.query_user_balances(GAIA_DOMAIN, USER_1).unwrap(),
+        vec![
+            (
+                DENOM_ATOM.to_string(),
+                ClearingBalance {
+                    amount: Uint128::new(1_000),
+                    last_updated_height: height,
+                }
+            ),
+            (
+                DENOM_OSMO.to_string(),
+                ClearingBalance {
+                    amount: Uint128::zero(),
+                    last_updated_height: height,
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_kv_result_replaces_snapshot() {
+    let (mut suite, clearing_account, query_id) = setup_monitored_clearing_account();
+    suite.set_query_balances(
+        query_id,
+        &clearing_account,
+        vec![coin(1_000, DENOM_ATOM), coin(500, DENOM_OSMO)],
+    );
+
+    suite.advance_time(50);
+    suite.set_query_balances(query_id, &clearing_account, coins(700, DENOM_ATOM));
+
+    assert_eq!(
+        suite.query_user_balances(GAIA_DOMAIN, USER_1).unwrap(),
+        vec![(
+            DENOM_ATOM.to_string(),
+            ClearingBalance {
+                amount: Uint128::new(700),
+                last_updated_height: suite.app.block_info().height,
+            }
+        )]
+    );
+}
+
+#[test]
+fn test_user_registered_query_updates_clearing_balances() {
+    let (mut suite, clearing_account) = setup_unmonitored_clearing_account();
+    let user = make_addr(&suite.app, USER_1);
+
+    let query_id = suite
+        .register_balances_query_as(
+            &user,
+            GAIA_DOMAIN,
+            &clearing_account,
+            vec![DENOM_ATOM.to_string()],
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+    suite.set_query_balances(query_id, &clearing_account, coins(2_000, DENOM_ATOM));
+
+    let balances = suite.query_user_balances(GAIA_DOMAIN, USER_1).unwrap();
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].1.amount, Uint128::new(2_000));
+}
+
+#[test]
+fn test_owner_registered_query_is_not_tracked() {
+    let (mut suite, clearing_account) = setup_unmonitored_clearing_account();
+
+    let query_id = suite
+        .register_balances_query(GAIA_DOMAIN, &clearing_account, vec![DENOM_ATOM.to_string()])
+        .unwrap();
+    suite.set_query_balances(query_id, &clearing_account, coins(2_000, DENOM_ATOM));
+
+    assert!(suite
+        .query_user_balances(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_kv_result_keeps_balances_of_other_queries() {
+    let (mut suite, clearing_account) = setup_unmonitored_clearing_account();
+    let user = make_addr(&suite.app, USER_1);
+    let mut register_query = |denom: &str| {
+        suite
+            .register_balances_query_as(
+                &user,
+                GAIA_DOMAIN,
+                &clearing_account,
+                vec![denom.to_string()],
+                &coins(ICQ_DEPOSIT, DENOM_NTRN),
+            )
+            .unwrap()
+    };
+    let atom_query_id = register_query(DENOM_ATOM);
+    let osmo_query_id = register_query(DENOM_OSMO);
+
+    suite.set_query_balances(osmo_query_id, &clearing_account, coins(500, DENOM_OSMO));
+    suite.set_query_balances(atom_query_id, &clearing_account, coins(2_000, DENOM_ATOM));
+
+    let balances = suite.query_user_balances(GAIA_DOMAIN, USER_1).unwrap();
+    assert_eq!(
+        balances
+            .into_iter()
+            .map(|(denom, balance)| (denom, balance.amount))
+            .collect::<Vec<_>>(),
+        vec![
+            (DENOM_ATOM.to_string(), Uint128::new(2_000)),
+            (DENOM_OSMO.to_string(), Uint128::new(500)),
+        ]
+    );
+}
@@ -0,0 +1,231 @@
+use cosmwasm_std::{coin, coins, Uint128};
+
+use orbital_core::state::TRANSFERS_QUERIES;
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, ICA_REGISTER_FEE, ICQ_DEPOSIT,
+            OSMOSIS_DOMAIN, SOLVER_1, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{get_attribute, setup_user_on_domains, Suite},
+};
+
+/// returns the gaia clearing account of USER_1 along with the id of
+/// the transfers query registered for it when it opened
+fn setup_user_transfers_query(suite: &mut Suite) -> (String, u64) {
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let query_id = suite
+        .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .transfers_query_id
+        .unwrap();
+
+    (clearing_account, query_id)
+}
+
+#[test]
+fn test_deposits_are_credited_to_user_domain() {
+    let mut suite = setup_user_on_domains();
+    let (clearing_account, query_id) = setup_user_transfers_query(&mut suite);
+
+    let response = suite
+        .observe_transfer(
+            query_id,
+            "cosmos1sender",
+            &clearing_account,
+            coin(1_000, DENOM_ATOM),
+            42,
+        )
+        .unwrap();
+    assert_eq!(
+        get_attribute(&response, "user"),
+        make_addr(&suite.app, USER_1).to_string()
+    );
+    assert_eq!(get_attribute(&response, "domain"), GAIA_DOMAIN);
+
+    suite
+        .observe_transfer(
+            query_id,
+            "cosmos1sender",
+            &clearing_account,
+            coin(500, DENOM_ATOM),
+            43,
+        )
+        .unwrap();
+    suite
+        .observe_transfer(
+            query_id,
+            "cosmos1sender",
+            &clearing_account,
+            coin(200, DENOM_OSMO),
+            43,
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_user_deposits(GAIA_DOMAIN, USER_1).unwrap(),
+        vec![
+            (DENOM_ATOM.to_string(), Uint128::new(1_500)),
+            (DENOM_OSMO.to_string(), Uint128::new(200)),
+        ]
+    );
+    assert!(suite
+        .query_user_deposits(OSMOSIS_DOMAIN, USER_1)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_replayed_tx_is_skipped() {
+    let mut suite = setup_user_on_domains();
+    let (clearing_account, query_id) = setup_user_transfers_query(&mut suite);
+
+    suite
+        .observe_transfer(
+            query_id,
+            "cosmos1sender",
+            &clearing_account,
+            coin(1_000, DENOM_ATOM),
+            42,
+        )
+        .unwrap();
+    let response = suite
+        .observe_transfer(
+            query_id,
+            "cosmos1sender",
+            &clearing_account,
+            coin(1_000, DENOM_ATOM),
+            42,
+        )
+        .unwrap();
+    assert_eq!(get_attribute(&response, "skipped"), "already_processed");
+
+    assert_eq!(
+        suite.query_user_deposits(GAIA_DOMAIN, USER_1).unwrap(),
+        vec![(DENOM_ATOM.to_string(), Uint128::new(1_000))]
+    );
+}
+
+#[test]
+fn test_tx_with_two_recipients_is_credited_to_both() {
+    let mut suite = setup_user_on_domains();
+    let (user_account, user_query_id) = setup_user_transfers_query(&mut suite);
+    suite.register_user(SOLVER_1).unwrap();
+    suite
+        .register_user_to_new_domain(
+            SOLVER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+    let solver_account = suite
+        .query_clearing_account(GAIA_DOMAIN, SOLVER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let solver_query_id = suite
+        .query_clearing_account_queries(GAIA_DOMAIN, SOLVER_1)
+        .unwrap()
+        .transfers_query_id
+        .unwrap();
+
+    // both queries observe the same tx
+    for query_id in [user_query_id, solver_query_id] {
+        suite
+            .observe_transfers(
+                query_id,
+                "cosmos1sender",
+                vec![
+                    (user_account.as_str(), coin(1_000, DENOM_ATOM)),
+                    (solver_account.as_str(), coin(300, DENOM_ATOM)),
+                ],
+                42,
+            )
+            .unwrap();
+    }
+
+    assert_eq!(
+        suite.query_user_deposits(GAIA_DOMAIN, USER_1).unwrap(),
+        vec![(DENOM_ATOM.to_string(), Uint128::new(1_000))]
+    );
+    assert_eq!(
+        suite.query_user_deposits(GAIA_DOMAIN, SOLVER_1).unwrap(),
+        vec![(DENOM_ATOM.to_string(), Uint128::new(300))]
+    );
+}
+
+#[test]
+fn test_identical_tx_at_other_height_is_credited() {
+    let mut suite = setup_user_on_domains();
+    let (clearing_account, query_id) = setup_user_transfers_query(&mut suite);
+
+    for height in [42, 43] {
+        suite
+            .observe_transfer(
+                query_id,
+                "cosmos1sender",
+                &clearing_account,
+                coin(1_000, DENOM_ATOM),
+                height,
+            )
+            .unwrap();
+    }
+
+    assert_eq!(
+        suite.query_user_deposits(GAIA_DOMAIN, USER_1).unwrap(),
+        vec![(DENOM_ATOM.to_string(), Uint128::new(2_000))]
+    );
+}
+
+#[test]
+fn test_tx_observed_by_two_queries_is_credited_once() {
+    let mut suite = setup_user_on_domains();
+    let (clearing_account, query_id) = setup_user_transfers_query(&mut suite);
+    let user = make_addr(&suite.app, USER_1);
+
+    // a query registered before transfers queries were indexed by recipient
+    // lets a second query monitor the clearing account
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    TRANSFERS_QUERIES.remove(storage.as_mut(), clearing_account.to_string());
+    drop(storage);
+    let other_query_id = suite
+        .register_transfers_query_as(
+            &user,
+            GAIA_DOMAIN,
+            &clearing_account,
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    suite
+        .observe_transfer(
+            query_id,
+            "cosmos1sender",
+            &clearing_account,
+            coin(1_000, DENOM_ATOM),
+            42,
+        )
+        .unwrap();
+    let response = suite
+        .observe_transfer(
+            other_query_id,
+            "cosmos1sender",
+            &clearing_account,
+            coin(1_000, DENOM_ATOM),
+            42,
+        )
+        .unwrap();
+    assert_eq!(get_attribute(&response, "skipped"), "already_processed");
+
+    assert_eq!(
+        suite.query_user_deposits(GAIA_DOMAIN, USER_1).unwrap(),
+        vec![(DENOM_ATOM.to_string(), Uint128::new(1_000))]
+    );
+}
@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{coin, coins, Uint64};
+use cw_multi_test::{error::AnyResult, AppResponse, Executor};
+
+use orbital_core::{
+    msg::ExecuteMsg,
+    orbital_domain::UncheckedOrbitalDomainConfig,
+    state::{IcaTxStatus, OrbitalDomainConfig},
+};
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, ICA_REGISTER_FEE, ICQ_DEPOSIT,
+            OSMOSIS_DOMAIN, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{
+        register_ica_domains, setup_user_on_domains, OrbitalCoreBuilder, Suite,
+    },
+};
+
+fn gaia_ica_config(timeout: u64) -> UncheckedOrbitalDomainConfig {
+    UncheckedOrbitalDomainConfig::InterchainAccount {
+        connection_id: "connection-gaia-2".to_string(),
+        channel_id: "channel-gaia-2".to_string(),
+        timeout: Uint64::new(timeout),
+        transfer_channels: BTreeMap::new(),
+        monitored_denoms: vec![],
+    }
+}
+
+fn withdraw(suite: &mut Suite) -> AnyResult<AppResponse> {
+    suite.withdraw_funds(
+        USER_1,
+        GAIA_DOMAIN,
+        coin(100, DENOM_ATOM),
+        "cosmos1dest",
+        &coins(20_000, DENOM_NTRN),
+    )
+}
+
+#[test]
+fn test_update_domain_happy() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .update_domain(GAIA_DOMAIN, gaia_ica_config(200))
+        .unwrap();
+
+    assert_eq!(
+        suite.query_domain(GAIA_DOMAIN).unwrap(),
+        OrbitalDomainConfig::InterchainAccount {
+            connection_id: "connection-gaia-2".to_string(),
+            channel_id: "channel-gaia-2".to_string(),
+            timeout: Uint64::new(200),
+            transfer_channels: BTreeMap::new(),
+            monitored_denoms: vec![],
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unknown domain: juno")]
+fn test_update_domain_validates_domain_existance() {
+    let mut suite = setup_user_on_domains();
+
+    suite.update_domain("juno", gaia_ica_config(100)).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Domain account type can not be changed: gaia")]
+fn test_update_domain_validates_account_type() {
+    let mut suite = setup_user_on_domains();
+    let note = suite.note.to_string();
+
+    suite
+        .update_domain(
+            GAIA_DOMAIN,
+            UncheckedOrbitalDomainConfig::Polytone {
+                note,
+                timeout: Uint64::new(100),
+                connection_id: None,
+                transfer_channels: BTreeMap::new(),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "timeout must be non-zero")]
+fn test_update_domain_validates_config() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .update_domain(GAIA_DOMAIN, gaia_ica_config(0))
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the contract's current owner")]
+fn test_update_domain_validates_owner() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+
+    suite
+        .app
+        .execute_contract(
+            user,
+            suite.orbital_core.clone(),
+            &ExecuteMsg::UpdateDomain {
+                domain: GAIA_DOMAIN.to_string(),
+                account_type: gaia_ica_config(100),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_pause_and_unpause_domain() {
+    let mut suite = setup_user_on_domains();
+    assert!(!suite.query_domain_paused(GAIA_DOMAIN).unwrap());
+
+    suite.pause_domain(GAIA_DOMAIN).unwrap();
+    assert!(suite.query_domain_paused(GAIA_DOMAIN).unwrap());
+    assert!(!suite.query_domain_paused(OSMOSIS_DOMAIN).unwrap());
+
+    suite.unpause_domain(GAIA_DOMAIN).unwrap();
+    assert!(!suite.query_domain_paused(GAIA_DOMAIN).unwrap());
+
+    // withdrawals resume once the domain is unpaused
+    withdraw(&mut suite).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Domain paused: gaia")]
+fn test_pause_domain_validates_not_paused() {
+    let mut suite = setup_user_on_domains();
+
+    suite.pause_domain(GAIA_DOMAIN).unwrap();
+    suite.pause_domain(GAIA_DOMAIN).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown domain: juno")]
+fn test_pause_domain_validates_domain_existance() {
+    let mut suite = setup_user_on_domains();
+
+    suite.pause_domain("juno").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the contract's current owner")]
+fn test_pause_domain_validates_owner() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+
+    suite
+        .app
+        .execute_contract(
+            user,
+            suite.orbital_core.clone(),
+            &ExecuteMsg::PauseDomain {
+                domain: GAIA_DOMAIN.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Domain not paused: gaia")]
+fn test_unpause_domain_validates_paused() {
+    let mut suite = setup_user_on_domains();
+
+    suite.unpause_domain(GAIA_DOMAIN).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Domain paused: gaia")]
+fn test_paused_domain_rejects_user_registration() {
+    let mut suite = setup_user_on_domains();
+    suite.pause_domain(GAIA_DOMAIN).unwrap();
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Domain paused: gaia")]
+fn test_paused_domain_rejects_withdrawals() {
+    let mut suite = setup_user_on_domains();
+    suite.pause_domain(GAIA_DOMAIN).unwrap();
+
+    withdraw(&mut suite).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Domain paused: osmosis")]
+fn test_paused_domain_rejects_intents() {
+    let mut suite = setup_user_on_domains();
+    suite.pause_domain(OSMOSIS_DOMAIN).unwrap();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(100, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_paused_domain_processes_ica_callbacks() {
+    let mut suite = setup_user_on_domains();
+    withdraw(&mut suite).unwrap();
+    suite.pause_domain(GAIA_DOMAIN).unwrap();
+
+    suite.ica_response(1, b"ack").unwrap();
+
+    assert_eq!(
+        suite.query_ica_tx(0).unwrap().status,
+        IcaTxStatus::Succeeded
+    );
+}
+
+/// builds a suite with gaia and osmosis registered as ICA domains, on which
+/// no user holds a clearing account yet
+fn setup_unused_domains() -> Suite {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    register_ica_domains(&mut suite);
+    suite.register_user(USER_1).unwrap();
+
+    suite
+}
+
+#[test]
+fn test_deregister_domain() {
+    let mut suite = setup_unused_domains();
+    suite.pause_domain(GAIA_DOMAIN).unwrap();
+
+    suite.deregister_domain(GAIA_DOMAIN).unwrap();
+
+    assert!(suite.query_domain(GAIA_DOMAIN).is_err());
+    assert!(!suite.query_domain_paused(GAIA_DOMAIN).unwrap());
+    // other domains remain registered
+    suite.query_domain(OSMOSIS_DOMAIN).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown domain: gaia")]
+fn test_deregistered_domain_rejects_registrations() {
+    let mut suite = setup_unused_domains();
+    suite.deregister_domain(GAIA_DOMAIN).unwrap();
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Domain in use: gaia")]
+fn test_deregister_domain_with_clearing_accounts() {
+    let mut suite = setup_user_on_domains();
+
+    suite.deregister_domain(GAIA_DOMAIN).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown domain: juno")]
+fn test_deregister_domain_validates_domain_existance() {
+    let mut suite = setup_user_on_domains();
+
+    suite.deregister_domain("juno").unwrap();
+}
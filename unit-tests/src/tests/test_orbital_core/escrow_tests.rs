@@ -0,0 +1,378 @@
+use cosmwasm_std::{coin, coins, Uint128};
+use cw_utils::Expiration;
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, ICA_REGISTER_FEE, ICQ_DEPOSIT,
+            OSMOSIS_DOMAIN, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{
+        register_ica_domains, setup_open_auction, setup_user_on_domains, OrbitalCoreBuilder, Suite,
+    },
+};
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 0uatom")]
+fn test_submit_intent_requires_reported_balance() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    register_ica_domains(&mut suite);
+    suite.register_user(USER_1).unwrap();
+    for domain in [GAIA_DOMAIN, OSMOSIS_DOMAIN] {
+        suite
+            .register_user_to_new_domain(
+                USER_1,
+                domain,
+                coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+            )
+            .unwrap();
+    }
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_submit_intent_locks_input() {
+    let mut suite = setup_open_auction();
+
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        suite.query_locked_balance(USER_1, OSMOSIS_DOMAIN, DENOM_OSMO),
+        Uint128::zero()
+    );
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 999900uatom")]
+fn test_submit_intent_rejects_locked_funds() {
+    let mut suite = setup_open_auction();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(999_901, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 999900uatom")]
+fn test_withdraw_rejects_locked_funds() {
+    let mut suite = setup_open_auction();
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(999_901, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 999900uatom")]
+fn test_batch_withdraw_rejects_locked_funds() {
+    let mut suite = setup_open_auction();
+
+    // each withdrawal is covered on its own, but not the batch as a whole
+    suite
+        .batch_withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            vec![
+                (coin(500_000, DENOM_ATOM), "cosmos1dest"),
+                (coin(499_901, DENOM_ATOM), "cosmos1other"),
+            ],
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_withdraw_available_funds() {
+    let mut suite = setup_open_auction();
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(999_900, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_withdraw_unmonitored_denom() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(500, DENOM_OSMO),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 0uosmo")]
+fn test_withdraw_monitored_denom_requires_reported_balance() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    suite
+        .register_balances_query_as(
+            &user,
+            GAIA_DOMAIN,
+            &clearing_account,
+            vec![DENOM_OSMO.to_string()],
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(500, DENOM_OSMO),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_cancel_intent_unlocks_input() {
+    let mut suite = setup_open_auction();
+
+    suite.cancel_intent(USER_1, 0, None, &[]).unwrap();
+
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_expired_intent_unlocks_input() {
+    let mut suite = setup_user_on_domains();
+    let deadline = Expiration::AtTime(suite.app.block_info().time.plus_seconds(100));
+    suite
+        .submit_intent_with_deadline(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(500_000, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+            Some(deadline),
+        )
+        .unwrap();
+
+    suite.advance_time(100);
+
+    // expiry is persisted and the input released as part of the withdrawal
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(1_000_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::zero()
+    );
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 400000uatom")]
+fn test_withdraw_rejects_pending_outflows() {
+    let mut suite = setup_user_on_domains();
+
+    // balances query keeps reporting the funds until the first withdrawal executed
+    for _ in 0..2 {
+        suite
+            .withdraw_funds(
+                USER_1,
+                GAIA_DOMAIN,
+                coin(600_000, DENOM_ATOM),
+                "cosmos1dest",
+                &coins(20_000, DENOM_NTRN),
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 400000uatom")]
+fn test_submit_intent_rejects_pending_outflows() {
+    let mut suite = setup_user_on_domains();
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(600_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(400_001, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_failed_withdrawal_releases_pending_outflows() {
+    let mut suite = setup_user_on_domains();
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(600_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    suite.ica_error(1, "insufficient funds").unwrap();
+
+    // funds never left the clearing account
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(1_000_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+/// withdraws 600_000uatom out of the gaia clearing account of USER_1, which
+/// executes on gaia. returns the id of the balances query of the account.
+fn execute_withdrawal(suite: &mut Suite) -> u64 {
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(600_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+    suite.ica_response(1, b"ack").unwrap();
+
+    suite
+        .query_user_icqs(USER_1)
+        .unwrap()
+        .into_iter()
+        .find(|(_, registration)| registration.denoms == vec![DENOM_ATOM.to_string()])
+        .unwrap()
+        .0
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 400000uatom")]
+fn test_executed_withdrawal_holds_outflows_until_next_snapshot() {
+    let mut suite = setup_user_on_domains();
+    execute_withdrawal(&mut suite);
+
+    // balances query still reports the funds taken out
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(600_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_snapshot_after_settlement_releases_outflows() {
+    let mut suite = setup_user_on_domains();
+    let query_id = execute_withdrawal(&mut suite);
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+
+    // a snapshot in the block of the settlement may predate the tx
+    suite.set_query_balances(query_id, &clearing_account, coins(1_000_000, DENOM_ATOM));
+    suite.advance_time(10);
+    suite.set_query_balances(query_id, &clearing_account, coins(400_000, DENOM_ATOM));
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(400_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_failed_submission_releases_pending_outflows() {
+    let mut suite = setup_user_on_domains();
+    suite.set_ica_failure(Some("channel is closed"));
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(600_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+    suite.set_ica_failure(None);
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(1_000_000, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
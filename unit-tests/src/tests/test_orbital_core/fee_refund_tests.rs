@@ -0,0 +1,138 @@
+use cosmwasm_std::{coin, coins, Uint128};
+
+use orbital_core::utils::NEUTRON_DOMAIN;
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::{make_addr, BaseSuite},
+        consts::{DENOM_ATOM, DENOM_NTRN, GAIA_DOMAIN, USER_1},
+    },
+    tests::test_orbital_core::suite::{setup_user_on_domains, Suite},
+};
+
+/// flattened min IBC fee of the mocked neutron module,
+/// made up of an ack fee and a timeout fee of 10_000 untrn each
+const IBC_FEE: u128 = 20_000;
+const ACK_FEE: u128 = 10_000;
+const TIMEOUT_FEE: u128 = 10_000;
+
+fn query_user_ntrn(suite: &Suite) -> Uint128 {
+    suite
+        .query_balance(&make_addr(&suite.app, USER_1), DENOM_NTRN)
+        .amount
+}
+
+/// withdraws funds of USER_1 from gaia while paying the given fee deposit,
+/// returning the untrn balance of the user before the withdrawal
+fn withdraw_with_fee(suite: &mut Suite, fee: u128) -> Uint128 {
+    let pre_withdraw_balance = query_user_ntrn(suite);
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(fee, DENOM_NTRN),
+        )
+        .unwrap();
+
+    pre_withdraw_balance
+}
+
+#[test]
+fn test_fee_payer_is_recorded() {
+    let mut suite = setup_user_on_domains();
+    withdraw_with_fee(&mut suite, IBC_FEE);
+
+    assert_eq!(
+        suite.query_ica_tx(0).unwrap().fee_payer,
+        Some(make_addr(&suite.app, USER_1))
+    );
+}
+
+#[test]
+fn test_excess_fee_is_refunded() {
+    let mut suite = setup_user_on_domains();
+
+    let pre_withdraw_balance = withdraw_with_fee(&mut suite, IBC_FEE + 5_000);
+
+    assert_eq!(
+        query_user_ntrn(&suite),
+        pre_withdraw_balance - Uint128::new(IBC_FEE)
+    );
+}
+
+#[test]
+fn test_excess_transfer_fee_is_refunded() {
+    let mut suite = setup_user_on_domains();
+    let pre_transfer_balance = query_user_ntrn(&suite);
+
+    suite
+        .transfer_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            NEUTRON_DOMAIN,
+            "dest_addr",
+            None,
+            None,
+            &coins(IBC_FEE + 5_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert_eq!(
+        query_user_ntrn(&suite),
+        pre_transfer_balance - Uint128::new(IBC_FEE)
+    );
+}
+
+#[test]
+fn test_response_refunds_timeout_fee() {
+    let mut suite = setup_user_on_domains();
+    let pre_withdraw_balance = withdraw_with_fee(&mut suite, IBC_FEE);
+
+    suite.ica_response(1, b"ack").unwrap();
+
+    assert_eq!(
+        query_user_ntrn(&suite),
+        pre_withdraw_balance - Uint128::new(ACK_FEE)
+    );
+}
+
+#[test]
+fn test_error_refunds_timeout_fee() {
+    let mut suite = setup_user_on_domains();
+    let pre_withdraw_balance = withdraw_with_fee(&mut suite, IBC_FEE);
+
+    suite.ica_error(1, "insufficient funds").unwrap();
+
+    assert_eq!(
+        query_user_ntrn(&suite),
+        pre_withdraw_balance - Uint128::new(ACK_FEE)
+    );
+}
+
+#[test]
+fn test_timeout_refunds_ack_fee() {
+    let mut suite = setup_user_on_domains();
+    let pre_withdraw_balance = withdraw_with_fee(&mut suite, IBC_FEE);
+
+    suite.ica_timeout(1).unwrap();
+
+    assert_eq!(
+        query_user_ntrn(&suite),
+        pre_withdraw_balance - Uint128::new(TIMEOUT_FEE)
+    );
+}
+
+#[test]
+fn test_submit_failure_refunds_fee() {
+    let mut suite = setup_user_on_domains();
+    suite.set_ica_failure(Some("channel closed"));
+
+    let pre_withdraw_balance = withdraw_with_fee(&mut suite, IBC_FEE);
+
+    // fees of txs which never left neutron are refunded in full
+    assert_eq!(query_user_ntrn(&suite), pre_withdraw_balance);
+}
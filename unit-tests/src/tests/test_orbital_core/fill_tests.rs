@@ -0,0 +1,281 @@
+use cosmwasm_std::{coin, Uint128};
+
+use orbital_core::state::{IntentStatus, Payout, PayoutStatus};
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{
+            DENOM_ATOM, DENOM_OSMO, GAIA_DOMAIN, OSMOSIS_DOMAIN, SOLVER_1, SOLVER_BOND_AMOUNT,
+            USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{setup_open_auction, setup_won_auction, Suite},
+};
+
+/// returns the id of the transfers query monitoring the osmosis clearing
+/// account of USER_1 along with the clearing account address
+fn destination_transfers_query(suite: &mut Suite) -> (u64, String) {
+    let recipient = suite
+        .query_clearing_account(OSMOSIS_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let query_id = suite.query_transfers_query_id(OSMOSIS_DOMAIN, USER_1);
+
+    (query_id, recipient)
+}
+
+#[test]
+fn test_fill_verified_from_transfer() {
+    let mut suite = setup_won_auction();
+    let (query_id, recipient) = destination_transfers_query(&mut suite);
+
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{SOLVER_1}"),
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_intent(0).unwrap().status,
+        IntentStatus::Filled {
+            solver: make_addr(&suite.app, SOLVER_1),
+            height: 42,
+        }
+    );
+
+    // solver is paid the input funds on gaia
+    assert_eq!(
+        suite.query_payout(0).unwrap(),
+        Payout {
+            recipient: format!("{GAIA_DOMAIN}_{SOLVER_1}"),
+            amount: coin(100, DENOM_ATOM),
+            status: PayoutStatus::Pending,
+            tx_id: Some(0),
+        }
+    );
+}
+
+#[test]
+fn test_fill_releases_reserved_bond() {
+    let mut suite = setup_won_auction();
+    let (query_id, recipient) = destination_transfers_query(&mut suite);
+    assert_eq!(
+        suite.query_solver(SOLVER_1).unwrap().reserved,
+        Uint128::new(SOLVER_BOND_AMOUNT)
+    );
+
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{SOLVER_1}"),
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_solver(SOLVER_1).unwrap().reserved,
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_fill_ignores_insufficient_amount() {
+    let mut suite = setup_won_auction();
+    let (query_id, recipient) = destination_transfers_query(&mut suite);
+
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{SOLVER_1}"),
+            &recipient,
+            coin(999, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Won);
+    assert!(suite.query_payout(0).is_err());
+}
+
+#[test]
+fn test_fill_ignores_other_denoms() {
+    let mut suite = setup_won_auction();
+    let (query_id, recipient) = destination_transfers_query(&mut suite);
+
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{SOLVER_1}"),
+            &recipient,
+            coin(1_000, DENOM_ATOM),
+            42,
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Won);
+}
+
+#[test]
+fn test_fill_ignores_other_recipients() {
+    let mut suite = setup_won_auction();
+    let input_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let query_id = suite.query_transfers_query_id(GAIA_DOMAIN, USER_1);
+
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{SOLVER_1}"),
+            &input_account,
+            coin(1_000, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Won);
+}
+
+#[test]
+fn test_fill_ignores_other_senders() {
+    let mut suite = setup_won_auction();
+    let (query_id, recipient) = destination_transfers_query(&mut suite);
+
+    // deposit of the intent owner into their own clearing account
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{USER_1}"),
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Won);
+}
+
+#[test]
+fn test_fill_ignores_txs_before_auction_close() {
+    let mut suite = setup_open_auction();
+    let (query_id, recipient) = destination_transfers_query(&mut suite);
+    let solver_addr = format!("{OSMOSIS_DOMAIN}_{SOLVER_1}");
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+
+    // osmosis is observed at height 50 while the auction is running
+    suite
+        .observe_transfer(
+            query_id,
+            "osmo_sender",
+            &recipient,
+            coin(10, DENOM_OSMO),
+            50,
+        )
+        .unwrap();
+    suite.advance_time(300);
+    suite.close_auction(0).unwrap();
+    assert_eq!(suite.query_auction(0).unwrap().min_fill_height, 50);
+
+    // transfer of the solver executed before the auction closed
+    suite
+        .observe_transfer(
+            query_id,
+            &solver_addr,
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            50,
+        )
+        .unwrap();
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Won);
+
+    suite
+        .observe_transfer(
+            query_id,
+            &solver_addr,
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            51,
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_intent(0).unwrap().status,
+        IntentStatus::Filled {
+            solver: make_addr(&suite.app, SOLVER_1),
+            height: 51,
+        }
+    );
+}
+
+#[test]
+fn test_fill_accepted_from_address_at_auction_close() {
+    let mut suite = setup_won_auction();
+    let (query_id, recipient) = destination_transfers_query(&mut suite);
+    let solver_addr = format!("{OSMOSIS_DOMAIN}_{SOLVER_1}");
+    assert_eq!(
+        suite.query_auction(0).unwrap().fill_sender,
+        Some(solver_addr.to_string())
+    );
+
+    // address changed after the close does not count for the auction
+    suite
+        .set_solver_domain_address(SOLVER_1, OSMOSIS_DOMAIN, "osmo_new")
+        .unwrap();
+    suite
+        .observe_transfer(
+            query_id,
+            "osmo_new",
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Won);
+
+    suite
+        .observe_transfer(
+            query_id,
+            &solver_addr,
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            43,
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_intent(0).unwrap().status,
+        IntentStatus::Filled {
+            solver: make_addr(&suite.app, SOLVER_1),
+            height: 43,
+        }
+    );
+}
+
+#[test]
+fn test_fill_ignores_txs_after_fill_deadline() {
+    let mut suite = setup_won_auction();
+    let (query_id, recipient) = destination_transfers_query(&mut suite);
+    suite.advance_time(601);
+
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{SOLVER_1}"),
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+
+    // intent remains open for slashing
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Won);
+    assert!(suite.query_payout(0).is_err());
+}
@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{coin, coins, Uint64};
+use proptest::prelude::*;
+
+use orbital_core::{
+    msg::MigrateMsg,
+    orbital_domain::UncheckedOrbitalDomainConfig,
+    state::{
+        ClearingAccountConfig, ClearingAccountStatus, UserConfig, CLEARING_ACCOUNTS,
+        LEGACY_ICA_IDENTIFIERS, USER_CONFIGS,
+    },
+    utils::{get_ica_identifier, validate_domain, MAX_DOMAIN_LENGTH},
+};
+
+use crate::{
+    testing_utils::consts::{
+        DENOM_ATOM, DENOM_NTRN, GAIA_DOMAIN, ICA_REGISTER_FEE, ICQ_DEPOSIT, OSMOSIS_DOMAIN, USER_1,
+    },
+    tests::test_orbital_core::suite::{
+        get_attribute, register_ica_domains, setup_user_on_domains, OrbitalCoreBuilder, Suite,
+    },
+};
+
+/// max length of an ICA identifier which keeps the ICA port id within IBC limits
+const MAX_ICA_IDENTIFIER_LENGTH: usize = 47;
+
+fn domain_strategy() -> impl Strategy<Value = String> {
+    "[a-z0-9_-]{1,26}"
+}
+
+proptest! {
+    #[test]
+    fn prop_ica_identifiers_are_unique(
+        (user_a, domain_a) in (any::<u64>(), domain_strategy()),
+        (user_b, domain_b) in (any::<u64>(), domain_strategy()),
+    ) {
+        prop_assume!(user_a != user_b || domain_a != domain_b);
+
+        prop_assert_ne!(
+            get_ica_identifier(Uint64::new(user_a), domain_a),
+            get_ica_identifier(Uint64::new(user_b), domain_b)
+        );
+    }
+
+    #[test]
+    fn prop_ica_identifiers_of_same_domain_are_unique(
+        domain in domain_strategy(),
+        user_a in any::<u64>(),
+        user_b in any::<u64>(),
+    ) {
+        prop_assume!(user_a != user_b);
+
+        prop_assert_ne!(
+            get_ica_identifier(Uint64::new(user_a), domain.to_string()),
+            get_ica_identifier(Uint64::new(user_b), domain)
+        );
+    }
+
+    #[test]
+    fn prop_valid_domains_fit_port_id(domain in domain_strategy(), user in any::<u64>()) {
+        prop_assert!(validate_domain(&domain).is_ok());
+
+        let ica_identifier = get_ica_identifier(Uint64::new(user), domain);
+        prop_assert!(ica_identifier.len() <= MAX_ICA_IDENTIFIER_LENGTH);
+        // the port id is split on `.` to recover the identifier
+        prop_assert!(!ica_identifier.contains('.'));
+    }
+
+    #[test]
+    fn prop_domains_with_invalid_characters_are_rejected(
+        prefix in "[a-z0-9_-]{0,12}",
+        invalid in "[A-Z./# ]",
+        suffix in "[a-z0-9_-]{0,12}",
+    ) {
+        let domain = format!("{prefix}{invalid}{suffix}");
+
+        prop_assert!(validate_domain(&domain).is_err());
+    }
+
+    #[test]
+    fn prop_long_domains_are_rejected(domain in "[a-z0-9_-]{27,64}") {
+        prop_assert!(validate_domain(&domain).is_err());
+    }
+}
+
+#[test]
+fn test_ica_identifier_legacy_collision() {
+    // both combinations used to map to `gaia10`
+    assert_ne!(
+        get_ica_identifier(Uint64::new(0), "gaia1".to_string()),
+        get_ica_identifier(Uint64::new(10), "gaia".to_string())
+    );
+}
+
+#[test]
+fn test_validate_domain_length_bounds() {
+    assert!(validate_domain("").is_err());
+    assert!(validate_domain(&"a".repeat(MAX_DOMAIN_LENGTH)).is_ok());
+    assert!(validate_domain(&"a".repeat(MAX_DOMAIN_LENGTH + 1)).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Invalid domain identifier: Gaia contains invalid characters")]
+fn test_register_domain_validates_identifier() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .register_new_domain(
+            "Gaia",
+            UncheckedOrbitalDomainConfig::InterchainAccount {
+                connection_id: "connection-gaia".to_string(),
+                channel_id: "channel-gaia".to_string(),
+                timeout: Uint64::new(100),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms: vec![],
+            },
+        )
+        .unwrap();
+}
+
+fn legacy_ica_identifier(user_id: Uint64, domain: &str) -> String {
+    format!("{domain}{user_id}")
+}
+
+/// moves the gaia clearing account of USER_1 to its legacy key, as if it
+/// was registered before the current identifier encoding
+fn setup_legacy_clearing_account() -> Suite {
+    let mut suite = setup_user_on_domains();
+    let user_id = suite.query_user(USER_1).unwrap().id;
+    let ica_identifier = get_ica_identifier(user_id, GAIA_DOMAIN.to_string());
+
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    let clearing_account = CLEARING_ACCOUNTS
+        .load(storage.as_ref(), ica_identifier.to_string())
+        .unwrap();
+    CLEARING_ACCOUNTS.remove(storage.as_mut(), ica_identifier);
+    CLEARING_ACCOUNTS
+        .save(
+            storage.as_mut(),
+            legacy_ica_identifier(user_id, GAIA_DOMAIN),
+            &clearing_account,
+        )
+        .unwrap();
+    drop(storage);
+
+    suite
+}
+
+fn query_raw_clearing_account(
+    suite: &Suite,
+    ica_identifier: String,
+) -> Option<Option<ClearingAccountConfig>> {
+    CLEARING_ACCOUNTS
+        .may_load(
+            suite.app.contract_storage(&suite.orbital_core).as_ref(),
+            ica_identifier,
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_migrate_legacy_clearing_account() {
+    let mut suite = setup_legacy_clearing_account();
+    assert!(suite.query_clearing_account(GAIA_DOMAIN, USER_1).is_err());
+
+    let response = suite.migrate(MigrateMsg::MigrateIcaIdentifiers {}).unwrap();
+    assert_eq!(get_attribute(&response, "migrated"), "1");
+    assert_eq!(get_attribute(&response, "collisions"), "");
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Open);
+
+    let user_id = suite.query_user(USER_1).unwrap().id;
+    assert_eq!(
+        query_raw_clearing_account(&suite, legacy_ica_identifier(user_id, GAIA_DOMAIN)),
+        None
+    );
+    // accounts already on the current encoding are left untouched
+    suite
+        .query_clearing_account(OSMOSIS_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+}
+
+#[test]
+fn test_migrated_clearing_account_reopens_on_legacy_port() {
+    let mut suite = setup_legacy_clearing_account();
+    suite.migrate(MigrateMsg::MigrateIcaIdentifiers {}).unwrap();
+
+    // close the clearing account by timing out a withdrawal
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+    suite.ica_timeout(1).unwrap();
+
+    // the open ack arrives on the legacy port and resolves to the migrated account
+    suite
+        .reopen_clearing_account(USER_1, GAIA_DOMAIN, coins(1_000_000, DENOM_NTRN))
+        .unwrap();
+
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Open);
+
+    let user_id = suite.query_user(USER_1).unwrap().id;
+    assert_eq!(
+        query_raw_clearing_account(&suite, legacy_ica_identifier(user_id, GAIA_DOMAIN)),
+        None
+    );
+}
+
+#[test]
+fn test_migrate_reports_legacy_collisions() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    // `gaia1` of user 0 and `gaia` of user 10 share the legacy identifier `gaia10`
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    for (user, id, domain) in [("user_a", 0, "gaia1"), ("user_b", 10, "gaia")] {
+        USER_CONFIGS
+            .save(
+                storage.as_mut(),
+                user.to_string(),
+                &UserConfig {
+                    id: Uint64::new(id),
+                    registered_domains: vec![domain.to_string()],
+                },
+            )
+            .unwrap();
+    }
+    CLEARING_ACCOUNTS
+        .save(
+            storage.as_mut(),
+            "gaia10".to_string(),
+            &Some(ClearingAccountConfig {
+                addr: "cosmos1clearing".to_string(),
+                controller_connection_id: "connection-gaia".to_string(),
+                status: ClearingAccountStatus::Open,
+            }),
+        )
+        .unwrap();
+    drop(storage);
+
+    let response = suite.migrate(MigrateMsg::MigrateIcaIdentifiers {}).unwrap();
+    assert_eq!(get_attribute(&response, "migrated"), "0");
+    assert_eq!(get_attribute(&response, "collisions"), "gaia10");
+
+    // the ambiguous account is left in place for manual resolution
+    assert!(query_raw_clearing_account(&suite, "gaia10".to_string()).is_some());
+    for (id, domain) in [(0, "gaia1"), (10, "gaia")] {
+        assert_eq!(
+            query_raw_clearing_account(
+                &suite,
+                get_ica_identifier(Uint64::new(id), domain.to_string())
+            ),
+            None
+        );
+    }
+}
+
+#[test]
+fn test_migrate_reports_collisions_with_future_identifiers() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    register_ica_domains(&mut suite);
+
+    // legacy `gaia_10` of user 10 on `gaia_` is the identifier user 10 would get on gaia
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    USER_CONFIGS
+        .save(
+            storage.as_mut(),
+            "user_a".to_string(),
+            &UserConfig {
+                id: Uint64::new(10),
+                registered_domains: vec!["gaia_".to_string()],
+            },
+        )
+        .unwrap();
+    CLEARING_ACCOUNTS
+        .save(
+            storage.as_mut(),
+            "gaia_10".to_string(),
+            &Some(ClearingAccountConfig {
+                addr: "cosmos1clearing".to_string(),
+                controller_connection_id: "connection-gaia".to_string(),
+                status: ClearingAccountStatus::Open,
+            }),
+        )
+        .unwrap();
+    drop(storage);
+
+    let response = suite.migrate(MigrateMsg::MigrateIcaIdentifiers {}).unwrap();
+    assert_eq!(get_attribute(&response, "migrated"), "0");
+    assert_eq!(get_attribute(&response, "collisions"), "gaia_10");
+}
+
+#[test]
+#[should_panic(expected = "ICA identifier already in use")]
+fn test_register_rejects_legacy_ica_identifier() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    register_ica_domains(&mut suite);
+    suite.register_user(USER_1).unwrap();
+    let user_id = suite.query_user(USER_1).unwrap().id;
+
+    // the migrated `gaia_` account of a user with the same id kept the
+    // identifier USER_1 would get on gaia as its port
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    LEGACY_ICA_IDENTIFIERS
+        .save(
+            storage.as_mut(),
+            get_ica_identifier(user_id, GAIA_DOMAIN.to_string()),
+            &get_ica_identifier(user_id, "gaia_".to_string()),
+        )
+        .unwrap();
+    drop(storage);
+
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
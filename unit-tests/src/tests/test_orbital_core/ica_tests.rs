@@ -0,0 +1,507 @@
+use cosmwasm_std::{coin, coins, Binary, Uint128};
+use cw_multi_test::{error::AnyResult, AppResponse};
+use neutron_sdk::bindings::msg::IbcFee;
+
+use orbital_core::state::{IcaTxKind, IcaTxStatus, IntentStatus, PayoutStatus};
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::{make_addr, BaseSuite},
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, ICA_CHANNEL, ICA_REGISTER_FEE,
+            ICA_TX_MAX_MESSAGES, MAX_TX_TIMEOUT, MIN_TX_TIMEOUT, OSMOSIS_DOMAIN, SOLVER_1,
+            SOLVER_2, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{setup_user_on_domains, setup_won_auction, Suite},
+};
+
+/// withdraws funds of USER_1 from gaia, submitting the first ICA tx
+fn setup_withdrawal() -> Suite {
+    let mut suite = setup_user_on_domains();
+    withdraw(&mut suite);
+    suite
+}
+
+fn withdraw(suite: &mut Suite) {
+    suite
+        .withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            "cosmos1dest",
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+/// verifies the fill of the won auction, submitting the payout ICA tx
+fn setup_payout() -> Suite {
+    let mut suite = setup_won_auction();
+    fill_won_auction(&mut suite);
+    suite
+}
+
+/// observes the transfer of the winning solver which fills intent 0
+fn fill_won_auction(suite: &mut Suite) {
+    let recipient = suite
+        .query_clearing_account(OSMOSIS_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let query_id = suite.query_transfers_query_id(OSMOSIS_DOMAIN, USER_1);
+
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{SOLVER_1}"),
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_submitted_tx_is_pending() {
+    let mut suite = setup_withdrawal();
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(ica_tx.domain, GAIA_DOMAIN);
+    assert_eq!(ica_tx.kind, IcaTxKind::Withdrawal);
+    assert_eq!(ica_tx.status, IcaTxStatus::Pending);
+    assert_eq!(ica_tx.channel, Some(ICA_CHANNEL.to_string()));
+    assert_eq!(ica_tx.sequence, Some(1));
+}
+
+#[test]
+fn test_ledger_records_submitted_tx() {
+    let mut suite = setup_withdrawal();
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(ica_tx.owner, make_addr(&suite.app, USER_1));
+    assert_eq!(
+        ica_tx.msgs,
+        vec!["/cosmos.bank.v1beta1.MsgSend".to_string()]
+    );
+    assert_eq!(
+        ica_tx.fee,
+        IbcFee {
+            recv_fee: vec![],
+            ack_fee: coins(10_000, DENOM_NTRN),
+            timeout_fee: coins(10_000, DENOM_NTRN),
+        }
+    );
+    assert_eq!(ica_tx.submitted_height, suite.app.block_info().height);
+    assert_eq!(ica_tx.ack, None);
+}
+
+#[test]
+fn test_batch_withdrawal_is_single_tx() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .batch_withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            vec![
+                (coin(100, DENOM_ATOM), "cosmos1dest"),
+                (coin(200, DENOM_ATOM), "cosmos1other"),
+                (coin(300, DENOM_ATOM), "cosmos1dest"),
+            ],
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(
+        ica_tx.msgs,
+        vec!["/cosmos.bank.v1beta1.MsgSend".to_string(); 3]
+    );
+    // the batch pays the ibc fee of a single tx
+    assert_eq!(ica_tx.fee.ack_fee, coins(10_000, DENOM_NTRN));
+    assert!(suite.query_ica_tx(1).is_err());
+}
+
+#[test]
+fn test_batch_withdrawal_up_to_max_messages() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .batch_withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            vec![(coin(1, DENOM_ATOM), "cosmos1dest"); ICA_TX_MAX_MESSAGES as usize],
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_ica_tx(0).unwrap().msgs.len(),
+        ICA_TX_MAX_MESSAGES as usize
+    );
+}
+
+#[test]
+#[should_panic(expected = "Withdrawal batch too large: 6 withdrawals, max 5")]
+fn test_batch_withdrawal_validates_max_messages() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .batch_withdraw_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            vec![(coin(1, DENOM_ATOM), "cosmos1dest"); ICA_TX_MAX_MESSAGES as usize + 1],
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Withdrawal batch is empty")]
+fn test_batch_withdrawal_validates_empty_batch() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .batch_withdraw_funds(USER_1, GAIA_DOMAIN, vec![], &coins(20_000, DENOM_NTRN))
+        .unwrap();
+}
+
+fn withdraw_with_options(
+    suite: &mut Suite,
+    timeout: Option<u64>,
+    memo: Option<&str>,
+) -> AnyResult<AppResponse> {
+    suite.withdraw_funds_with_options(
+        USER_1,
+        GAIA_DOMAIN,
+        vec![(coin(100, DENOM_ATOM), "cosmos1dest")],
+        timeout,
+        memo,
+        &coins(20_000, DENOM_NTRN),
+    )
+}
+
+#[test]
+fn test_withdrawal_uses_domain_timeout() {
+    let mut suite = setup_withdrawal();
+
+    // gaia is registered with a timeout of 100 seconds
+    assert_eq!(suite.query_submitted_tx_timeout(1), 100);
+    assert_eq!(suite.query_ica_tx(0).unwrap().memo, None);
+}
+
+#[test]
+fn test_withdrawal_with_timeout_and_memo() {
+    let mut suite = setup_user_on_domains();
+
+    withdraw_with_options(&mut suite, Some(MAX_TX_TIMEOUT), Some("order 42")).unwrap();
+
+    assert_eq!(suite.query_submitted_tx_timeout(1), MAX_TX_TIMEOUT);
+    assert_eq!(
+        suite.query_ica_tx(0).unwrap().memo,
+        Some("order 42".to_string())
+    );
+}
+
+#[test]
+#[should_panic(expected = "Tx timeout of 9 seconds outside of bounds [10, 3600]")]
+fn test_withdrawal_validates_min_timeout() {
+    let mut suite = setup_user_on_domains();
+
+    withdraw_with_options(&mut suite, Some(MIN_TX_TIMEOUT - 1), None).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Tx timeout of 3601 seconds outside of bounds [10, 3600]")]
+fn test_withdrawal_validates_max_timeout() {
+    let mut suite = setup_user_on_domains();
+
+    withdraw_with_options(&mut suite, Some(MAX_TX_TIMEOUT + 1), None).unwrap();
+}
+
+#[test]
+fn test_query_user_ica_txs_paginated() {
+    let mut suite = setup_withdrawal();
+    withdraw(&mut suite);
+    withdraw(&mut suite);
+
+    let first_page = suite.query_user_ica_txs(USER_1, None, Some(2)).unwrap();
+    assert_eq!(
+        first_page.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+
+    let second_page = suite.query_user_ica_txs(USER_1, Some(1), Some(2)).unwrap();
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].0, 2);
+    assert_eq!(second_page[0].1.sequence, Some(3));
+
+    assert!(suite
+        .query_user_ica_txs(SOLVER_1, None, None)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_response_marks_tx_succeeded() {
+    let mut suite = setup_withdrawal();
+
+    suite.ica_response(1, b"ack").unwrap();
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(ica_tx.status, IcaTxStatus::Succeeded);
+    assert_eq!(ica_tx.ack, Some(Binary::from(b"ack")));
+}
+
+#[test]
+fn test_error_marks_tx_failed() {
+    let mut suite = setup_withdrawal();
+
+    suite.ica_error(1, "insufficient funds").unwrap();
+
+    assert_eq!(
+        suite.query_ica_tx(0).unwrap().status,
+        IcaTxStatus::Failed {
+            error: "insufficient funds".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_timeout_marks_tx_timed_out() {
+    let mut suite = setup_withdrawal();
+
+    suite.ica_timeout(1).unwrap();
+
+    assert_eq!(suite.query_ica_tx(0).unwrap().status, IcaTxStatus::TimedOut);
+}
+
+#[test]
+#[should_panic(expected = "unknown ICA tx: channel-1/2")]
+fn test_callback_validates_sequence() {
+    let mut suite = setup_withdrawal();
+
+    suite.ica_response(2, b"ack").unwrap();
+}
+
+#[test]
+fn test_payout_response_completes_payout() {
+    let mut suite = setup_payout();
+
+    assert_eq!(
+        suite.query_ica_tx(0).unwrap().kind,
+        IcaTxKind::Payout { intent_id: 0 }
+    );
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::new(100)
+    );
+
+    suite.ica_response(1, b"ack").unwrap();
+
+    assert_eq!(
+        suite.query_payout(0).unwrap().status,
+        PayoutStatus::Completed
+    );
+    // paid out funds are no longer locked in the clearing account
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_payout_timeout_fails_payout() {
+    let mut suite = setup_payout();
+
+    suite.ica_timeout(1).unwrap();
+
+    assert_eq!(suite.query_payout(0).unwrap().status, PayoutStatus::Failed);
+    // funds stay locked until the payout is settled
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::new(100)
+    );
+}
+
+#[test]
+fn test_retry_resubmits_failed_payout() {
+    let mut suite = setup_payout();
+    suite.ica_error(1, "insufficient funds").unwrap();
+
+    // anyone may retry the payout on behalf of the solver
+    suite.retry_payout(SOLVER_2, 0).unwrap();
+
+    assert_eq!(
+        suite.query_ica_tx(1).unwrap().kind,
+        IcaTxKind::Payout { intent_id: 0 }
+    );
+    assert_eq!(suite.query_payout(0).unwrap().status, PayoutStatus::Pending);
+
+    suite.ica_response(2, b"ack").unwrap();
+
+    assert_eq!(
+        suite.query_payout(0).unwrap().status,
+        PayoutStatus::Completed
+    );
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_retry_resubmits_payout_which_failed_to_submit() {
+    let mut suite = setup_won_auction();
+    suite.set_ica_failure(Some("channel is closed"));
+    fill_won_auction(&mut suite);
+    suite.set_ica_failure(None);
+
+    suite.retry_payout(SOLVER_1, 0).unwrap();
+
+    assert_eq!(suite.query_payout(0).unwrap().status, PayoutStatus::Pending);
+    assert_eq!(suite.query_ica_tx(1).unwrap().sequence, Some(1));
+}
+
+#[test]
+#[should_panic(expected = "Unexpected payout status: Pending")]
+fn test_retry_rejects_pending_payout() {
+    let mut suite = setup_payout();
+
+    suite.retry_payout(SOLVER_1, 0).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown intent: 0")]
+fn test_retry_rejects_intent_without_payout() {
+    let mut suite = setup_won_auction();
+
+    suite.retry_payout(SOLVER_1, 0).unwrap();
+}
+
+#[test]
+fn test_reopen_resubmits_timed_out_payout() {
+    let mut suite = setup_payout();
+    suite.ica_timeout(1).unwrap();
+
+    suite
+        .reopen_clearing_account(USER_1, GAIA_DOMAIN, coins(ICA_REGISTER_FEE, DENOM_NTRN))
+        .unwrap();
+
+    assert_eq!(
+        suite.query_ica_tx(1).unwrap().kind,
+        IcaTxKind::Payout { intent_id: 0 }
+    );
+    assert_eq!(suite.query_payout(0).unwrap().status, PayoutStatus::Pending);
+
+    suite.ica_response(2, b"ack").unwrap();
+
+    assert_eq!(
+        suite.query_payout(0).unwrap().status,
+        PayoutStatus::Completed
+    );
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_reopen_resubmits_pending_payout() {
+    let mut suite = setup_payout();
+    // the withdrawal times out while the payout is in flight
+    withdraw(&mut suite);
+    suite.ica_timeout(2).unwrap();
+
+    suite
+        .reopen_clearing_account(USER_1, GAIA_DOMAIN, coins(ICA_REGISTER_FEE, DENOM_NTRN))
+        .unwrap();
+
+    let payout = suite.query_payout(0).unwrap();
+    assert_eq!(payout.status, PayoutStatus::Pending);
+    assert_eq!(payout.tx_id, Some(2));
+
+    // the outcome of the payout tx sent over the closed channel is ignored
+    suite.ica_error(1, "channel closed").unwrap();
+    assert_eq!(suite.query_payout(0).unwrap().status, PayoutStatus::Pending);
+
+    suite.ica_response(3, b"ack").unwrap();
+    assert_eq!(
+        suite.query_payout(0).unwrap().status,
+        PayoutStatus::Completed
+    );
+}
+
+#[test]
+fn test_submit_failure_marks_tx_failed() {
+    let mut suite = setup_user_on_domains();
+    suite.set_ica_failure(Some("channel is closed"));
+
+    // the withdrawal goes through, but its tx never leaves orbital
+    withdraw(&mut suite);
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert!(matches!(
+        ica_tx.status,
+        IcaTxStatus::Failed { error } if error.contains("channel is closed")
+    ));
+    assert_eq!(ica_tx.channel, None);
+    assert_eq!(ica_tx.sequence, None);
+}
+
+#[test]
+fn test_payout_submit_failure_fails_payout() {
+    let mut suite = setup_won_auction();
+    suite.set_ica_failure(Some("channel is closed"));
+
+    fill_won_auction(&mut suite);
+
+    assert_eq!(suite.query_payout(0).unwrap().status, PayoutStatus::Failed);
+    // funds stay locked until the payout is settled
+    assert_eq!(
+        suite.query_locked_balance(USER_1, GAIA_DOMAIN, DENOM_ATOM),
+        Uint128::new(100)
+    );
+}
+
+#[test]
+fn test_fill_stands_while_input_clearing_account_is_closed() {
+    let mut suite = setup_won_auction();
+    // the gaia clearing account closes after a withdrawal times out
+    withdraw(&mut suite);
+    suite.ica_timeout(1).unwrap();
+
+    fill_won_auction(&mut suite);
+
+    assert!(matches!(
+        suite.query_intent(0).unwrap().status,
+        IntentStatus::Filled { .. }
+    ));
+    let payout = suite.query_payout(0).unwrap();
+    assert_eq!(payout.status, PayoutStatus::Failed);
+    assert_eq!(payout.tx_id, None);
+
+    // payout is resubmitted once the clearing account is reopened
+    suite
+        .reopen_clearing_account(USER_1, GAIA_DOMAIN, coins(ICA_REGISTER_FEE, DENOM_NTRN))
+        .unwrap();
+
+    assert_eq!(
+        suite.query_ica_tx(1).unwrap().kind,
+        IcaTxKind::Payout { intent_id: 0 }
+    );
+    assert_eq!(suite.query_payout(0).unwrap().status, PayoutStatus::Pending);
+}
+
+#[test]
+fn test_payout_fees_stay_with_contract() {
+    let mut suite = setup_payout();
+    assert_eq!(suite.query_ica_tx(0).unwrap().fee_payer, None);
+
+    let pre_ack_balance = suite.query_balance(&suite.orbital_core, DENOM_NTRN);
+    suite.ica_response(1, b"ack").unwrap();
+
+    // refunded fees of payouts belong to orbital-core, which paid them
+    suite.assert_balance(&suite.orbital_core, pre_ack_balance);
+}
@@ -0,0 +1,173 @@
+use cosmwasm_std::Uint128;
+use neutron_sdk::interchain_queries::{
+    types::{TransactionFilterItem, TransactionFilterOp, TransactionFilterValue},
+    v047::types::RECIPIENT_FIELD,
+};
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::{make_addr, BaseSuite},
+        consts::{DENOM_NTRN, GAIA_DOMAIN, ICQ_DEPOSIT, SOLVER_1, USER_1},
+    },
+    tests::test_orbital_core::suite::{setup_user_on_domains, Suite},
+};
+
+/// returns the id of the transfers query registered for the gaia clearing
+/// account of USER_1 when it opened
+fn user_transfers_query_id(suite: &mut Suite) -> u64 {
+    suite
+        .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .transfers_query_id
+        .unwrap()
+}
+
+fn recipient_filter(recipient: &str) -> Vec<TransactionFilterItem> {
+    vec![TransactionFilterItem {
+        field: RECIPIENT_FIELD.to_string(),
+        op: TransactionFilterOp::Eq,
+        value: TransactionFilterValue::String(recipient.to_string()),
+    }]
+}
+
+#[test]
+fn test_user_updates_update_period_of_own_query() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let query_id = user_transfers_query_id(&mut suite);
+
+    suite
+        .update_interchain_query(&user, query_id, None, Some(20), None)
+        .unwrap();
+
+    assert_eq!(
+        suite
+            .query_registered_query(query_id)
+            .unwrap()
+            .update_period,
+        20
+    );
+}
+
+#[test]
+fn test_owner_updates_filter_of_own_query() {
+    let mut suite = setup_user_on_domains();
+    let owner = suite.owner.clone();
+    let query_id = suite
+        .register_transfers_query(GAIA_DOMAIN, "cosmos1recipient")
+        .unwrap();
+
+    suite
+        .update_interchain_query(
+            &owner,
+            query_id,
+            None,
+            None,
+            Some(recipient_filter("cosmos1other")),
+        )
+        .unwrap();
+
+    let registered_query = suite.query_registered_query(query_id).unwrap();
+    assert!(registered_query
+        .transactions_filter
+        .contains("cosmos1other"));
+    assert_eq!(registered_query.update_period, 5);
+}
+
+#[test]
+#[should_panic(expected = "Only the owner of interchain query")]
+fn test_update_query_validates_owner() {
+    let mut suite = setup_user_on_domains();
+    let solver = make_addr(&suite.app, SOLVER_1);
+    let query_id = user_transfers_query_id(&mut suite);
+
+    suite
+        .update_interchain_query(&solver, query_id, None, Some(20), None)
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Only the update period of interchain query")]
+fn test_user_can_not_update_filter_of_own_query() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let query_id = user_transfers_query_id(&mut suite);
+
+    suite
+        .update_interchain_query(
+            &user,
+            query_id,
+            None,
+            None,
+            Some(recipient_filter("cosmos1other")),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_remove_query_refunds_deposit_to_owner() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let query_id = user_transfers_query_id(&mut suite);
+    let pre_remove_balance = suite.query_balance(&user, DENOM_NTRN).amount;
+
+    suite.remove_interchain_query(&user, query_id).unwrap();
+
+    assert_eq!(
+        suite.query_balance(&user, DENOM_NTRN).amount,
+        pre_remove_balance + Uint128::new(ICQ_DEPOSIT)
+    );
+    assert!(suite.query_registered_query(query_id).is_none());
+    assert!(suite.query_icq_deposit(query_id).is_err());
+}
+
+#[test]
+fn test_remove_query_clears_its_references() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let query_id = user_transfers_query_id(&mut suite);
+    let user_icqs = suite.query_user_icqs(USER_1).unwrap();
+    assert!(user_icqs.iter().any(|(id, _)| *id == query_id));
+
+    suite.remove_interchain_query(&user, query_id).unwrap();
+
+    assert_eq!(
+        suite
+            .query_clearing_account_queries(GAIA_DOMAIN, USER_1)
+            .unwrap()
+            .transfers_query_id,
+        None
+    );
+    assert_eq!(
+        suite.query_user_icqs(USER_1).unwrap().len(),
+        user_icqs.len() - 1
+    );
+}
+
+#[test]
+fn test_owner_removes_own_query() {
+    let mut suite = setup_user_on_domains();
+    let owner = suite.owner.clone();
+    let query_id = suite
+        .register_transfers_query(GAIA_DOMAIN, "cosmos1recipient")
+        .unwrap();
+    let pre_remove_balance = suite.query_balance(&owner, DENOM_NTRN).amount;
+
+    suite.remove_interchain_query(&owner, query_id).unwrap();
+
+    assert_eq!(
+        suite.query_balance(&owner, DENOM_NTRN).amount,
+        pre_remove_balance + Uint128::new(ICQ_DEPOSIT)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only the owner of interchain query")]
+fn test_remove_query_validates_owner() {
+    let mut suite = setup_user_on_domains();
+    let owner = suite.owner.clone();
+    let query_id = user_transfers_query_id(&mut suite);
+
+    // queries of users can not be removed by the contract owner either
+    suite.remove_interchain_query(&owner, query_id).unwrap();
+}
@@ -0,0 +1,216 @@
+use cosmwasm_std::{coins, Uint128};
+
+use orbital_core::state::IcqDeposit;
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::{make_addr, BaseSuite},
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, GAIA_DOMAIN, ICQ_DEPOSIT, OSMOSIS_DOMAIN, SOLVER_1, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{setup_user_on_domains, OrbitalCoreBuilder, Suite},
+};
+
+fn query_clearing_account_addr(suite: &mut Suite, domain: &str) -> String {
+    suite
+        .query_clearing_account(domain, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr
+}
+
+#[test]
+fn test_owner_registers_any_query() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let owner = suite.owner.clone();
+
+    let query_id = suite
+        .register_transfers_query(GAIA_DOMAIN, "cosmos1recipient")
+        .unwrap();
+
+    assert_eq!(
+        suite.query_icq_deposit(query_id).unwrap(),
+        IcqDeposit {
+            payer: owner,
+            deposit: coins(ICQ_DEPOSIT, DENOM_NTRN),
+            addr: "cosmos1recipient".to_string(),
+            denoms: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_user_registers_balances_query_for_own_clearing_account() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let clearing_account = query_clearing_account_addr(&mut suite, GAIA_DOMAIN);
+    let pre_register_balance = suite.query_balance(&user, DENOM_NTRN).amount;
+
+    let query_id = suite
+        .register_balances_query_as(
+            &user,
+            GAIA_DOMAIN,
+            &clearing_account,
+            vec![DENOM_ATOM.to_string()],
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_icq_deposit(query_id).unwrap().payer, user);
+    assert_eq!(
+        suite.query_balance(&user, DENOM_NTRN).amount,
+        pre_register_balance - Uint128::new(ICQ_DEPOSIT)
+    );
+}
+
+#[test]
+fn test_user_registers_transfers_query_for_own_clearing_account() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let clearing_account = query_clearing_account_addr(&mut suite, OSMOSIS_DOMAIN);
+    // the query registered when the account opened is replaced
+    let opening_query_id = suite.query_transfers_query_id(OSMOSIS_DOMAIN, USER_1);
+    suite
+        .remove_interchain_query(&user, opening_query_id)
+        .unwrap();
+
+    let query_id = suite
+        .register_transfers_query_as(
+            &user,
+            OSMOSIS_DOMAIN,
+            &clearing_account,
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_icq_deposit(query_id).unwrap(),
+        IcqDeposit {
+            payer: user,
+            deposit: coins(ICQ_DEPOSIT, DENOM_NTRN),
+            addr: clearing_account,
+            denoms: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_excess_query_deposit_is_refunded() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let clearing_account = query_clearing_account_addr(&mut suite, GAIA_DOMAIN);
+    let pre_register_balance = suite.query_balance(&user, DENOM_NTRN).amount;
+
+    suite
+        .register_balances_query_as(
+            &user,
+            GAIA_DOMAIN,
+            &clearing_account,
+            vec![DENOM_ATOM.to_string()],
+            &coins(ICQ_DEPOSIT + 5_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_balance(&user, DENOM_NTRN).amount,
+        pre_register_balance - Uint128::new(ICQ_DEPOSIT)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Insufficient interchain query deposit, required: 1000000untrn")]
+fn test_register_query_validates_deposit() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let clearing_account = query_clearing_account_addr(&mut suite, GAIA_DOMAIN);
+
+    suite
+        .register_balances_query_as(
+            &user,
+            GAIA_DOMAIN,
+            &clearing_account,
+            vec![DENOM_ATOM.to_string()],
+            &coins(ICQ_DEPOSIT - 1, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to register an interchain query for: cosmos1recipient")]
+fn test_user_can_not_register_query_for_foreign_address() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+
+    suite
+        .register_transfers_query_as(
+            &user,
+            GAIA_DOMAIN,
+            "cosmos1recipient",
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to register an interchain query for")]
+fn test_user_can_not_register_query_on_foreign_connection() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let clearing_account = query_clearing_account_addr(&mut suite, GAIA_DOMAIN);
+
+    suite
+        .register_transfers_query_as(
+            &user,
+            OSMOSIS_DOMAIN,
+            &clearing_account,
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to register an interchain query for")]
+fn test_user_can_not_register_query_for_clearing_account_of_other_user() {
+    let mut suite = setup_user_on_domains();
+    let solver = make_addr(&suite.app, SOLVER_1);
+    let clearing_account = query_clearing_account_addr(&mut suite, GAIA_DOMAIN);
+
+    suite
+        .register_transfers_query_as(
+            &solver,
+            GAIA_DOMAIN,
+            &clearing_account,
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Transfers query already registered for")]
+fn test_user_can_not_register_second_transfers_query() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+    let clearing_account = query_clearing_account_addr(&mut suite, GAIA_DOMAIN);
+
+    suite
+        .register_transfers_query_as(
+            &user,
+            GAIA_DOMAIN,
+            &clearing_account,
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Transfers query already registered for: cosmos1recipient")]
+fn test_owner_can_not_register_second_transfers_query() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    for _ in 0..2 {
+        suite
+            .register_transfers_query(GAIA_DOMAIN, "cosmos1recipient")
+            .unwrap();
+    }
+}
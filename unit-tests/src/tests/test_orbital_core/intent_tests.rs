@@ -0,0 +1,122 @@
+use cosmwasm_std::{coin, coins, Uint64};
+
+use orbital_core::state::IntentStatus;
+
+use crate::{
+    testing_utils::consts::{
+        DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, ICA_REGISTER_FEE, ICQ_DEPOSIT,
+        OSMOSIS_DOMAIN, USER_1,
+    },
+    tests::test_orbital_core::suite::{
+        register_ica_domains, setup_user_on_domains, OrbitalCoreBuilder,
+    },
+};
+
+#[test]
+#[should_panic(expected = "User not registered")]
+fn test_submit_intent_validates_user_registration() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(100, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown domain: juno")]
+fn test_submit_intent_validates_domain_existance() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            "juno",
+            coin(100, "ujuno"),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "User not registered to domain: osmosis")]
+fn test_submit_intent_validates_user_domain_registration() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    register_ica_domains(&mut suite);
+    suite.register_user(USER_1).unwrap();
+    suite
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(100, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Invalid intent: amounts must be non-zero")]
+fn test_submit_intent_validates_amounts() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(0, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(100, DENOM_OSMO),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_submit_intent_happy() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(200, DENOM_OSMO),
+        )
+        .unwrap();
+    suite
+        .submit_intent(
+            USER_1,
+            OSMOSIS_DOMAIN,
+            coin(300, DENOM_OSMO),
+            GAIA_DOMAIN,
+            coin(400, DENOM_ATOM),
+        )
+        .unwrap();
+
+    let intent = suite.query_intent(0).unwrap();
+    assert_eq!(intent.id, Uint64::zero());
+    assert_eq!(intent.input_domain, GAIA_DOMAIN);
+    assert_eq!(intent.input_coin, coin(100, DENOM_ATOM));
+    assert_eq!(intent.destination_domain, OSMOSIS_DOMAIN);
+    assert_eq!(intent.destination_coin, coin(200, DENOM_OSMO));
+    assert_eq!(intent.status, IntentStatus::Open);
+
+    let user_intents = suite.query_user_intents(USER_1).unwrap();
+    assert_eq!(user_intents.len(), 2);
+    assert_eq!(user_intents[1].id, Uint64::one());
+}
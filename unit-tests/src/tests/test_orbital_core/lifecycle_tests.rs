@@ -0,0 +1,147 @@
+use cosmwasm_std::{coin, coins};
+use cw_utils::Expiration;
+
+use orbital_core::state::IntentStatus;
+
+use crate::{
+    testing_utils::consts::{
+        DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, OSMOSIS_DOMAIN, SOLVER_1, USER_1,
+    },
+    tests::test_orbital_core::suite::{
+        setup_open_auction, setup_user_on_domains, setup_won_auction, Suite,
+    },
+};
+
+/// submits an intent for USER_1 which expires `seconds` from now
+fn submit_intent_expiring_in(suite: &mut Suite, seconds: u64) {
+    let deadline = Expiration::AtTime(suite.app.block_info().time.plus_seconds(seconds));
+
+    suite
+        .submit_intent_with_deadline(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+            Some(deadline),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Only the intent owner can perform this action")]
+fn test_cancel_intent_validates_owner() {
+    let mut suite = setup_open_auction();
+
+    suite.cancel_intent(SOLVER_1, 0, None, &[]).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unexpected intent status: Won")]
+fn test_cancel_won_intent() {
+    let mut suite = setup_won_auction();
+
+    suite.cancel_intent(USER_1, 0, None, &[]).unwrap();
+}
+
+#[test]
+fn test_cancel_intent_happy() {
+    let mut suite = setup_open_auction();
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+
+    suite.cancel_intent(USER_1, 0, None, &[]).unwrap();
+
+    assert_eq!(
+        suite.query_intent(0).unwrap().status,
+        IntentStatus::Cancelled
+    );
+
+    // cancelled intents no longer accept bids
+    let err = suite.bid(SOLVER_1, 0, 2_000).unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("Unexpected intent status: Cancelled"));
+}
+
+#[test]
+fn test_cancel_intent_releases_to_destination() {
+    let mut suite = setup_open_auction();
+
+    let response = suite
+        .cancel_intent(
+            USER_1,
+            0,
+            Some("cosmos1release".to_string()),
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert!(response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .any(|attr| attr.key == "release_to" && attr.value == "cosmos1release"));
+    assert_eq!(
+        suite.query_intent(0).unwrap().status,
+        IntentStatus::Cancelled
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid intent: deadline must be in the future")]
+fn test_submit_intent_validates_deadline() {
+    let mut suite = setup_user_on_domains();
+    let now = suite.app.block_info().time;
+
+    suite
+        .submit_intent_with_deadline(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+            Some(Expiration::AtTime(now)),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_intent_expires_after_deadline() {
+    let mut suite = setup_user_on_domains();
+    submit_intent_expiring_in(&mut suite, 100);
+
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Open);
+
+    suite.advance_time(100);
+
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Expired);
+    assert_eq!(
+        suite.query_user_intents(USER_1).unwrap()[0].status,
+        IntentStatus::Expired
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unexpected intent status: Expired")]
+fn test_expired_intent_cannot_be_cancelled() {
+    let mut suite = setup_user_on_domains();
+    submit_intent_expiring_in(&mut suite, 100);
+
+    suite.advance_time(100);
+
+    suite.cancel_intent(USER_1, 0, None, &[]).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unexpected intent status: Expired")]
+fn test_auction_of_expired_intent_cannot_be_closed() {
+    let mut suite = setup_open_auction();
+    submit_intent_expiring_in(&mut suite, 200);
+    suite.bid(SOLVER_1, 1, 1_000).unwrap();
+
+    // auction ends after the intent deadline
+    suite.advance_time(300);
+
+    suite.close_auction(1).unwrap();
+}
@@ -0,0 +1,264 @@
+use cosmwasm_std::{coin, Uint128, Uint64};
+use cw2::{get_contract_version, set_contract_version};
+use cw_utils::Duration;
+
+use orbital_core::{
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    msg::MigrateMsg,
+    state::{
+        ClearingAccountStatus, UserConfig, CLEARING_ACCOUNTS, CLEARING_ACCOUNT_ADDRESSES, CONFIG,
+        INTENT_NONCE, ORBITAL_DOMAINS, SOLVER_ADDRESS_OWNERS, SOLVER_DOMAIN_ADDRESSES,
+        USER_CONFIGS,
+    },
+};
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{DENOM_NTRN, GAIA_DOMAIN, USER_1},
+    },
+    tests::test_orbital_core::suite::{
+        get_attribute, setup_user_on_domains, OrbitalCoreBuilder, Suite,
+    },
+};
+
+/// overwrites the cw2 version stored by orbital core
+fn set_stored_version(suite: &mut Suite, contract: &str, version: &str) {
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    set_contract_version(storage.as_mut(), contract, version).unwrap();
+}
+
+/// seeds the state of the initial release: no config or intent nonce, a gaia
+/// domain without transfer channels or monitored denoms, and USER_1 holding
+/// a clearing account without status under the legacy `gaia0` identifier
+fn setup_baseline_state() -> Suite {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    suite.register_user(USER_1).unwrap();
+    let user = make_addr(&suite.app, USER_1);
+    set_stored_version(&mut suite, CONTRACT_NAME, "0.1.0");
+
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    CONFIG.remove(storage.as_mut());
+    INTENT_NONCE.remove(storage.as_mut());
+    storage.set(
+        &ORBITAL_DOMAINS.key(GAIA_DOMAIN.to_string()),
+        br#"{"interchain_account":{"connection_id":"connection-gaia","channel_id":"channel-gaia","timeout":"100"}}"#,
+    );
+    USER_CONFIGS
+        .save(
+            storage.as_mut(),
+            user.to_string(),
+            &UserConfig {
+                id: Uint64::zero(),
+                registered_domains: vec![GAIA_DOMAIN.to_string()],
+            },
+        )
+        .unwrap();
+    storage.set(
+        &CLEARING_ACCOUNTS.key("gaia0".to_string()),
+        br#"{"addr":"cosmos1baseline","controller_connection_id":"connection-gaia"}"#,
+    );
+    drop(storage);
+
+    suite
+}
+
+#[test]
+fn test_migrate_all_from_baseline() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let config = suite.query_config().unwrap();
+    let mut suite = setup_baseline_state();
+
+    let response = suite
+        .migrate(MigrateMsg::MigrateAll {
+            config: Some(config.clone()),
+        })
+        .unwrap();
+    assert_eq!(get_attribute(&response, "method"), "migrate_all");
+    assert_eq!(get_attribute(&response, "migrated"), "1");
+    assert_eq!(get_attribute(&response, "collisions"), "");
+    assert_eq!(get_attribute(&response, "conflicts"), "");
+
+    assert_eq!(suite.query_config().unwrap(), config);
+    suite.query_domain(GAIA_DOMAIN).unwrap();
+
+    // the legacy clearing account is moved to its current identifier
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.addr, "cosmos1baseline");
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Open);
+
+    let storage = suite.app.contract_storage(&suite.orbital_core);
+    assert_eq!(INTENT_NONCE.load(storage.as_ref()).unwrap(), Uint64::zero());
+    assert!(CLEARING_ACCOUNT_ADDRESSES.has(storage.as_ref(), "cosmos1baseline".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "config must be provided when none is stored")]
+fn test_migrate_all_from_baseline_requires_config() {
+    let mut suite = setup_baseline_state();
+
+    suite
+        .migrate(MigrateMsg::MigrateAll { config: None })
+        .unwrap();
+}
+
+#[test]
+fn test_migrate_all_from_current_version() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .migrate(MigrateMsg::MigrateAll { config: None })
+        .unwrap();
+
+    // state of the migrated contract remains usable
+    suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+}
+
+#[test]
+fn test_migrate_from_older_version() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    set_stored_version(&mut suite, CONTRACT_NAME, "0.0.1");
+
+    let response = suite.migrate(MigrateMsg::UpdateVersion {}).unwrap();
+    assert_eq!(get_attribute(&response, "previous_version"), "0.0.1");
+    assert_eq!(get_attribute(&response, "version"), CONTRACT_VERSION);
+
+    let version =
+        get_contract_version(suite.app.contract_storage(&suite.orbital_core).as_ref()).unwrap();
+    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(version.version, CONTRACT_VERSION);
+}
+
+#[test]
+fn test_migrate_from_current_version() {
+    let mut suite = setup_user_on_domains();
+
+    suite.migrate(MigrateMsg::UpdateVersion {}).unwrap();
+
+    // state of the migrated contract remains usable
+    suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot migrate from newer version")]
+fn test_migrate_refuses_downgrade() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    set_stored_version(&mut suite, CONTRACT_NAME, "99.0.0");
+
+    suite.migrate(MigrateMsg::UpdateVersion {}).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Cannot migrate from crates.io:cw20-base to orbital-core")]
+fn test_migrate_validates_contract_name() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    set_stored_version(&mut suite, "crates.io:cw20-base", "0.0.1");
+
+    suite.migrate(MigrateMsg::UpdateVersion {}).unwrap();
+}
+
+#[test]
+fn test_migrate_update_config() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let mut config = suite.query_config().unwrap();
+    config.solver_bond = coin(2_000_000, DENOM_NTRN);
+    config.min_bid_increment = Uint128::new(5);
+
+    suite
+        .migrate(MigrateMsg::UpdateConfig {
+            config: config.clone(),
+        })
+        .unwrap();
+
+    assert_eq!(suite.query_config().unwrap(), config);
+}
+
+#[test]
+#[should_panic(expected = "solver bond must be non-zero")]
+fn test_migrate_update_config_validates_config() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let mut config = suite.query_config().unwrap();
+    config.solver_bond = coin(0, DENOM_NTRN);
+
+    suite.migrate(MigrateMsg::UpdateConfig { config }).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "unbonding period must exceed auction duration plus fill timeout")]
+fn test_migrate_update_config_validates_unbonding_period() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let mut config = suite.query_config().unwrap();
+    // auctions run for 300 seconds and winners have 600 seconds to fill
+    config.unbonding_period = Duration::Time(900);
+
+    suite.migrate(MigrateMsg::UpdateConfig { config }).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "unbonding period must exceed auction duration plus fill timeout")]
+fn test_migrate_update_config_validates_unbonding_period_unit() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let mut config = suite.query_config().unwrap();
+    config.unbonding_period = Duration::Height(1_000_000);
+
+    suite.migrate(MigrateMsg::UpdateConfig { config }).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "min tx timeout must not exceed max tx timeout")]
+fn test_migrate_update_config_validates_tx_timeout_bounds() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let mut config = suite.query_config().unwrap();
+    config.min_tx_timeout = config.max_tx_timeout + Uint64::one();
+
+    suite.migrate(MigrateMsg::UpdateConfig { config }).unwrap();
+}
+
+#[test]
+fn test_migrate_index_domain_addresses() {
+    let mut suite = setup_user_on_domains();
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+
+    // addresses set before they were indexed, one of them by two solvers
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    CLEARING_ACCOUNT_ADDRESSES.remove(storage.as_mut(), clearing_account.to_string());
+    for solver in ["solver_a", "solver_b"] {
+        SOLVER_DOMAIN_ADDRESSES
+            .save(
+                storage.as_mut(),
+                (solver.to_string(), GAIA_DOMAIN.to_string()),
+                &"cosmos1shared".to_string(),
+            )
+            .unwrap();
+    }
+    drop(storage);
+
+    let response = suite.migrate(MigrateMsg::IndexDomainAddresses {}).unwrap();
+    assert_eq!(get_attribute(&response, "conflicts"), "gaia:cosmos1shared");
+
+    let storage = suite.app.contract_storage(&suite.orbital_core);
+    assert!(CLEARING_ACCOUNT_ADDRESSES.has(storage.as_ref(), clearing_account));
+    assert_eq!(
+        SOLVER_ADDRESS_OWNERS
+            .load(
+                storage.as_ref(),
+                (GAIA_DOMAIN.to_string(), "cosmos1shared".to_string())
+            )
+            .unwrap()
+            .as_str(),
+        "solver_a"
+    );
+}
@@ -1,2 +1,22 @@
+mod auction_tests;
+mod clearing_account_queries_tests;
+mod clearing_account_tests;
+mod clearing_balances_tests;
+mod deposit_tests;
+mod domain_tests;
+mod escrow_tests;
+mod fee_refund_tests;
+mod fill_tests;
+mod ica_identifier_tests;
+mod ica_tests;
+mod icq_lifecycle_tests;
+mod icq_tests;
+mod intent_tests;
+mod lifecycle_tests;
+mod migrate_tests;
+mod polytone_tests;
+mod slash_tests;
+mod solver_tests;
 mod suite;
+mod transfer_tests;
 mod unit_tests;
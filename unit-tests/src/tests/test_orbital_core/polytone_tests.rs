@@ -0,0 +1,385 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Uint64};
+use cw_multi_test::Executor;
+
+use orbital_core::{
+    msg::ExecuteMsg,
+    orbital_domain::UncheckedOrbitalDomainConfig,
+    polytone::{Callback, CallbackMessage, PolytoneCallback},
+    state::{ClearingAccountStatus, IcaTxKind, IcaTxStatus, CLEARING_ACCOUNTS, USER_CONFIGS},
+    utils::{get_ica_identifier, NEUTRON_DOMAIN},
+};
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{DENOM_NTRN, DENOM_STARS, FAUCET, ICQ_DEPOSIT, SOLVER_1, STARGAZE_DOMAIN, USER_1},
+    },
+    tests::test_orbital_core::suite::{OrbitalCoreBuilder, Suite},
+};
+
+/// builds a suite with stargaze registered as a polytone domain and USER_1
+/// holding a clearing account on it. the clearing account is monitored by
+/// a balances query of USER_1 reporting 1_000_000 ustars.
+fn setup_user_on_polytone_domain() -> Suite {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let note = suite.note.to_string();
+
+    suite
+        .register_new_domain(
+            STARGAZE_DOMAIN,
+            UncheckedOrbitalDomainConfig::Polytone {
+                note,
+                timeout: Uint64::new(100),
+                connection_id: Some("connection-stargaze".to_string()),
+                transfer_channels: BTreeMap::from([(
+                    NEUTRON_DOMAIN.to_string(),
+                    "channel-stargaze-neutron".to_string(),
+                )]),
+            },
+        )
+        .unwrap();
+    suite.register_user(USER_1).unwrap();
+    suite
+        .register_user_to_new_domain(USER_1, STARGAZE_DOMAIN, vec![])
+        .unwrap();
+
+    let clearing_account = suite
+        .query_clearing_account(STARGAZE_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let user = make_addr(&suite.app, USER_1);
+    let query_id = suite
+        .register_balances_query_as(
+            &user,
+            STARGAZE_DOMAIN,
+            &clearing_account,
+            vec![DENOM_STARS.to_string()],
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+    suite.set_query_balances(query_id, &clearing_account, coins(1_000_000, DENOM_STARS));
+
+    suite
+}
+
+/// funds the remote proxy serving as the clearing account of USER_1
+fn fund_clearing_account(suite: &mut Suite, amount: u128) {
+    let clearing_account = suite
+        .query_clearing_account(STARGAZE_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let faucet = make_addr(&suite.app, FAUCET);
+
+    suite
+        .app
+        .send_tokens(
+            faucet,
+            Addr::unchecked(clearing_account),
+            &coins(amount, DENOM_STARS),
+        )
+        .unwrap();
+}
+
+fn withdraw(suite: &mut Suite) {
+    suite
+        .withdraw_funds(
+            USER_1,
+            STARGAZE_DOMAIN,
+            coin(100, DENOM_STARS),
+            "stars1dest",
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_register_polytone_clearing_account() {
+    let mut suite = setup_user_on_polytone_domain();
+
+    let clearing_account = suite
+        .query_clearing_account(STARGAZE_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    assert!(!clearing_account.addr.is_empty());
+    assert_eq!(
+        clearing_account.controller_connection_id,
+        suite.note.to_string()
+    );
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Open);
+}
+
+#[test]
+fn test_polytone_clearing_accounts_are_distinct_per_user() {
+    let mut suite = setup_user_on_polytone_domain();
+    suite.register_user(SOLVER_1).unwrap();
+    suite
+        .register_user_to_new_domain(SOLVER_1, STARGAZE_DOMAIN, vec![])
+        .unwrap();
+
+    let user_account = suite
+        .query_clearing_account(STARGAZE_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap();
+    let other_account = suite
+        .query_clearing_account(STARGAZE_DOMAIN, SOLVER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(other_account.status, ClearingAccountStatus::Open);
+    assert_ne!(user_account.addr, other_account.addr);
+}
+
+#[test]
+fn test_polytone_withdrawal_succeeds() {
+    let mut suite = setup_user_on_polytone_domain();
+    fund_clearing_account(&mut suite, 1_000);
+
+    withdraw(&mut suite);
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(ica_tx.domain, STARGAZE_DOMAIN);
+    assert_eq!(ica_tx.kind, IcaTxKind::Withdrawal);
+    assert_eq!(
+        ica_tx.msgs,
+        vec!["/cosmos.bank.v1beta1.MsgSend".to_string()]
+    );
+    assert_eq!(ica_tx.status, IcaTxStatus::Succeeded);
+    assert!(ica_tx.ack.is_some());
+    // polytone executions are not correlated by packet sequence
+    assert_eq!(ica_tx.sequence, None);
+}
+
+#[test]
+#[should_panic(expected = "does no accept funds")]
+fn test_polytone_withdrawal_rejects_funds() {
+    let mut suite = setup_user_on_polytone_domain();
+    fund_clearing_account(&mut suite, 1_000);
+
+    suite
+        .withdraw_funds(
+            USER_1,
+            STARGAZE_DOMAIN,
+            coin(100, DENOM_STARS),
+            "stars1dest",
+            &coins(1_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_polytone_withdrawal_failure_is_recorded() {
+    let mut suite = setup_user_on_polytone_domain();
+
+    // remote proxy holds no funds, so the send fails on the remote domain
+    withdraw(&mut suite);
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert!(matches!(
+        ica_tx.status,
+        IcaTxStatus::Failed { error } if error.contains("insufficient funds")
+    ));
+    assert_eq!(ica_tx.ack, None);
+}
+
+#[test]
+fn test_polytone_batch_withdrawal_succeeds() {
+    let mut suite = setup_user_on_polytone_domain();
+    fund_clearing_account(&mut suite, 1_000);
+
+    suite
+        .batch_withdraw_funds(
+            USER_1,
+            STARGAZE_DOMAIN,
+            vec![
+                (coin(100, DENOM_STARS), "stars1dest"),
+                (coin(200, DENOM_STARS), "stars1other"),
+            ],
+            &[],
+        )
+        .unwrap();
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(
+        ica_tx.msgs,
+        vec!["/cosmos.bank.v1beta1.MsgSend".to_string(); 2]
+    );
+    assert_eq!(ica_tx.status, IcaTxStatus::Succeeded);
+}
+
+#[test]
+fn test_polytone_withdrawal_memo_is_recorded() {
+    let mut suite = setup_user_on_polytone_domain();
+    fund_clearing_account(&mut suite, 1_000);
+
+    suite
+        .withdraw_funds_with_options(
+            USER_1,
+            STARGAZE_DOMAIN,
+            vec![(coin(100, DENOM_STARS), "stars1dest")],
+            Some(60),
+            Some("order 42"),
+            &[],
+        )
+        .unwrap();
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(ica_tx.memo, Some("order 42".to_string()));
+    assert_eq!(ica_tx.status, IcaTxStatus::Succeeded);
+}
+
+#[test]
+fn test_polytone_transfer_succeeds() {
+    let mut suite = setup_user_on_polytone_domain();
+    fund_clearing_account(&mut suite, 1_000);
+
+    suite
+        .transfer_funds(
+            USER_1,
+            STARGAZE_DOMAIN,
+            coin(100, DENOM_STARS),
+            NEUTRON_DOMAIN,
+            "neutron1dest",
+            None,
+            None,
+            &[],
+        )
+        .unwrap();
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(
+        ica_tx.kind,
+        IcaTxKind::Transfer {
+            dest_domain: NEUTRON_DOMAIN.to_string()
+        }
+    );
+    assert_eq!(
+        ica_tx.msgs,
+        vec!["/ibc.applications.transfer.v1.MsgTransfer".to_string()]
+    );
+    assert_eq!(ica_tx.status, IcaTxStatus::Succeeded);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to register an interchain query")]
+fn test_user_query_validates_polytone_connection() {
+    let mut suite = setup_user_on_polytone_domain();
+    let note = suite.note.to_string();
+    suite
+        .update_domain(
+            STARGAZE_DOMAIN,
+            UncheckedOrbitalDomainConfig::Polytone {
+                note,
+                timeout: Uint64::new(100),
+                connection_id: Some("connection-other".to_string()),
+                transfer_channels: BTreeMap::new(),
+            },
+        )
+        .unwrap();
+
+    let clearing_account = suite
+        .query_clearing_account(STARGAZE_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let user = make_addr(&suite.app, USER_1);
+    // the suite queries stargaze over `connection-stargaze`
+    suite
+        .register_balances_query_as(
+            &user,
+            STARGAZE_DOMAIN,
+            &clearing_account,
+            vec![DENOM_STARS.to_string()],
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized polytone callback")]
+fn test_callback_validates_note() {
+    let mut suite = setup_user_on_polytone_domain();
+    let user = make_addr(&suite.app, USER_1);
+
+    suite
+        .app
+        .execute_contract(
+            user,
+            suite.orbital_core.clone(),
+            &ExecuteMsg::Callback(CallbackMessage {
+                initiator: suite.orbital_core.clone(),
+                initiator_msg: to_json_binary(&PolytoneCallback::Tx { id: 0 }).unwrap(),
+                result: Callback::FatalError("timeout".to_string()),
+            }),
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_failed_registration_rolls_back_clearing_account() {
+    let mut suite = setup_user_on_polytone_domain();
+    suite.register_user(SOLVER_1).unwrap();
+    let solver = make_addr(&suite.app, SOLVER_1);
+    let ica_identifier = get_ica_identifier(
+        suite.query_user(SOLVER_1).unwrap().id,
+        STARGAZE_DOMAIN.to_string(),
+    );
+
+    // registration of SOLVER_1 awaits the callback of the note
+    let mut storage = suite.app.contract_storage_mut(&suite.orbital_core);
+    CLEARING_ACCOUNTS
+        .save(storage.as_mut(), ica_identifier.to_string(), &None)
+        .unwrap();
+    let mut user_config = USER_CONFIGS
+        .load(storage.as_ref(), solver.to_string())
+        .unwrap();
+    user_config
+        .registered_domains
+        .push(STARGAZE_DOMAIN.to_string());
+    USER_CONFIGS
+        .save(storage.as_mut(), solver.to_string(), &user_config)
+        .unwrap();
+    drop(storage);
+
+    let note = suite.note.clone();
+    suite
+        .app
+        .execute_contract(
+            note,
+            suite.orbital_core.clone(),
+            &ExecuteMsg::Callback(CallbackMessage {
+                initiator: suite.orbital_core.clone(),
+                initiator_msg: to_json_binary(&PolytoneCallback::RegisterClearingAccount {
+                    ica_identifier,
+                    owner: Some(solver),
+                    domain: Some(STARGAZE_DOMAIN.to_string()),
+                })
+                .unwrap(),
+                result: Callback::FatalError("timeout".to_string()),
+            }),
+            &[],
+        )
+        .unwrap();
+
+    assert!(suite
+        .query_clearing_account(STARGAZE_DOMAIN, SOLVER_1)
+        .is_err());
+    assert!(suite
+        .query_user(SOLVER_1)
+        .unwrap()
+        .registered_domains
+        .is_empty());
+
+    // the user can register to the domain again
+    suite
+        .register_user_to_new_domain(SOLVER_1, STARGAZE_DOMAIN, vec![])
+        .unwrap();
+    let clearing_account = suite
+        .query_clearing_account(STARGAZE_DOMAIN, SOLVER_1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(clearing_account.status, ClearingAccountStatus::Open);
+}
@@ -0,0 +1,156 @@
+use cosmwasm_std::{coin, coins, Decimal, Uint128};
+use cw_utils::Expiration;
+
+use orbital_core::state::IntentStatus;
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::{make_addr, BaseSuite},
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, OSMOSIS_DOMAIN,
+            SLASHED_BOND_USER_SHARE_PERCENT, SOLVER_1, SOLVER_BOND_AMOUNT, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{get_attribute, setup_open_auction, setup_won_auction},
+};
+
+#[test]
+#[should_panic(expected = "Fill deadline not reached")]
+fn test_slash_before_fill_deadline() {
+    let mut suite = setup_won_auction();
+
+    suite.advance_time(599);
+
+    suite.slash_expired(0).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unexpected intent status: Filled")]
+fn test_slash_filled_intent() {
+    let mut suite = setup_won_auction();
+    let recipient = suite
+        .query_clearing_account(OSMOSIS_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+    let query_id = suite.query_transfers_query_id(OSMOSIS_DOMAIN, USER_1);
+    suite
+        .observe_transfer(
+            query_id,
+            &format!("{OSMOSIS_DOMAIN}_{SOLVER_1}"),
+            &recipient,
+            coin(1_000, DENOM_OSMO),
+            42,
+        )
+        .unwrap();
+
+    suite.advance_time(600);
+
+    suite.slash_expired(0).unwrap();
+}
+
+#[test]
+fn test_slash_expired_happy() {
+    let mut suite = setup_won_auction();
+    let user_addr = make_addr(&suite.app, USER_1);
+    let owner_addr = suite.owner.clone();
+    let pre_slash_user_balance = suite.query_balance(&user_addr, DENOM_NTRN);
+    let pre_slash_owner_balance = suite.query_balance(&owner_addr, DENOM_NTRN);
+
+    suite.advance_time(600);
+    suite.slash_expired(0).unwrap();
+
+    // bond is split between the intent owner and the contract owner
+    let user_compensation = Uint128::new(SOLVER_BOND_AMOUNT)
+        .mul_floor(Decimal::percent(SLASHED_BOND_USER_SHARE_PERCENT));
+    let owner_share = Uint128::new(SOLVER_BOND_AMOUNT) - user_compensation;
+    assert_eq!(
+        suite.query_balance(&user_addr, DENOM_NTRN).amount,
+        pre_slash_user_balance.amount + user_compensation
+    );
+    assert_eq!(
+        suite.query_balance(&owner_addr, DENOM_NTRN).amount,
+        pre_slash_owner_balance.amount + owner_share
+    );
+
+    // slashed solver is removed from the registry
+    assert!(suite.query_solver(SOLVER_1).is_err());
+
+    // intent is reopened with a fresh auction
+    assert_eq!(suite.query_intent(0).unwrap().status, IntentStatus::Open);
+    let auction = suite.query_auction(0).unwrap();
+    assert!(auction.highest_bid.is_none());
+    assert!(auction.winner.is_none());
+    assert!(auction.fill_deadline.is_none());
+    assert!(!auction.end.is_expired(&suite.app.block_info()));
+}
+
+#[test]
+fn test_slash_without_owner_compensates_user_fully() {
+    let mut suite = setup_won_auction();
+    let user_addr = make_addr(&suite.app, USER_1);
+    let pre_slash_user_balance = suite.query_balance(&user_addr, DENOM_NTRN);
+    suite.renounce_ownership().unwrap();
+
+    suite.advance_time(600);
+    let response = suite.slash_expired(0).unwrap();
+
+    assert_eq!(
+        suite.query_balance(&user_addr, DENOM_NTRN).amount,
+        pre_slash_user_balance.amount + Uint128::new(SOLVER_BOND_AMOUNT)
+    );
+    assert_eq!(get_attribute(&response, "owner_share"), "0");
+}
+
+#[test]
+fn test_slash_after_intent_deadline() {
+    let mut suite = setup_open_auction();
+    let deadline = Expiration::AtTime(suite.app.block_info().time.plus_seconds(400));
+    suite
+        .submit_intent_with_deadline(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+            Some(deadline),
+        )
+        .unwrap();
+    suite.bid(SOLVER_1, 1, 1_000).unwrap();
+    suite.advance_time(300);
+    suite.close_auction(1).unwrap();
+
+    // intent deadline passes while waiting for the fill
+    suite.advance_time(600);
+    suite.slash_expired(1).unwrap();
+
+    assert_eq!(
+        suite.query_intent(1).unwrap().status,
+        IntentStatus::Slashed {
+            solver: make_addr(&suite.app, SOLVER_1),
+        }
+    );
+}
+
+#[test]
+fn test_slash_keeps_unreserved_bond() {
+    let mut suite = setup_open_auction();
+    suite
+        .increase_solver_bond(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+    suite.advance_time(300);
+    suite.close_auction(0).unwrap();
+
+    suite.advance_time(600);
+    let response = suite.slash_expired(0).unwrap();
+
+    // only the bond reserved by the auction is slashed
+    assert_eq!(
+        get_attribute(&response, "slashed_amount"),
+        SOLVER_BOND_AMOUNT.to_string()
+    );
+    let solver = suite.query_solver(SOLVER_1).unwrap();
+    assert_eq!(solver.bond, Uint128::new(SOLVER_BOND_AMOUNT));
+    assert_eq!(solver.reserved, Uint128::zero());
+}
@@ -0,0 +1,208 @@
+use cosmwasm_std::{coin, coins, Uint128};
+use cw_utils::Expiration;
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::BaseSuite,
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, GAIA_DOMAIN, MIN_BID_INCREMENT, SOLVER_1, SOLVER_2,
+            SOLVER_BOND_AMOUNT, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{setup_open_auction, setup_won_auction, OrbitalCoreBuilder},
+};
+
+#[test]
+#[should_panic(expected = "Insufficient solver bond")]
+fn test_register_solver_validates_bond_amount() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .register_solver(SOLVER_1, coins(SOLVER_BOND_AMOUNT - 1, DENOM_NTRN))
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Must send reserve token 'untrn'")]
+fn test_register_solver_validates_bond_denom() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .register_solver(SOLVER_1, vec![coin(SOLVER_BOND_AMOUNT, DENOM_ATOM)])
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Solver already registered")]
+fn test_register_solver_duplicate() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .register_solver(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+    suite
+        .register_solver(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+}
+
+#[test]
+fn test_register_solver_and_increase_bond_happy() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .register_solver(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+    suite
+        .increase_solver_bond(SOLVER_1, coins(500, DENOM_NTRN))
+        .unwrap();
+
+    let solver = suite.query_solver(SOLVER_1).unwrap();
+    assert_eq!(solver.bond, Uint128::new(SOLVER_BOND_AMOUNT + 500));
+    assert_eq!(solver.unbonding, None);
+}
+
+#[test]
+#[should_panic(expected = "Solver not registered")]
+fn test_increase_bond_validates_solver_registration() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .increase_solver_bond(SOLVER_1, coins(500, DENOM_NTRN))
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "No unbonded funds ready to be claimed")]
+fn test_claim_unbonded_before_release() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .register_solver(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+    suite.unbond_solver(SOLVER_1).unwrap();
+
+    suite.advance_time(60);
+
+    suite.claim_unbonded(SOLVER_1).unwrap();
+}
+
+#[test]
+fn test_unbond_and_claim_happy() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    let solver_addr = suite.app.api().addr_make(SOLVER_1);
+    let pre_bond_balance = suite.query_balance(&solver_addr, DENOM_NTRN);
+
+    suite
+        .register_solver(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+    suite.unbond_solver(SOLVER_1).unwrap();
+
+    let solver = suite.query_solver(SOLVER_1).unwrap();
+    let unbonding = solver.unbonding.unwrap();
+    assert_eq!(solver.bond, Uint128::zero());
+    assert_eq!(unbonding.amount, Uint128::new(SOLVER_BOND_AMOUNT));
+    assert!(matches!(unbonding.release_at, Expiration::AtTime(_)));
+
+    suite.advance_time(86_400);
+    suite.claim_unbonded(SOLVER_1).unwrap();
+
+    // fully unbonded solvers are removed from the registry
+    assert!(suite.query_solver(SOLVER_1).is_err());
+    suite.assert_balance(&solver_addr, pre_bond_balance);
+}
+
+#[test]
+#[should_panic(expected = "Solver bond backs the leading bid or execution rights of intent 0")]
+fn test_unbond_with_leading_bid() {
+    let mut suite = setup_open_auction();
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+
+    suite.unbond_solver(SOLVER_1).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Solver bond backs the leading bid or execution rights of intent 0")]
+fn test_unbond_with_won_auction() {
+    let mut suite = setup_won_auction();
+
+    suite.unbond_solver(SOLVER_1).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Solver bond backs the leading bid or execution rights of intent 0")]
+fn test_claim_unbonded_with_won_auction() {
+    let mut suite = setup_open_auction();
+    // SOLVER_1 rebonds while the previous bond is still unbonding
+    suite.unbond_solver(SOLVER_1).unwrap();
+    suite
+        .increase_solver_bond(SOLVER_1, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+        .unwrap();
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+    suite.advance_time(300);
+    suite.close_auction(0).unwrap();
+
+    // the won auction is neither filled nor slashed by the end of the unbonding
+    suite.advance_time(86_400);
+    suite.claim_unbonded(SOLVER_1).unwrap();
+}
+
+#[test]
+fn test_unbond_after_being_outbid() {
+    let mut suite = setup_open_auction();
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+    suite.bid(SOLVER_2, 0, 1_000 + MIN_BID_INCREMENT).unwrap();
+
+    suite.unbond_solver(SOLVER_1).unwrap();
+
+    let solver = suite.query_solver(SOLVER_1).unwrap();
+    assert_eq!(solver.bond, Uint128::zero());
+    assert_eq!(
+        solver.unbonding.unwrap().amount,
+        Uint128::new(SOLVER_BOND_AMOUNT)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Domain address already in use: gaia_solver_1")]
+fn test_set_domain_address_bound_to_other_solver() {
+    let mut suite = setup_open_auction();
+
+    suite
+        .set_solver_domain_address(SOLVER_2, GAIA_DOMAIN, &format!("{GAIA_DOMAIN}_{SOLVER_1}"))
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Domain address already in use")]
+fn test_set_domain_address_of_clearing_account() {
+    let mut suite = setup_open_auction();
+    let clearing_account = suite
+        .query_clearing_account(GAIA_DOMAIN, USER_1)
+        .unwrap()
+        .unwrap()
+        .addr;
+
+    suite
+        .set_solver_domain_address(SOLVER_1, GAIA_DOMAIN, &clearing_account)
+        .unwrap();
+}
+
+#[test]
+fn test_set_domain_address_releases_previous_address() {
+    let mut suite = setup_open_auction();
+    let previous_addr = format!("{GAIA_DOMAIN}_{SOLVER_1}");
+
+    suite
+        .set_solver_domain_address(SOLVER_1, GAIA_DOMAIN, "gaia_new")
+        .unwrap();
+    suite
+        .set_solver_domain_address(SOLVER_2, GAIA_DOMAIN, &previous_addr)
+        .unwrap();
+
+    assert_eq!(
+        suite
+            .query_solver_domain_address(SOLVER_2, GAIA_DOMAIN)
+            .unwrap(),
+        Some(previous_addr)
+    );
+}
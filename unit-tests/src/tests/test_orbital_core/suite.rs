@@ -1,13 +1,45 @@
-use cosmwasm_std::{Addr, Coin, StdResult};
+use std::collections::BTreeMap;
+
+use cosmos_sdk_proto::{
+    cosmos::{
+        bank::v1beta1::MsgSend,
+        base::v1beta1::Coin as ProtoCoin,
+        tx::v1beta1::{TxBody, TxRaw},
+    },
+    Any,
+};
+use cosmwasm_std::{coin, coins, Addr, Binary, Coin, Decimal, StdResult, Uint128, Uint64};
 use cw_multi_test::{error::AnyResult, AppResponse, Executor};
+use cw_ownable::Action;
+use cw_utils::Expiration;
+use neutron_sdk::{
+    bindings::types::{Height, InterchainQueryResult, KVKey, RegisteredQuery, StorageValue},
+    interchain_queries::{
+        helpers::decode_and_convert, types::TransactionFilterItem,
+        v045::helpers::create_account_denom_balance_key, v047::types::COSMOS_SDK_TRANSFER_MSG_URL,
+    },
+    sudo::msg::{RequestPacket, SudoMsg},
+};
 use orbital_core::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, Withdrawal},
     orbital_domain::UncheckedOrbitalDomainConfig,
-    state::{OrbitalDomainConfig, UserConfig},
+    state::{
+        Auction, ClearingAccountConfig, ClearingAccountQueries, ClearingBalance, Config, IcaTx,
+        IcqDeposit, Intent, OrbitalDomainConfig, Payout, SolverConfig, UserConfig,
+    },
+    utils::NEUTRON_DOMAIN,
 };
+use prost::Message;
 
 use crate::testing_utils::{
-    base_suite_builder::{make_addr, SuiteBuilder},
+    base_suite_builder::{make_addr, BaseSuite, SuiteBuilder},
+    consts::{
+        AUCTION_DURATION, DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, FILL_TIMEOUT, GAIA_DOMAIN,
+        ICA_CHANNEL, ICA_FAILURE, ICA_REGISTER_FEE, ICQ_DEPOSIT, ICQ_FAILURE, MAX_TX_TIMEOUT,
+        MIN_BID_INCREMENT, MIN_TX_TIMEOUT, OSMOSIS_DOMAIN, QUERY_NONCE, QUERY_RESULTS,
+        REGISTERED_QUERIES, SLASHED_BOND_USER_SHARE_PERCENT, SOLVER_1, SOLVER_2,
+        SOLVER_BOND_AMOUNT, SUBMITTED_TX_TIMEOUTS, UNBONDING_PERIOD, USER_1,
+    },
     types::CustomApp,
 };
 
@@ -24,7 +56,17 @@ impl Default for OrbitalCoreBuilder {
 
         Self {
             builder,
-            instantiate_msg: InstantiateMsg { owner },
+            instantiate_msg: InstantiateMsg {
+                owner,
+                solver_bond: coin(SOLVER_BOND_AMOUNT, DENOM_NTRN),
+                unbonding_period: UNBONDING_PERIOD,
+                auction_duration: AUCTION_DURATION,
+                min_bid_increment: Uint128::new(MIN_BID_INCREMENT),
+                fill_timeout: FILL_TIMEOUT,
+                slashed_bond_user_share: Decimal::percent(SLASHED_BOND_USER_SHARE_PERCENT),
+                min_tx_timeout: Uint64::new(MIN_TX_TIMEOUT),
+                max_tx_timeout: Uint64::new(MAX_TX_TIMEOUT),
+            },
         }
     }
 }
@@ -43,7 +85,7 @@ impl OrbitalCoreBuilder {
                 &self.instantiate_msg,
                 &[],
                 "orbital-core",
-                None,
+                Some(owner.to_string()),
             )
             .unwrap();
 
@@ -63,7 +105,20 @@ pub struct Suite {
     pub note: Addr,
 }
 
+impl BaseSuite for Suite {
+    fn get_app(&self) -> &CustomApp {
+        &self.app
+    }
+}
+
 impl Suite {
+    pub fn advance_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += seconds / 5;
+        });
+    }
+
     pub fn register_user(&mut self, user_addr: &str) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             make_addr(&self.app, user_addr),
@@ -89,6 +144,300 @@ impl Suite {
         )
     }
 
+    pub fn reopen_clearing_account(
+        &mut self,
+        user_addr: &str,
+        domain: &str,
+        funds: Vec<Coin>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, user_addr),
+            self.orbital_core.clone(),
+            &ExecuteMsg::ReopenClearingAccount {
+                domain: domain.to_string(),
+            },
+            &funds,
+        )
+    }
+
+    pub fn withdraw_funds(
+        &mut self,
+        user_addr: &str,
+        domain: &str,
+        coin: Coin,
+        dest: &str,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.batch_withdraw_funds(user_addr, domain, vec![(coin, dest)], funds)
+    }
+
+    pub fn batch_withdraw_funds(
+        &mut self,
+        user_addr: &str,
+        domain: &str,
+        withdrawals: Vec<(Coin, &str)>,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.withdraw_funds_with_options(user_addr, domain, withdrawals, None, None, funds)
+    }
+
+    pub fn withdraw_funds_with_options(
+        &mut self,
+        user_addr: &str,
+        domain: &str,
+        withdrawals: Vec<(Coin, &str)>,
+        timeout: Option<u64>,
+        memo: Option<&str>,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, user_addr),
+            self.orbital_core.clone(),
+            &ExecuteMsg::UserWithdrawFunds {
+                domain: domain.to_string(),
+                withdrawals: withdrawals
+                    .into_iter()
+                    .map(|(coin, dest)| Withdrawal {
+                        coin,
+                        dest: dest.to_string(),
+                    })
+                    .collect(),
+                timeout: timeout.map(Uint64::new),
+                memo: memo.map(str::to_string),
+            },
+            funds,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_funds(
+        &mut self,
+        user_addr: &str,
+        domain: &str,
+        coin: Coin,
+        dest_domain: &str,
+        dest: &str,
+        timeout: Option<u64>,
+        memo: Option<&str>,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, user_addr),
+            self.orbital_core.clone(),
+            &ExecuteMsg::UserTransferFunds {
+                domain: domain.to_string(),
+                coin,
+                dest_domain: dest_domain.to_string(),
+                dest: dest.to_string(),
+                timeout: timeout.map(Uint64::new),
+                memo: memo.map(str::to_string),
+            },
+            funds,
+        )
+    }
+
+    pub fn submit_intent(
+        &mut self,
+        user_addr: &str,
+        input_domain: &str,
+        input_coin: Coin,
+        destination_domain: &str,
+        destination_coin: Coin,
+    ) -> AnyResult<AppResponse> {
+        self.submit_intent_with_deadline(
+            user_addr,
+            input_domain,
+            input_coin,
+            destination_domain,
+            destination_coin,
+            None,
+        )
+    }
+
+    pub fn submit_intent_with_deadline(
+        &mut self,
+        user_addr: &str,
+        input_domain: &str,
+        input_coin: Coin,
+        destination_domain: &str,
+        destination_coin: Coin,
+        deadline: Option<Expiration>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, user_addr),
+            self.orbital_core.clone(),
+            &ExecuteMsg::SubmitIntent {
+                input_domain: input_domain.to_string(),
+                input_coin,
+                destination_domain: destination_domain.to_string(),
+                destination_coin,
+                deadline,
+            },
+            &[],
+        )
+    }
+
+    pub fn cancel_intent(
+        &mut self,
+        user_addr: &str,
+        id: u64,
+        release_to: Option<String>,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, user_addr),
+            self.orbital_core.clone(),
+            &ExecuteMsg::CancelIntent { id, release_to },
+            funds,
+        )
+    }
+
+    pub fn query_intent(&mut self, id: u64) -> StdResult<Intent> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.orbital_core.clone(), &QueryMsg::Intent { id })
+    }
+
+    pub fn query_user_intents(&mut self, user: &str) -> StdResult<Vec<Intent>> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::UserIntents {
+                addr: make_addr(&self.app, user).to_string(),
+            },
+        )
+    }
+
+    pub fn bid(&mut self, solver: &str, intent_id: u64, amount: u128) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, solver),
+            self.orbital_core.clone(),
+            &ExecuteMsg::Bid {
+                intent_id,
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+    }
+
+    pub fn close_auction(&mut self, intent_id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::CloseAuction { intent_id },
+            &[],
+        )
+    }
+
+    pub fn slash_expired(&mut self, intent_id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::SlashExpired { intent_id },
+            &[],
+        )
+    }
+
+    pub fn retry_payout(&mut self, caller: &str, intent_id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, caller),
+            self.orbital_core.clone(),
+            &ExecuteMsg::RetryPayout { intent_id },
+            &[],
+        )
+    }
+
+    pub fn query_payout(&mut self, intent_id: u64) -> StdResult<Payout> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.orbital_core.clone(), &QueryMsg::Payout { intent_id })
+    }
+
+    pub fn query_auction(&mut self, intent_id: u64) -> StdResult<Auction> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.orbital_core.clone(), &QueryMsg::Auction { intent_id })
+    }
+
+    pub fn register_solver(&mut self, solver: &str, bond: Vec<Coin>) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, solver),
+            self.orbital_core.clone(),
+            &ExecuteMsg::RegisterSolver {},
+            &bond,
+        )
+    }
+
+    pub fn increase_solver_bond(
+        &mut self,
+        solver: &str,
+        bond: Vec<Coin>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, solver),
+            self.orbital_core.clone(),
+            &ExecuteMsg::IncreaseBond {},
+            &bond,
+        )
+    }
+
+    pub fn unbond_solver(&mut self, solver: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, solver),
+            self.orbital_core.clone(),
+            &ExecuteMsg::UnbondSolver {},
+            &[],
+        )
+    }
+
+    pub fn claim_unbonded(&mut self, solver: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, solver),
+            self.orbital_core.clone(),
+            &ExecuteMsg::ClaimUnbonded {},
+            &[],
+        )
+    }
+
+    pub fn set_solver_domain_address(
+        &mut self,
+        solver: &str,
+        domain: &str,
+        addr: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            make_addr(&self.app, solver),
+            self.orbital_core.clone(),
+            &ExecuteMsg::SetSolverDomainAddress {
+                domain: domain.to_string(),
+                addr: addr.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn query_solver(&mut self, solver: &str) -> StdResult<SolverConfig> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::Solver {
+                addr: make_addr(&self.app, solver).to_string(),
+            },
+        )
+    }
+
+    pub fn query_solver_domain_address(
+        &mut self,
+        solver: &str,
+        domain: &str,
+    ) -> StdResult<Option<String>> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::SolverDomainAddress {
+                addr: make_addr(&self.app, solver).to_string(),
+                domain: domain.to_string(),
+            },
+        )
+    }
+
     pub fn query_domain(&mut self, domain: &str) -> StdResult<OrbitalDomainConfig> {
         self.app.wrap().query_wasm_smart(
             self.orbital_core.clone(),
@@ -107,16 +456,403 @@ impl Suite {
         )
     }
 
-    pub fn _query_clearing_account(&mut self, domain: &str, user: &str) -> StdResult<Option<Addr>> {
+    pub fn query_clearing_account(
+        &mut self,
+        domain: &str,
+        user: &str,
+    ) -> StdResult<Option<ClearingAccountConfig>> {
         self.app.wrap().query_wasm_smart(
             self.orbital_core.clone(),
             &QueryMsg::ClearingAccountAddress {
-                addr: user.to_string(),
+                addr: make_addr(&self.app, user).to_string(),
                 domain: domain.to_string(),
             },
         )
     }
 
+    /// registers a transfers query for the recipient and returns the query id
+    pub fn register_transfers_query(&mut self, domain: &str, recipient: &str) -> AnyResult<u64> {
+        let owner = self.owner.clone();
+        self.register_transfers_query_as(&owner, domain, recipient, &coins(ICQ_DEPOSIT, DENOM_NTRN))
+    }
+
+    /// registers a transfers query for the recipient on behalf of
+    /// the caller and returns the query id
+    pub fn register_transfers_query_as(
+        &mut self,
+        caller: &Addr,
+        domain: &str,
+        recipient: &str,
+        funds: &[Coin],
+    ) -> AnyResult<u64> {
+        self.app.execute_contract(
+            caller.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::RegisterTransfersQuery {
+                connection_id: format!("connection-{domain}"),
+                update_period: 5,
+                recipient: recipient.to_string(),
+                min_height: None,
+            },
+            funds,
+        )?;
+
+        Ok(self.last_query_id())
+    }
+
+    /// registers a balances query for the address and returns the query id
+    pub fn register_balances_query(
+        &mut self,
+        domain: &str,
+        addr: &str,
+        denoms: Vec<String>,
+    ) -> AnyResult<u64> {
+        let owner = self.owner.clone();
+        self.register_balances_query_as(
+            &owner,
+            domain,
+            addr,
+            denoms,
+            &coins(ICQ_DEPOSIT, DENOM_NTRN),
+        )
+    }
+
+    /// registers a balances query for the address on behalf of
+    /// the caller and returns the query id
+    pub fn register_balances_query_as(
+        &mut self,
+        caller: &Addr,
+        domain: &str,
+        addr: &str,
+        denoms: Vec<String>,
+        funds: &[Coin],
+    ) -> AnyResult<u64> {
+        self.app.execute_contract(
+            caller.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::RegisterBalancesQuery {
+                connection_id: format!("connection-{domain}"),
+                update_period: 5,
+                addr: addr.to_string(),
+                denoms,
+            },
+            funds,
+        )?;
+
+        Ok(self.last_query_id())
+    }
+
+    pub fn query_icq_deposit(&mut self, query_id: u64) -> StdResult<IcqDeposit> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::IcqDeposit { query_id },
+        )
+    }
+
+    pub fn query_clearing_account_queries(
+        &mut self,
+        domain: &str,
+        user: &str,
+    ) -> StdResult<ClearingAccountQueries> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::ClearingAccountQueries {
+                addr: make_addr(&self.app, user).to_string(),
+                domain: domain.to_string(),
+            },
+        )
+    }
+
+    /// returns the id of the transfers query registered for the clearing
+    /// account of the user on the domain when it opened
+    pub fn query_transfers_query_id(&mut self, domain: &str, user: &str) -> u64 {
+        self.query_clearing_account_queries(domain, user)
+            .unwrap()
+            .transfers_query_id
+            .unwrap()
+    }
+
+    pub fn update_interchain_query(
+        &mut self,
+        caller: &Addr,
+        query_id: u64,
+        new_keys: Option<Vec<KVKey>>,
+        new_update_period: Option<u64>,
+        new_transactions_filter: Option<Vec<TransactionFilterItem>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            caller.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::UpdateInterchainQuery {
+                query_id,
+                new_keys,
+                new_update_period,
+                new_transactions_filter,
+            },
+            &[],
+        )
+    }
+
+    pub fn remove_interchain_query(
+        &mut self,
+        caller: &Addr,
+        query_id: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            caller.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::RemoveInterchainQuery { query_id },
+            &[],
+        )
+    }
+
+    pub fn query_user_icqs(&mut self, user: &str) -> StdResult<Vec<(u64, IcqDeposit)>> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::UserIcqs {
+                addr: make_addr(&self.app, user).to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+    }
+
+    /// returns the query as registered in the mocked neutron module, if any
+    pub fn query_registered_query(&self, query_id: u64) -> Option<RegisteredQuery> {
+        self.app
+            .read_module(|_, _, storage| REGISTERED_QUERIES.may_load(storage, query_id))
+            .unwrap()
+    }
+
+    /// returns the id of the most recently registered interchain query
+    pub fn last_query_id(&self) -> u64 {
+        self.app
+            .read_module(|_, _, storage| QUERY_NONCE.load(storage))
+            .unwrap()
+    }
+
+    /// stores a balances query result reporting that `addr` holds `balances`
+    /// and notifies orbital-core about it, like a relayer submitting it would
+    pub fn set_query_balances(&mut self, query_id: u64, addr: &str, balances: Vec<Coin>) {
+        let kv_results = balances
+            .into_iter()
+            .map(|balance| StorageValue {
+                storage_prefix: "bank".to_string(),
+                key: Binary::from(
+                    create_account_denom_balance_key(
+                        decode_and_convert(addr).unwrap(),
+                        balance.denom,
+                    )
+                    .unwrap(),
+                ),
+                value: Binary::from(balance.amount.to_string().into_bytes()),
+            })
+            .collect();
+        let height = self.app.block_info().height;
+
+        self.app.init_modules(|_, _, storage| {
+            QUERY_RESULTS
+                .save(
+                    storage,
+                    query_id,
+                    &InterchainQueryResult {
+                        kv_results,
+                        height,
+                        revision: 0,
+                    },
+                )
+                .unwrap()
+        });
+
+        self.app
+            .wasm_sudo(
+                self.orbital_core.clone(),
+                &SudoMsg::KVQueryResult { query_id },
+            )
+            .unwrap();
+    }
+
+    pub fn query_user_balances(
+        &mut self,
+        domain: &str,
+        user: &str,
+    ) -> StdResult<Vec<(String, ClearingBalance)>> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::UserBalances {
+                addr: make_addr(&self.app, user).to_string(),
+                domain: domain.to_string(),
+            },
+        )
+    }
+
+    pub fn query_user_deposits(
+        &mut self,
+        domain: &str,
+        user: &str,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::UserDeposits {
+                addr: make_addr(&self.app, user).to_string(),
+                domain: domain.to_string(),
+            },
+        )
+    }
+
+    pub fn query_locked_balance(&mut self, user: &str, domain: &str, denom: &str) -> Uint128 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.orbital_core.clone(),
+                &QueryMsg::LockedBalance {
+                    addr: make_addr(&self.app, user).to_string(),
+                    domain: domain.to_string(),
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    /// delivers a transfers query result to the contract, containing a single
+    /// bank send of `amount` from `sender` to `recipient` observed at `height`
+    pub fn observe_transfer(
+        &mut self,
+        query_id: u64,
+        sender: &str,
+        recipient: &str,
+        amount: Coin,
+        height: u64,
+    ) -> AnyResult<AppResponse> {
+        self.observe_transfers(query_id, sender, vec![(recipient, amount)], height)
+    }
+
+    /// delivers a transfers query result to the contract, containing a bank
+    /// send from `sender` for each of the (recipient, amount) pairs, observed
+    /// at `height`
+    pub fn observe_transfers(
+        &mut self,
+        query_id: u64,
+        sender: &str,
+        transfers: Vec<(&str, Coin)>,
+        height: u64,
+    ) -> AnyResult<AppResponse> {
+        let tx_body = TxBody {
+            messages: transfers
+                .into_iter()
+                .map(|(recipient, amount)| {
+                    let msg_send = MsgSend {
+                        from_address: sender.to_string(),
+                        to_address: recipient.to_string(),
+                        amount: vec![ProtoCoin {
+                            denom: amount.denom,
+                            amount: amount.amount.to_string(),
+                        }],
+                    };
+                    Any {
+                        type_url: COSMOS_SDK_TRANSFER_MSG_URL.to_string(),
+                        value: msg_send.encode_to_vec(),
+                    }
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let tx_raw = TxRaw {
+            body_bytes: tx_body.encode_to_vec(),
+            ..Default::default()
+        };
+
+        self.app.wasm_sudo(
+            self.orbital_core.clone(),
+            &SudoMsg::TxQueryResult {
+                query_id,
+                height: Height {
+                    revision_number: 0,
+                    revision_height: height,
+                },
+                data: Binary::from(tx_raw.encode_to_vec()),
+            },
+        )
+    }
+
+    pub fn query_ica_tx(&mut self, id: u64) -> StdResult<IcaTx> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.orbital_core.clone(), &QueryMsg::IcaTx { id })
+    }
+
+    pub fn query_user_ica_txs(
+        &mut self,
+        user: &str,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(u64, IcaTx)>> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::UserIcaTxs {
+                addr: make_addr(&self.app, user).to_string(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    /// delivers the acknowledgement of a successfully executed ICA tx packet
+    pub fn ica_response(&mut self, sequence: u64, ack: &[u8]) -> AnyResult<AppResponse> {
+        self.app.wasm_sudo(
+            self.orbital_core.clone(),
+            &SudoMsg::Response {
+                request: ica_request_packet(sequence),
+                data: Binary::from(ack),
+            },
+        )
+    }
+
+    /// delivers the acknowledgement of an ICA tx packet which failed on the remote domain
+    pub fn ica_error(&mut self, sequence: u64, details: &str) -> AnyResult<AppResponse> {
+        self.app.wasm_sudo(
+            self.orbital_core.clone(),
+            &SudoMsg::Error {
+                request: ica_request_packet(sequence),
+                details: details.to_string(),
+            },
+        )
+    }
+
+    /// delivers the timeout of an ICA tx packet
+    pub fn ica_timeout(&mut self, sequence: u64) -> AnyResult<AppResponse> {
+        self.app.wasm_sudo(
+            self.orbital_core.clone(),
+            &SudoMsg::Timeout {
+                request: ica_request_packet(sequence),
+            },
+        )
+    }
+
+    /// timeout in seconds of the ICA tx submitted with the given sequence
+    pub fn query_submitted_tx_timeout(&mut self, sequence: u64) -> u64 {
+        self.app
+            .init_modules(|_, _, storage| SUBMITTED_TX_TIMEOUTS.load(storage, sequence).unwrap())
+    }
+
+    /// makes the mocked neutron module fail ICA registrations and
+    /// tx submissions with the given error until it is cleared
+    pub fn set_ica_failure(&mut self, error: Option<&str>) {
+        self.app.init_modules(|_, _, storage| match error {
+            Some(error) => ICA_FAILURE.save(storage, &error.to_string()).unwrap(),
+            None => ICA_FAILURE.remove(storage),
+        });
+    }
+
+    /// makes the mocked neutron module fail interchain query
+    /// registrations with the given error until it is cleared
+    pub fn set_icq_failure(&mut self, error: Option<&str>) {
+        self.app.init_modules(|_, _, storage| match error {
+            Some(error) => ICQ_FAILURE.save(storage, &error.to_string()).unwrap(),
+            None => ICQ_FAILURE.remove(storage),
+        });
+    }
+
     pub fn register_new_domain(
         &mut self,
         domain: &str,
@@ -132,4 +868,264 @@ impl Suite {
             &[],
         )
     }
+
+    pub fn update_domain(
+        &mut self,
+        domain: &str,
+        account_type: UncheckedOrbitalDomainConfig,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::UpdateDomain {
+                domain: domain.to_string(),
+                account_type,
+            },
+            &[],
+        )
+    }
+
+    pub fn pause_domain(&mut self, domain: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::PauseDomain {
+                domain: domain.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn unpause_domain(&mut self, domain: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::UnpauseDomain {
+                domain: domain.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn deregister_domain(&mut self, domain: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::DeregisterDomain {
+                domain: domain.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn update_tx_timeouts(
+        &mut self,
+        min_tx_timeout: u64,
+        max_tx_timeout: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::UpdateTxTimeouts {
+                min_tx_timeout: Uint64::new(min_tx_timeout),
+                max_tx_timeout: Uint64::new(max_tx_timeout),
+            },
+            &[],
+        )
+    }
+
+    pub fn query_domain_paused(&mut self, domain: &str) -> StdResult<bool> {
+        self.app.wrap().query_wasm_smart(
+            self.orbital_core.clone(),
+            &QueryMsg::DomainPaused {
+                domain: domain.to_string(),
+            },
+        )
+    }
+
+    pub fn query_config(&mut self) -> StdResult<Config> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.orbital_core.clone(), &QueryMsg::Config {})
+    }
+
+    /// migrates orbital core to its current code with the given msg
+    pub fn migrate(&mut self, msg: MigrateMsg) -> AnyResult<AppResponse> {
+        let code_id = self
+            .app
+            .wrap()
+            .query_wasm_contract_info(self.orbital_core.clone())?
+            .code_id;
+
+        self.app
+            .migrate_contract(self.owner.clone(), self.orbital_core.clone(), &msg, code_id)
+    }
+
+    pub fn renounce_ownership(&mut self) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.orbital_core.clone(),
+            &ExecuteMsg::UpdateOwnership(Action::RenounceOwnership),
+            &[],
+        )
+    }
+}
+
+/// request packet sent over the mocked ICA channel with the given sequence
+fn ica_request_packet(sequence: u64) -> RequestPacket {
+    RequestPacket {
+        sequence: Some(sequence),
+        source_port: None,
+        source_channel: Some(ICA_CHANNEL.to_string()),
+        destination_port: None,
+        destination_channel: None,
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
+}
+
+/// returns the value of the first attribute with the given key
+pub fn get_attribute(response: &AppResponse, key: &str) -> String {
+    response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.to_string())
+        .unwrap()
+}
+
+/// registers gaia and osmosis as ICA domains. both domains have a transfer
+/// channel to neutron, only gaia has one to osmosis.
+pub fn register_ica_domains(suite: &mut Suite) {
+    for (domain, dest_domains) in [
+        (GAIA_DOMAIN, vec![NEUTRON_DOMAIN, OSMOSIS_DOMAIN]),
+        (OSMOSIS_DOMAIN, vec![NEUTRON_DOMAIN]),
+    ] {
+        let transfer_channels = dest_domains
+            .into_iter()
+            .map(|dest_domain| {
+                (
+                    dest_domain.to_string(),
+                    format!("channel-{domain}-{dest_domain}"),
+                )
+            })
+            .collect();
+
+        suite
+            .register_new_domain(
+                domain,
+                UncheckedOrbitalDomainConfig::InterchainAccount {
+                    connection_id: format!("connection-{domain}"),
+                    channel_id: format!("channel-{domain}"),
+                    timeout: Uint64::new(100),
+                    transfer_channels,
+                    monitored_denoms: vec![],
+                },
+            )
+            .unwrap();
+    }
+}
+
+/// builds a suite with gaia and osmosis registered as ICA domains
+/// and USER_1 holding a clearing account on both of them. the clearing
+/// accounts are monitored by balances queries of USER_1 reporting
+/// 1_000_000 uatom on gaia and 1_000_000 uosmo on osmosis.
+pub fn setup_user_on_domains() -> Suite {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    register_ica_domains(&mut suite);
+
+    suite.register_user(USER_1).unwrap();
+    let user = make_addr(&suite.app, USER_1);
+    for (domain, denom) in [(GAIA_DOMAIN, DENOM_ATOM), (OSMOSIS_DOMAIN, DENOM_OSMO)] {
+        suite
+            .register_user_to_new_domain(
+                USER_1,
+                domain,
+                coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+            )
+            .unwrap();
+
+        let clearing_account = suite
+            .query_clearing_account(domain, USER_1)
+            .unwrap()
+            .unwrap()
+            .addr;
+        let query_id = suite
+            .register_balances_query_as(
+                &user,
+                domain,
+                &clearing_account,
+                vec![denom.to_string()],
+                &coins(ICQ_DEPOSIT, DENOM_NTRN),
+            )
+            .unwrap();
+        suite.set_query_balances(query_id, &clearing_account, coins(1_000_000, denom));
+    }
+
+    suite
+}
+
+/// builds a suite with gaia registered as an ICA domain whose clearing
+/// accounts are monitored for the given denoms, and USER_1 registered
+pub fn setup_monitored_domain(monitored_denoms: Vec<String>) -> Suite {
+    let mut suite = OrbitalCoreBuilder::default().build();
+    suite
+        .register_new_domain(
+            GAIA_DOMAIN,
+            UncheckedOrbitalDomainConfig::InterchainAccount {
+                connection_id: "connection-gaia".to_string(),
+                channel_id: "channel-gaia".to_string(),
+                timeout: Uint64::new(100),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms,
+            },
+        )
+        .unwrap();
+    suite.register_user(USER_1).unwrap();
+    suite
+}
+
+/// builds a suite with a single open intent (id 0) asking for at least
+/// 1_000 uosmo on osmosis, and two bonded solvers receiving payouts
+/// on gaia at `gaia_{solver}` and filling from `osmosis_{solver}`
+pub fn setup_open_auction() -> Suite {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .submit_intent(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            OSMOSIS_DOMAIN,
+            coin(1_000, DENOM_OSMO),
+        )
+        .unwrap();
+
+    for solver in [SOLVER_1, SOLVER_2] {
+        suite
+            .register_solver(solver, coins(SOLVER_BOND_AMOUNT, DENOM_NTRN))
+            .unwrap();
+        for domain in [GAIA_DOMAIN, OSMOSIS_DOMAIN] {
+            suite
+                .set_solver_domain_address(solver, domain, &format!("{domain}_{solver}"))
+                .unwrap();
+        }
+    }
+
+    suite
+}
+
+/// builds on top of `setup_open_auction` by having SOLVER_1 win
+/// the auction of intent 0 with a bid of 1_000 uosmo. SOLVER_1 fills
+/// from the `osmosis_{SOLVER_1}` address.
+pub fn setup_won_auction() -> Suite {
+    let mut suite = setup_open_auction();
+
+    suite.bid(SOLVER_1, 0, 1_000).unwrap();
+    suite.advance_time(300);
+    suite.close_auction(0).unwrap();
+
+    suite
 }
@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{coin, coins, Uint64};
+use cw_multi_test::{error::AnyResult, AppResponse, Executor};
+
+use orbital_core::{
+    msg::ExecuteMsg,
+    orbital_domain::UncheckedOrbitalDomainConfig,
+    state::{IcaTxKind, IcaTxStatus},
+    utils::NEUTRON_DOMAIN,
+};
+
+use crate::{
+    testing_utils::{
+        base_suite_builder::make_addr,
+        consts::{
+            DENOM_ATOM, DENOM_NTRN, DENOM_OSMO, GAIA_DOMAIN, ICA_CHANNEL, MAX_TX_TIMEOUT,
+            MIN_TX_TIMEOUT, OSMOSIS_DOMAIN, USER_1,
+        },
+    },
+    tests::test_orbital_core::suite::{
+        get_attribute, setup_open_auction, setup_user_on_domains, OrbitalCoreBuilder, Suite,
+    },
+};
+
+fn transfer(suite: &mut Suite, domain: &str, dest_domain: &str) -> AnyResult<AppResponse> {
+    let denom = if domain == GAIA_DOMAIN {
+        DENOM_ATOM
+    } else {
+        DENOM_OSMO
+    };
+
+    suite.transfer_funds(
+        USER_1,
+        domain,
+        coin(100, denom),
+        dest_domain,
+        "dest_addr",
+        None,
+        None,
+        &coins(20_000, DENOM_NTRN),
+    )
+}
+
+#[test]
+fn test_transfer_to_neutron() {
+    let mut suite = setup_user_on_domains();
+
+    let response = transfer(&mut suite, GAIA_DOMAIN, NEUTRON_DOMAIN).unwrap();
+    assert_eq!(get_attribute(&response, "dest_domain"), NEUTRON_DOMAIN);
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(ica_tx.domain, GAIA_DOMAIN);
+    assert_eq!(
+        ica_tx.kind,
+        IcaTxKind::Transfer {
+            dest_domain: NEUTRON_DOMAIN.to_string()
+        }
+    );
+    assert_eq!(
+        ica_tx.msgs,
+        vec!["/ibc.applications.transfer.v1.MsgTransfer".to_string()]
+    );
+    assert_eq!(ica_tx.status, IcaTxStatus::Pending);
+    assert_eq!(ica_tx.channel, Some(ICA_CHANNEL.to_string()));
+}
+
+#[test]
+fn test_transfer_to_orbital_domain() {
+    let mut suite = setup_user_on_domains();
+
+    transfer(&mut suite, GAIA_DOMAIN, OSMOSIS_DOMAIN).unwrap();
+    suite.ica_response(1, b"ack").unwrap();
+
+    let ica_tx = suite.query_ica_tx(0).unwrap();
+    assert_eq!(
+        ica_tx.kind,
+        IcaTxKind::Transfer {
+            dest_domain: OSMOSIS_DOMAIN.to_string()
+        }
+    );
+    assert_eq!(ica_tx.status, IcaTxStatus::Succeeded);
+}
+
+#[test]
+#[should_panic(expected = "No transfer channel from osmosis to gaia")]
+fn test_transfer_validates_transfer_channel() {
+    let mut suite = setup_user_on_domains();
+
+    transfer(&mut suite, OSMOSIS_DOMAIN, GAIA_DOMAIN).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Unknown domain: juno")]
+fn test_transfer_validates_dest_domain() {
+    let mut suite = setup_user_on_domains();
+
+    transfer(&mut suite, GAIA_DOMAIN, "juno").unwrap();
+}
+
+fn transfer_with_timeout(suite: &mut Suite, timeout: u64) -> AnyResult<AppResponse> {
+    suite.transfer_funds(
+        USER_1,
+        GAIA_DOMAIN,
+        coin(100, DENOM_ATOM),
+        NEUTRON_DOMAIN,
+        "dest_addr",
+        Some(timeout),
+        None,
+        &coins(20_000, DENOM_NTRN),
+    )
+}
+
+#[test]
+fn test_transfer_with_timeout() {
+    let mut suite = setup_user_on_domains();
+
+    transfer_with_timeout(&mut suite, MAX_TX_TIMEOUT).unwrap();
+
+    assert_eq!(suite.query_ica_tx(0).unwrap().status, IcaTxStatus::Pending);
+}
+
+#[test]
+fn test_transfer_memo_is_recorded() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .transfer_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            NEUTRON_DOMAIN,
+            "dest_addr",
+            None,
+            Some("order 42"),
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.query_ica_tx(0).unwrap().memo,
+        Some("order 42".to_string())
+    );
+}
+
+#[test]
+#[should_panic(expected = "Tx timeout of 9 seconds outside of bounds [10, 3600]")]
+fn test_transfer_validates_min_timeout() {
+    let mut suite = setup_user_on_domains();
+
+    transfer_with_timeout(&mut suite, MIN_TX_TIMEOUT - 1).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Tx timeout of 3601 seconds outside of bounds [10, 3600]")]
+fn test_transfer_validates_max_timeout() {
+    let mut suite = setup_user_on_domains();
+
+    transfer_with_timeout(&mut suite, MAX_TX_TIMEOUT + 1).unwrap();
+}
+
+#[test]
+fn test_update_tx_timeouts() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .update_tx_timeouts(MIN_TX_TIMEOUT, MAX_TX_TIMEOUT + 100)
+        .unwrap();
+
+    let config = suite.query_config().unwrap();
+    assert_eq!(config.min_tx_timeout, Uint64::new(MIN_TX_TIMEOUT));
+    assert_eq!(config.max_tx_timeout, Uint64::new(MAX_TX_TIMEOUT + 100));
+    transfer_with_timeout(&mut suite, MAX_TX_TIMEOUT + 1).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the contract's current owner")]
+fn test_update_tx_timeouts_validates_owner() {
+    let mut suite = setup_user_on_domains();
+    let user = make_addr(&suite.app, USER_1);
+
+    suite
+        .app
+        .execute_contract(
+            user,
+            suite.orbital_core.clone(),
+            &ExecuteMsg::UpdateTxTimeouts {
+                min_tx_timeout: Uint64::new(MIN_TX_TIMEOUT),
+                max_tx_timeout: Uint64::new(MAX_TX_TIMEOUT + 100),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "min tx timeout must not exceed max tx timeout")]
+fn test_update_tx_timeouts_validates_bounds() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .update_tx_timeouts(MAX_TX_TIMEOUT, MIN_TX_TIMEOUT)
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "insufficient fee coverage")]
+fn test_transfer_validates_fee_coverage() {
+    let mut suite = setup_user_on_domains();
+
+    suite
+        .transfer_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(100, DENOM_ATOM),
+            NEUTRON_DOMAIN,
+            "dest_addr",
+            None,
+            None,
+            &coins(1, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 999900uatom")]
+fn test_transfer_rejects_locked_funds() {
+    let mut suite = setup_open_auction();
+
+    suite
+        .transfer_funds(
+            USER_1,
+            GAIA_DOMAIN,
+            coin(999_901, DENOM_ATOM),
+            NEUTRON_DOMAIN,
+            "dest_addr",
+            None,
+            None,
+            &coins(20_000, DENOM_NTRN),
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Insufficient available balance, available: 400000uatom")]
+fn test_transfer_rejects_pending_outflows() {
+    let mut suite = setup_user_on_domains();
+
+    for _ in 0..2 {
+        suite
+            .transfer_funds(
+                USER_1,
+                GAIA_DOMAIN,
+                coin(600_000, DENOM_ATOM),
+                NEUTRON_DOMAIN,
+                "dest_addr",
+                None,
+                None,
+                &coins(20_000, DENOM_NTRN),
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+#[should_panic(expected = "Domain paused: gaia")]
+fn test_transfer_from_paused_domain() {
+    let mut suite = setup_user_on_domains();
+    suite.pause_domain(GAIA_DOMAIN).unwrap();
+
+    transfer(&mut suite, GAIA_DOMAIN, NEUTRON_DOMAIN).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "invalid transfer channel destination: Juno")]
+fn test_register_domain_validates_transfer_channels() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .register_new_domain(
+            GAIA_DOMAIN,
+            UncheckedOrbitalDomainConfig::InterchainAccount {
+                connection_id: "connection-gaia".to_string(),
+                channel_id: "channel-gaia".to_string(),
+                timeout: Uint64::new(100),
+                transfer_channels: BTreeMap::from([(
+                    "Juno".to_string(),
+                    "channel-gaia-juno".to_string(),
+                )]),
+                monitored_denoms: vec![],
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Invalid domain identifier: neutron is reserved")]
+fn test_register_domain_rejects_neutron() {
+    let mut suite = OrbitalCoreBuilder::default().build();
+
+    suite
+        .register_new_domain(
+            NEUTRON_DOMAIN,
+            UncheckedOrbitalDomainConfig::InterchainAccount {
+                connection_id: "connection-neutron".to_string(),
+                channel_id: "channel-neutron".to_string(),
+                timeout: Uint64::new(100),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms: vec![],
+            },
+        )
+        .unwrap();
+}
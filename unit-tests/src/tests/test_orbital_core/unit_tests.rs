@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{coins, Uint64};
 use cw_multi_test::Executor;
 use cw_ownable::Ownership;
@@ -9,7 +11,9 @@ use orbital_core::{
 };
 
 use crate::{
-    testing_utils::consts::{DENOM_NTRN, GAIA_DOMAIN, OSMOSIS_DOMAIN, USER_1},
+    testing_utils::consts::{
+        DENOM_NTRN, GAIA_DOMAIN, ICA_REGISTER_FEE, ICQ_DEPOSIT, OSMOSIS_DOMAIN, USER_1,
+    },
     tests::test_orbital_core::suite::OrbitalCoreBuilder,
 };
 
@@ -37,6 +41,8 @@ fn test_register_orbital_domain_validates_addr() {
             UncheckedOrbitalDomainConfig::Polytone {
                 note: "invalid_note".to_string(),
                 timeout: Uint64::one(),
+                connection_id: None,
+                transfer_channels: BTreeMap::new(),
             },
         )
         .unwrap();
@@ -53,6 +59,8 @@ fn test_register_duplicate_orbital_domain() {
             UncheckedOrbitalDomainConfig::Polytone {
                 note: suite.note.to_string(),
                 timeout: Uint64::one(),
+                connection_id: None,
+                transfer_channels: BTreeMap::new(),
             },
         )
         .unwrap();
@@ -62,6 +70,8 @@ fn test_register_duplicate_orbital_domain() {
             UncheckedOrbitalDomainConfig::Polytone {
                 note: suite.note.to_string(),
                 timeout: Uint64::one(),
+                connection_id: None,
+                transfer_channels: BTreeMap::new(),
             },
         )
         .unwrap();
@@ -82,6 +92,8 @@ fn test_register_orbital_domain_validates_domain_owner() {
                 account_type: UncheckedOrbitalDomainConfig::Polytone {
                     note: suite.note.to_string(),
                     timeout: Uint64::one(),
+                    connection_id: None,
+                    transfer_channels: BTreeMap::new(),
                 },
             },
             &[],
@@ -101,6 +113,8 @@ fn test_register_orbital_ica_domain_validates_timeout() {
                 connection_id: "connection-id".to_string(),
                 channel_id: "channel-id".to_string(),
                 timeout: Uint64::zero(),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms: vec![],
             },
         )
         .unwrap();
@@ -117,6 +131,8 @@ fn test_register_orbital_polytone_domain_validates_timeout() {
             UncheckedOrbitalDomainConfig::Polytone {
                 note: suite.note.to_string(),
                 timeout: Uint64::zero(),
+                connection_id: None,
+                transfer_channels: BTreeMap::new(),
             },
         )
         .unwrap();
@@ -132,6 +148,8 @@ fn test_register_orbital_domain_happy() {
             UncheckedOrbitalDomainConfig::Polytone {
                 note: suite.note.to_string(),
                 timeout: Uint64::one(),
+                connection_id: None,
+                transfer_channels: BTreeMap::new(),
             },
         )
         .unwrap();
@@ -143,6 +161,8 @@ fn test_register_orbital_domain_happy() {
                 connection_id: "connection-id".to_string(),
                 channel_id: "channel-id".to_string(),
                 timeout: Uint64::one(),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms: vec![],
             },
         )
         .unwrap();
@@ -154,7 +174,9 @@ fn test_register_orbital_domain_happy() {
         polytone_domain
             == OrbitalDomainConfig::Polytone {
                 note: suite.note,
-                timeout: Uint64::one()
+                timeout: Uint64::one(),
+                connection_id: None,
+                transfer_channels: BTreeMap::new(),
             }
     );
 
@@ -163,7 +185,9 @@ fn test_register_orbital_domain_happy() {
             == OrbitalDomainConfig::InterchainAccount {
                 connection_id: "connection-id".to_string(),
                 channel_id: "channel-id".to_string(),
-                timeout: Uint64::one()
+                timeout: Uint64::one(),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms: vec![],
             }
     );
 }
@@ -227,6 +251,8 @@ fn test_register_user_new_ica_domain_asserts_fee_denom() {
                 connection_id: "connection-id".to_string(),
                 channel_id: "channel-id".to_string(),
                 timeout: Uint64::one(),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms: vec![],
             },
         )
         .unwrap();
@@ -248,6 +274,8 @@ fn test_register_user_new_ica_domain_asserts_insufficient_fee() {
                 connection_id: "connection-id".to_string(),
                 channel_id: "channel-id".to_string(),
                 timeout: Uint64::one(),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms: vec![],
             },
         )
         .unwrap();
@@ -268,12 +296,18 @@ fn test_register_user_new_ica_domain_happy() {
                 connection_id: "connection-id".to_string(),
                 channel_id: "channel-id".to_string(),
                 timeout: Uint64::one(),
+                transfer_channels: BTreeMap::new(),
+                monitored_denoms: vec![],
             },
         )
         .unwrap();
 
     suite
-        .register_user_to_new_domain(USER_1, GAIA_DOMAIN, coins(1_000_000, DENOM_NTRN))
+        .register_user_to_new_domain(
+            USER_1,
+            GAIA_DOMAIN,
+            coins(ICA_REGISTER_FEE + ICQ_DEPOSIT, DENOM_NTRN),
+        )
         .unwrap();
 
     let user_config = suite.query_user(USER_1).unwrap();